@@ -15,6 +15,7 @@ pub use sql_generator::ParamsAccess;
 pub use sql_generator::generate_sql;
 pub use sql_generator::generate_bound_sql;
 pub use sql_generator::BoundSql;
+pub use sql_generator::ParameterMapping;
 
 #[cfg(test)]
 mod tests {
@@ -666,17 +667,20 @@ mod tests {
         <resultMap id="userMap" type="User">
             <id property="id" column="user_id"/>
             <result property="name" column="user_name" rustType="String"/>
+            <result property="balance" column="balance" jdbcType="DECIMAL" typeHandler="Money"/>
         </resultMap>
         </mapper>"#;
         let mapper = MyBatisXmlParser::new(xml).parse_mapper().unwrap();
         let rm = mapper.result_maps.get("userMap").unwrap();
         assert_eq!(rm.type_name, "User");
-        assert_eq!(rm.result_columns.len(), 2);
+        assert_eq!(rm.result_columns.len(), 3);
         assert!(rm.result_columns[0].is_id, "<id> 应标记为 id");
         assert_eq!(rm.result_columns[0].property, "id");
         assert_eq!(rm.result_columns[0].column, "user_id");
         assert!(!rm.result_columns[1].is_id, "<result> 不是 id");
         assert_eq!(rm.result_columns[1].rust_type.as_deref(), Some("String"));
+        assert_eq!(rm.result_columns[2].jdbc_type.as_deref(), Some("DECIMAL"));
+        assert_eq!(rm.result_columns[2].type_handler.as_deref(), Some("Money"));
         assert!(rm.associations.is_empty());
         assert!(rm.collections.is_empty());
     }
@@ -877,4 +881,64 @@ mod tests {
         // 缺失参数 .size() → 0，条件不成立
         assert!(!sql_for(xml, "q", &HashMap::new()).contains("WHERE"));
     }
+
+    #[test]
+    fn parse_statement_timeout() {
+        let xml = r#"<mapper namespace="t">
+        <select id="report" timeout="30">SELECT 1</select>
+        <select id="plain">SELECT 1</select>
+        </mapper>"#;
+        let mapper = MyBatisXmlParser::new(xml).parse_mapper().unwrap();
+        assert_eq!(mapper.statements["report"].timeout, Some(30));
+        assert_eq!(mapper.statements["plain"].timeout, None);
+
+        let bad = r#"<mapper namespace="t"><select id="q" timeout="soon">SELECT 1</select></mapper>"#;
+        let err = MyBatisXmlParser::new(bad).parse_mapper().unwrap_err().to_string();
+        assert!(err.contains("timeout"));
+    }
+
+    #[test]
+    fn bound_sql_parameter_mappings() {
+        let xml = r#"<mapper namespace="t">
+        <insert id="ins">
+            INSERT INTO t (id, price, flag, note) VALUES (#{id}, #{price, typeHandler=Money}, #{flag,jdbcType=BOOLEAN}, #{ note , javaType=String })
+        </insert>
+        </mapper>"#;
+
+        let mut params = HashMap::new();
+        params.insert("id".to_string(), Value::Number(1.into()));
+        params.insert("price".to_string(), Value::String("9.90".to_string()));
+        params.insert("flag".to_string(), Value::Bool(true));
+        params.insert("note".to_string(), Value::Null);
+
+        let bound = build_bound(xml, "ins", &params);
+        assert_eq!(bound.sql.trim(), "INSERT INTO t (id, price, flag, note) VALUES (?, ?, ?, ?)");
+        assert_eq!(bound.parameters[1], Value::String("9.90".to_string()));
+        assert_eq!(bound.parameter_mappings.len(), 4);
+        assert!(!bound.parameter_mappings[0].has_type());
+        assert_eq!(bound.parameter_mappings[1].property, "price");
+        assert_eq!(bound.parameter_mappings[1].type_handler.as_deref(), Some("Money"));
+        assert_eq!(bound.parameter_mappings[2].jdbc_type.as_deref(), Some("BOOLEAN"));
+        assert_eq!(bound.parameter_mappings[3].property, "note");
+        assert_eq!(bound.parameter_mappings[3].rust_type.as_deref(), Some("String"));
+    }
+
+    #[test]
+    fn bound_sql_redact_option() {
+        let xml = r#"<mapper namespace="t">
+        <update id="reset">UPDATE t SET password = #{password, redact=true}, hint = #{hint, redact = TRUE, jdbcType=VARCHAR} WHERE id = #{id, redact=false}</update>
+        </mapper>"#;
+
+        let mut params = HashMap::new();
+        params.insert("password".to_string(), Value::String("s3cret".to_string()));
+        params.insert("hint".to_string(), Value::String("pet".to_string()));
+        params.insert("id".to_string(), Value::Number(7.into()));
+
+        let bound = build_bound(xml, "reset", &params);
+        assert_eq!(bound.parameters[0], Value::String("s3cret".to_string()), "绑定值不受影响");
+        let redacted: Vec<bool> = bound.parameter_mappings.iter().map(|m| m.redact).collect();
+        assert_eq!(redacted, [true, true, false]);
+        assert_eq!(bound.parameter_mappings[1].jdbc_type.as_deref(), Some("VARCHAR"));
+        assert!(!bound.parameter_mappings[0].has_type());
+    }
 }
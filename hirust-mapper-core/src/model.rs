@@ -98,6 +98,8 @@ pub struct SqlStatement {
     pub parameters: Vec<String>,
     /// selectKey（主键回填，仅 INSERT/UPDATE）
     pub select_key: Option<SelectKey>,
+    /// 执行超时（秒，对应 `timeout` 属性；`0` 表示不限时，None 表示沿用全局默认）
+    pub timeout: Option<u64>,
 }
 
 /// selectKey 的执行时机
@@ -151,6 +153,8 @@ pub struct ResultColumn {
     pub jdbc_type: Option<String>,
     /// Rust 类型（rustType 属性）
     pub rust_type: Option<String>,
+    /// 类型处理器名（typeHandler 属性，按名称在运行时注册表中查找）
+    pub type_handler: Option<String>,
     /// 是否为 <id>（标记身份，用于 collection 分组）
     pub is_id: bool,
 }
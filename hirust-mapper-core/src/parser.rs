@@ -4,6 +4,9 @@ use quick_xml::events::BytesStart;
 use super::model::*;
 use std::io::Cursor;
 
+/// `<choose>` 解析结果：(`<when>` 条件与内容列表, `<otherwise>` 内容)
+type ChooseBranches = (Vec<(String, Vec<DynamicSqlNode>)>, Option<Vec<DynamicSqlNode>>);
+
 /// MyBatis XML解析器
 pub struct MyBatisXmlParser {
     reader: Reader<Cursor<Vec<u8>>>,
@@ -45,10 +48,10 @@ fn resolve_entity(name: &str) -> String {
                 if let Some(c) = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32) {
                     return c.to_string();
                 }
-            } else if let Some(dec) = other.strip_prefix('#') {
-                if let Some(c) = dec.parse::<u32>().ok().and_then(char::from_u32) {
-                    return c.to_string();
-                }
+            } else if let Some(dec) = other.strip_prefix('#')
+                && let Some(c) = dec.parse::<u32>().ok().and_then(char::from_u32)
+            {
+                return c.to_string();
             }
             format!("&{other};")
         }
@@ -137,11 +140,7 @@ impl MyBatisXmlParser {
                     },
                     _ => {}
                 },
-                Ok(Event::End(e)) => {
-                    if e.name().as_ref() == b"mapper" {
-                        break;
-                    }
-                },
+                Ok(Event::End(e)) if e.name().as_ref() == b"mapper" => break,
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(MapperError::from(e)),
                 _ => {}
@@ -166,6 +165,12 @@ impl MyBatisXmlParser {
                 b"parameterType" => stmt.parameter_type = Some(bytes_to_str(&attr.value)?),
                 b"resultType" => stmt.result_type = Some(bytes_to_str(&attr.value)?),
                 b"resultMap" => stmt.result_map = Some(bytes_to_str(&attr.value)?),
+                b"timeout" => {
+                    let value = bytes_to_str(&attr.value)?;
+                    stmt.timeout = Some(value.trim().parse().map_err(|_| MapperError::ParseError {
+                        message: format!("语句 timeout 属性应为非负整数（秒），实际 '{}'", value),
+                    })?);
+                }
                 _ => {}
             }
         }
@@ -366,7 +371,7 @@ impl MyBatisXmlParser {
     }
 
     /// 解析choose标签内部结构
-    fn parse_choose(&mut self) -> Result<(Vec<(String, Vec<DynamicSqlNode>)>, Option<Vec<DynamicSqlNode>>), MapperError> {
+    fn parse_choose(&mut self) -> Result<ChooseBranches, MapperError> {
         let mut whens = Vec::new();
         let mut otherwise = None;
 
@@ -388,11 +393,7 @@ impl MyBatisXmlParser {
                         self.skip_element()?;
                     }
                 },
-                Ok(Event::End(e)) => {
-                    if e.name().as_ref() == b"choose" {
-                        break;
-                    }
-                },
+                Ok(Event::End(e)) if e.name().as_ref() == b"choose" => break,
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(MapperError::from(e)),
                 _ => {}
@@ -514,6 +515,7 @@ impl MyBatisXmlParser {
                 b"javaType" => column.java_type = Some(bytes_to_str(&attr.value)?),
                 b"jdbcType" => column.jdbc_type = Some(bytes_to_str(&attr.value)?),
                 b"rustType" => column.rust_type = Some(bytes_to_str(&attr.value)?),
+                b"typeHandler" => column.type_handler = Some(bytes_to_str(&attr.value)?),
                 _ => {}
             }
         }
@@ -536,7 +538,7 @@ impl MyBatisXmlParser {
                     }
                     param.push(chars.next().unwrap());
                 }
-                let param_name = param.split(|c| c == ':' || c == ',').next().unwrap_or(&param).trim();
+                let param_name = param.split([':', ',']).next().unwrap_or(&param).trim();
                 if !param_name.is_empty() {
                     params.insert(param_name.to_string());
                 }
@@ -181,7 +181,7 @@ fn evaluate_single(kv: &KeyValue, params: &impl ParamsAccess) -> bool {
                 "=" | "==" => {
                     if kv.value == "null" {
                         // 参数存在且为 JSON null 时，"key == null" 才为 true（对齐 MyBatis OGNL）
-                        return value.is_null();
+                        value.is_null()
                     } else if let Some(b) = value_bool {
                         matches!(*value, Value::Bool(x) if x == b)
                     } else if kv.value.starts_with('\'') && kv.value.ends_with('\'') {
@@ -196,7 +196,7 @@ fn evaluate_single(kv: &KeyValue, params: &impl ParamsAccess) -> bool {
                 "!=" => {
                     if kv.value == "null" {
                         // 参数存在且为 JSON null 时，"key != null" 应为 false（对齐 MyBatis OGNL）
-                        return !value.is_null();
+                        !value.is_null()
                     } else if let Some(b) = value_bool {
                         matches!(*value, Value::Bool(x) if x != b)
                     } else if kv.value.starts_with('\'') && kv.value.ends_with('\'') {
@@ -325,13 +325,13 @@ fn replace_parameters(content: &str, params: &impl ParamsAccess) -> Result<Strin
             Some(Value::Bool(b)) => if *b { "1".to_string() } else { "0".to_string() },
             Some(Value::Null) => "NULL".to_string(),
             Some(v) => serde_json::to_string(v).unwrap_or_else(|_| "NULL".to_string()),
-            None => return format!("/* MISSING:${} */", path),
+            None => format!("/* MISSING:${} */", path),
         }
     }).to_string();
 
-    // 再处理 #{...} — 字符串加引号
+    // 再处理 #{...} — 字符串加引号（忽略 `, jdbcType=...` 等类型选项）
     Ok(PARAM_REGEX.replace_all(&with_dollar, |caps: &regex::Captures| {
-        let path = &caps[1];
+        let path = caps[1].split(',').next().unwrap_or_default().trim();
         match params.get_param(path) {
             Some(Value::String(s)) => {
                 let escaped = s.replace('\'', "''");
@@ -344,7 +344,7 @@ fn replace_parameters(content: &str, params: &impl ParamsAccess) -> Result<Strin
                 let json = serde_json::to_string(v).unwrap_or_else(|_| "NULL".to_string());
                 format!("'{json}'")
             },
-            None => return format!("/* MISSING:#{} */", path),
+            None => format!("/* MISSING:#{} */", path),
         }
     }).to_string())
 }
@@ -422,15 +422,17 @@ pub fn generate_sql<P: ParamsAccess>(node: &DynamicSqlNode, params: &P, mapper:
             sql = strip_overrides(sql,prefix_overrides.as_deref(), None, true);
             sql = strip_overrides(sql,suffix_overrides.as_deref(), None, false);
 
-            if let Some(p) = prefix {
-                if !sql.is_empty() && !p.trim_end().is_empty() {
-                    sql = format!("{} {}", p.trim_end(), sql.trim_start());
-                }
+            if let Some(p) = prefix
+                && !sql.is_empty()
+                && !p.trim_end().is_empty()
+            {
+                sql = format!("{} {}", p.trim_end(), sql.trim_start());
             }
-            if let Some(s) = suffix {
-                if !sql.is_empty() && !s.trim_start().is_empty() {
-                    sql = format!("{} {}", sql.trim_end(), s.trim_start());
-                }
+            if let Some(s) = suffix
+                && !sql.is_empty()
+                && !s.trim_start().is_empty()
+            {
+                sql = format!("{} {}", sql.trim_end(), s.trim_start());
             }
 
             Ok(sql)
@@ -557,6 +559,57 @@ impl Mapper {
 
 // ─── BoundSql 两阶段绑定（Phase 2）──────────────────────────────────
 
+/// `#{...}` 占位符的参数映射：属性路径 + 可选的类型声明
+///
+/// 支持 MyBatis 风格的逗号分隔选项：`#{price, typeHandler=Money}`、
+/// `#{flag, jdbcType=BOOLEAN}`、`#{id, rustType=i64}`（`javaType` 视同 `rustType`）。
+/// `#{password, redact=true}` 把参数标记为敏感值：运行时在 SQL 日志与事件中以掩码代替（不影响实际绑定）。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterMapping {
+    /// 参数属性路径（如 `user.name`）
+    pub property: String,
+    /// JDBC 类型（jdbcType 选项）
+    pub jdbc_type: Option<String>,
+    /// Rust 类型（rustType / javaType 选项）
+    pub rust_type: Option<String>,
+    /// 类型处理器名（typeHandler 选项）
+    pub type_handler: Option<String>,
+    /// 是否为敏感参数（redact 选项）
+    pub redact: bool,
+}
+
+impl ParameterMapping {
+    /// 解析占位符内容（`#{` 与 `}` 之间的文本）；未知选项忽略
+    pub fn parse(expr: &str) -> Self {
+        let mut parts = expr.split(',');
+        let mut mapping = ParameterMapping {
+            property: parts.next().unwrap_or_default().trim().to_string(),
+            ..Default::default()
+        };
+        for part in parts {
+            let Some((key, value)) = part.split_once('=') else { continue };
+            let value = value.trim();
+            if key.trim() == "redact" {
+                mapping.redact = value.eq_ignore_ascii_case("true");
+                continue;
+            }
+            let value = Some(value.to_string());
+            match key.trim() {
+                "jdbcType" => mapping.jdbc_type = value,
+                "rustType" | "javaType" => mapping.rust_type = value,
+                "typeHandler" => mapping.type_handler = value,
+                _ => {}
+            }
+        }
+        mapping
+    }
+
+    /// 是否声明了任何类型信息
+    pub fn has_type(&self) -> bool {
+        self.jdbc_type.is_some() || self.rust_type.is_some() || self.type_handler.is_some()
+    }
+}
+
 /// 绑定后的 SQL：含 `?` 占位符的 SQL 字符串 + 有序参数列表
 ///
 /// 对应两阶段解析的 Phase 2（绑定阶段）输出。`?` 占位符与 `parameters`
@@ -567,12 +620,25 @@ pub struct BoundSql {
     pub sql: String,
     /// 有序参数列表，与 SQL 中 `?` 占位符一一对应（按出现顺序）
     pub parameters: Vec<Value>,
+    /// 参数映射，与 `parameters` 一一对应；手工构造的 BoundSql 可为空（视为无类型声明）
+    pub parameter_mappings: Vec<ParameterMapping>,
 }
 
 impl BoundSql {
     /// 创建一个带初始 SQL 的 BoundSql（参数列表为空）
     pub fn new(sql: String) -> Self {
-        Self { sql, parameters: Vec::new() }
+        Self { sql, ..Default::default() }
+    }
+
+    /// 第 `index` 个参数的映射（无映射时为 `None`）
+    pub fn parameter_mapping(&self, index: usize) -> Option<&ParameterMapping> {
+        self.parameter_mappings.get(index)
+    }
+
+    /// 追加另一段的参数与映射（SQL 文本由调用方拼接）
+    fn append_parameters(&mut self, other: BoundSql) {
+        self.parameters.extend(other.parameters);
+        self.parameter_mappings.extend(other.parameter_mappings);
     }
 
     /// 参数数量
@@ -610,18 +676,20 @@ fn replace_parameters_bound(content: &str, params: &impl ParamsAccess) -> Result
 
     // 再处理 #{...} — 替换为 ? 占位符 + 参数进列表（按出现顺序）
     let mut parameters = Vec::new();
+    let mut parameter_mappings = Vec::new();
     let sql = PARAM_REGEX.replace_all(&with_dollar, |caps: &regex::Captures| {
-        let path = &caps[1];
-        match params.get_param(path) {
+        let mapping = ParameterMapping::parse(&caps[1]);
+        match params.get_param(&mapping.property) {
             Some(value) => {
                 parameters.push(value.clone());
+                parameter_mappings.push(mapping);
                 "?".to_string()
             },
-            None => format!("/* MISSING:#{} */", path),
+            None => format!("/* MISSING:#{} */", mapping.property),
         }
     }).to_string();
 
-    Ok(BoundSql { sql, parameters })
+    Ok(BoundSql { sql, parameters, parameter_mappings })
 }
 
 /// 将节点序列拼接为 [`BoundSql`]，支持 bind 变量注入
@@ -653,7 +721,7 @@ fn join_with_spaces_bound<P: ParamsAccess>(
                         result.sql.push(' ');
                     }
                     result.sql.push_str(&child.sql);
-                    result.parameters.extend(child.parameters);
+                    result.append_parameters(child);
                 }
             },
         }
@@ -715,7 +783,7 @@ pub fn generate_bound_sql<P: ParamsAccess>(
                 }
                 let child = join_with_spaces_bound(contents, &temp, mapper)?;
                 result.sql.push_str(&child.sql);
-                result.parameters.extend(child.parameters);
+                result.append_parameters(child);
             }
 
             result.sql.push_str(close);
@@ -727,15 +795,17 @@ pub fn generate_bound_sql<P: ParamsAccess>(
             bound.sql = strip_overrides(bound.sql, prefix_overrides.as_deref(), None, true);
             bound.sql = strip_overrides(bound.sql, suffix_overrides.as_deref(), None, false);
 
-            if let Some(p) = prefix {
-                if !bound.sql.is_empty() && !p.trim_end().is_empty() {
-                    bound.sql = format!("{} {}", p.trim_end(), bound.sql.trim_start());
-                }
+            if let Some(p) = prefix
+                && !bound.sql.is_empty()
+                && !p.trim_end().is_empty()
+            {
+                bound.sql = format!("{} {}", p.trim_end(), bound.sql.trim_start());
             }
-            if let Some(s) = suffix {
-                if !bound.sql.is_empty() && !s.trim_start().is_empty() {
-                    bound.sql = format!("{} {}", bound.sql.trim_end(), s.trim_start());
-                }
+            if let Some(s) = suffix
+                && !bound.sql.is_empty()
+                && !s.trim_start().is_empty()
+            {
+                bound.sql = format!("{} {}", bound.sql.trim_end(), s.trim_start());
             }
 
             Ok(bound)
@@ -777,7 +847,7 @@ pub fn generate_bound_sql<P: ParamsAccess>(
                     result.sql.push(' ');
                 }
                 result.sql.push_str(&part.sql);
-                result.parameters.extend(part.parameters);
+                result.append_parameters(part);
             }
             Ok(result)
         },
@@ -4,6 +4,11 @@
 //! - `#[dao]` on **`impl`**：遍历带 `#[mapper_query]` 的 async 方法，按签名生成方法体，委托 `SqlSession`。
 //!
 //! 方法名→statement_id、模块路径(`module_path!()`)→namespace、形参名→SQL 参数键、返回类型→select/insert/...。
+//! 返回 `Result<Page<T>>` 时委托 `select_page`，`PageRequest` 类型的形参作为分页参数。
+//! 返回 `Result<HashMap<K, V>>` 时委托 `select_map_with`，键属性由 `#[mapper_query(map_key = "...")]` 指定，
+//! 重复键处理由 `on_duplicate = "error|first|last"` 指定（默认 `error`）。
+//! 返回标量或元组（`Result<i64>` / `Result<Option<String>>` / `Result<Vec<(i64, String)>>`）时
+//! 委托 `select_scalar` / `select_values`，按列位置映射。
 
 use std::path::PathBuf;
 
@@ -156,7 +161,7 @@ fn dao_on_impl(attr: TokenStream, mut item: ItemImpl) -> TokenStream {
             };
             let marker = method.attrs.remove(marker_pos);
             // 解析标记参数
-            let (id_override, kind_arg) = match parse_marker(&marker) {
+            let marker_args = match parse_marker(&marker) {
                 Ok(v) => v,
                 Err(e) => {
                     errors.push(e);
@@ -165,7 +170,7 @@ fn dao_on_impl(attr: TokenStream, mut item: ItemImpl) -> TokenStream {
             };
 
             // 生成方法体
-            match gen_method_body(method, &id_override, kind_arg, &ns_expr, &field_ident, mapper.as_ref()) {
+            match gen_method_body(method, &marker_args, &ns_expr, &field_ident, mapper.as_ref()) {
                 Ok(()) => {}
                 Err(e) => errors.push(e),
             }
@@ -186,16 +191,45 @@ fn dao_on_impl(attr: TokenStream, mut item: ItemImpl) -> TokenStream {
     quote! { #item }.into()
 }
 
-/// 解析 `#[mapper_query(id = "...", kind = "...")]`
-fn parse_marker(attr: &Attribute) -> syn::Result<(Option<String>, Option<Kind>)> {
-    let mut id = None;
-    let mut kind = None;
-    let _ = attr.parse_nested_meta(|meta| {
+/// `#[mapper_query(...)]` 参数
+#[derive(Default)]
+struct MarkerArgs {
+    id: Option<String>,
+    kind: Option<Kind>,
+    /// `HashMap` 返回的键属性
+    map_key: Option<String>,
+    /// `HashMap` 返回的重复键处理（`DuplicateKeyPolicy` 变体名）
+    on_duplicate: Option<proc_macro2::TokenStream>,
+}
+
+/// 解析 `#[mapper_query(id = "...", kind = "...", map_key = "...", on_duplicate = "...")]`（无参数的 `#[mapper_query]` 取默认值）
+fn parse_marker(attr: &Attribute) -> syn::Result<MarkerArgs> {
+    let mut args = MarkerArgs::default();
+    if matches!(attr.meta, syn::Meta::Path(_)) {
+        return Ok(args);
+    }
+    let MarkerArgs { id, kind, map_key, on_duplicate } = &mut args;
+    attr.parse_nested_meta(|meta| {
         if meta.path.is_ident("id") {
-            id = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+            *id = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+        } else if meta.path.is_ident("map_key") {
+            *map_key = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+        } else if meta.path.is_ident("on_duplicate") {
+            let s: syn::LitStr = meta.value()?.parse()?;
+            *on_duplicate = Some(match s.value().as_str() {
+                "error" => quote! { Error },
+                "first" => quote! { FirstWins },
+                "last" => quote! { LastWins },
+                other => {
+                    return Err(syn::Error::new(
+                        s.span(),
+                        format!("非法 on_duplicate '{}'（error/first/last）", other),
+                    ))
+                }
+            });
         } else if meta.path.is_ident("kind") {
             let s: syn::LitStr = meta.value()?.parse()?;
-            kind = Some(match s.value().as_str() {
+            *kind = Some(match s.value().as_str() {
                 "select" => Kind::Select,
                 "insert" => Kind::Insert,
                 "update" => Kind::Update,
@@ -208,25 +242,24 @@ fn parse_marker(attr: &Attribute) -> syn::Result<(Option<String>, Option<Kind>)>
                 }
             });
         } else {
-            return Err(meta.error("未知参数，支持 id / kind"));
+            return Err(meta.error("未知参数，支持 id / kind / map_key / on_duplicate"));
         }
         Ok(())
-    });
-    Ok((id, kind))
+    })?;
+    Ok(args)
 }
 
 /// 生成单个方法体（原地替换 method.block）
 fn gen_method_body(
     method: &mut syn::ImplItemFn,
-    id_override: &Option<String>,
-    kind_arg: Option<Kind>,
+    marker: &MarkerArgs,
     ns_expr: &proc_macro2::TokenStream,
     field: &syn::Ident,
     mapper: Option<&hirust_mapper_core::Mapper>,
 ) -> syn::Result<()> {
     let sig = &method.sig;
     let method_name = sig.ident.to_string();
-    let statement_id = id_override.clone().unwrap_or_else(|| method_name.clone());
+    let statement_id = marker.id.clone().unwrap_or_else(|| method_name.clone());
 
     // 校验：async
     if sig.asyncness.is_none() {
@@ -244,8 +277,9 @@ fn gen_method_body(
         ));
     }
 
-    // 收集参数（跳过 self）
+    // 收集参数（跳过 self）；`PageRequest` 类型的形参作为分页参数，不进入 SQL 参数表
     let mut param_inserts: Vec<proc_macro2::TokenStream> = Vec::new();
+    let mut page_arg: Option<&Pat> = None;
     for arg in sig.inputs.iter().skip(1) {
         match arg {
             FnArg::Typed(pt) => {
@@ -265,6 +299,10 @@ fn gen_method_body(
                     }
                 };
                 let pat = &pt.pat;
+                if last_path_segment(&pt.ty).is_some_and(|seg| seg.ident == "PageRequest") {
+                    page_arg = Some(pat);
+                    continue;
+                }
                 param_inserts.push(quote! {
                     __p.insert(#name.to_string(),
                         ::serde_json::to_value(#pat)
@@ -277,14 +315,18 @@ fn gen_method_body(
 
     // 推断 kind（结合返回类型）
     let inner = extract_result_inner(&sig.output)?;
-    let kind = match (kind_arg, type_shape(inner)) {
+    let scalar = scalar_return(inner);
+    let kind = match (marker.kind, type_shape(inner)) {
         (Some(k), _) => k,
         (None, Shape::Vec) => Kind::Select,
         (None, Shape::Option) => Kind::Select,
+        (None, Shape::Page) => Kind::Select,
+        (None, Shape::HashMap) => Kind::Select,
+        (None, _) if scalar.is_some() => Kind::Select,
         (None, _) => {
             return Err(syn::Error::new_spanned(
                 inner,
-                "无法推断操作类型：写操作须 #[mapper_query(kind=\"insert|update|delete\")]，查询用 Result<Vec<T>> 或 Result<Option<T>>",
+                "无法推断操作类型：写操作须 #[mapper_query(kind=\"insert|update|delete\")]，查询用 Result<Vec<T>> / Result<Option<T>> / Result<Page<T>> / Result<HashMap<K, V>> / Result<标量>",
             ));
         }
     };
@@ -301,14 +343,34 @@ fn gen_method_body(
 
     // 拼装方法体
     let id_lit = statement_id.clone();
-    let body = match kind {
-        Kind::Select => match type_shape(inner) {
+    let body = match (kind, scalar) {
+        (Kind::Select, Some(scalar)) => {
+            let tail = match scalar {
+                ScalarReturn::Many => quote! { __s.select_values(__ns, __id, &__p).await },
+                ScalarReturn::Optional => quote! { __s.select_scalar(__ns, __id, &__p).await },
+                ScalarReturn::One => quote! {
+                    __s.select_scalar(__ns, __id, &__p).await
+                        .and_then(|__o| __o.ok_or_else(|| ::hirust_mapper_runtime::MapperRuntimeError::NoData {
+                            namespace: __ns.into(), id: __id.into()
+                        }))
+                },
+            };
+            quote! {
+                let __ns = #ns_expr;
+                let __id = #id_lit;
+                let mut __p = <::std::collections::HashMap<String, ::serde_json::Value>>::new();
+                #(#param_inserts)*
+                let mut __s = self.#field.current_session().await;
+                #tail
+            }
+        }
+        (Kind::Select, None) => match type_shape(inner) {
             Shape::Vec => quote! {
                 let __ns = #ns_expr;
                 let __id = #id_lit;
                 let mut __p = <::std::collections::HashMap<String, ::serde_json::Value>>::new();
                 #(#param_inserts)*
-                let mut __s = self.#field.open_session();
+                let mut __s = self.#field.current_session().await;
                 __s.select_list(__ns, __id, &__p).await
             },
             Shape::Option => quote! {
@@ -316,9 +378,42 @@ fn gen_method_body(
                 let __id = #id_lit;
                 let mut __p = <::std::collections::HashMap<String, ::serde_json::Value>>::new();
                 #(#param_inserts)*
-                let mut __s = self.#field.open_session();
+                let mut __s = self.#field.current_session().await;
                 __s.select_one(__ns, __id, &__p).await
             },
+            Shape::Page => {
+                let Some(page) = page_arg else {
+                    return Err(syn::Error::new_spanned(
+                        sig.ident.clone(),
+                        "返回 Result<Page<T>> 的方法须有一个 PageRequest 类型的参数",
+                    ));
+                };
+                quote! {
+                    let __ns = #ns_expr;
+                    let __id = #id_lit;
+                    let mut __p = <::std::collections::HashMap<String, ::serde_json::Value>>::new();
+                    #(#param_inserts)*
+                    let mut __s = self.#field.current_session().await;
+                    __s.select_page(__ns, __id, &__p, #page).await
+                }
+            }
+            Shape::HashMap => {
+                let Some(key) = &marker.map_key else {
+                    return Err(syn::Error::new_spanned(
+                        sig.ident.clone(),
+                        "返回 Result<HashMap<K, V>> 的方法须以 #[mapper_query(map_key = \"...\")] 指定键属性",
+                    ));
+                };
+                let policy = marker.on_duplicate.clone().unwrap_or_else(|| quote! { Error });
+                quote! {
+                    let __ns = #ns_expr;
+                    let __id = #id_lit;
+                    let mut __p = <::std::collections::HashMap<String, ::serde_json::Value>>::new();
+                    #(#param_inserts)*
+                    let mut __s = self.#field.current_session().await;
+                    __s.select_map_with(__ns, __id, &__p, #key, ::hirust_mapper_runtime::DuplicateKeyPolicy::#policy).await
+                }
+            }
             _ => {
                 // 裸 T：select_one + 无行报错
                 quote! {
@@ -326,7 +421,7 @@ fn gen_method_body(
                     let __id = #id_lit;
                     let mut __p = <::std::collections::HashMap<String, ::serde_json::Value>>::new();
                     #(#param_inserts)*
-                    let mut __s = self.#field.open_session();
+                    let mut __s = self.#field.current_session().await;
                     __s.select_one(__ns, __id, &__p).await
                         .and_then(|__o| __o.ok_or_else(|| ::hirust_mapper_runtime::MapperRuntimeError::NoData {
                             namespace: __ns.into(), id: __id.into()
@@ -334,9 +429,9 @@ fn gen_method_body(
                 }
             }
         },
-        Kind::Insert => write_body(&id_lit, ns_expr, &param_inserts, field, WriteKind::Insert, type_shape(inner)),
-        Kind::Update => write_body(&id_lit, ns_expr, &param_inserts, field, WriteKind::Update, type_shape(inner)),
-        Kind::Delete => write_body(&id_lit, ns_expr, &param_inserts, field, WriteKind::Delete, type_shape(inner)),
+        (Kind::Insert, _) => write_body(&id_lit, ns_expr, &param_inserts, field, WriteKind::Insert, type_shape(inner)),
+        (Kind::Update, _) => write_body(&id_lit, ns_expr, &param_inserts, field, WriteKind::Update, type_shape(inner)),
+        (Kind::Delete, _) => write_body(&id_lit, ns_expr, &param_inserts, field, WriteKind::Delete, type_shape(inner)),
     };
 
     method.block = syn::parse_quote!({ #body });
@@ -377,7 +472,7 @@ fn write_body(
         let mut __p = <::std::collections::HashMap<String, ::serde_json::Value>>::new();
         #(#param_inserts)*
         let __pv = ::serde_json::Value::Object(__p.into_iter().collect());
-        let mut __s = self.#field.open_session();
+        let mut __s = self.#field.current_session().await;
         #tail
     }
 }
@@ -388,6 +483,8 @@ fn write_body(
 enum Shape {
     Vec,
     Option,
+    Page,
+    HashMap,
     I64,
     OptionI64,
     U64,
@@ -417,12 +514,56 @@ fn type_shape(ty: &Type) -> Shape {
             }
             Shape::Option
         }
+        "Page" => Shape::Page,
+        "HashMap" => Shape::HashMap,
         "i64" => Shape::I64,
         "u64" => Shape::U64,
         _ => Shape::Other,
     }
 }
 
+/// 标量 / 元组返回的形态
+enum ScalarReturn {
+    /// `T`：无行报 `NoData`
+    One,
+    /// `Option<T>`
+    Optional,
+    /// `Vec<T>`
+    Many,
+}
+
+/// 识别按列位置映射的返回类型：基本类型、`String`、`rust_decimal::Decimal`（须写全路径，
+/// 避免同名的用户类型被当作标量）与非空元组，及其 `Option` / `Vec`
+fn scalar_return(ty: &Type) -> Option<ScalarReturn> {
+    if is_scalar(ty) {
+        return Some(ScalarReturn::One);
+    }
+    let seg = last_path_segment(ty)?;
+    let arg = first_generic_arg(seg)?;
+    match seg.ident.to_string().as_str() {
+        "Option" if is_scalar(arg) => Some(ScalarReturn::Optional),
+        "Vec" if is_scalar(arg) => Some(ScalarReturn::Many),
+        _ => None,
+    }
+}
+
+fn is_scalar(ty: &Type) -> bool {
+    match ty {
+        Type::Tuple(t) => !t.elems.is_empty(),
+        Type::Path(p) if p.path.segments.last().is_some_and(|s| s.arguments.is_none()) => {
+            let segments = &p.path.segments;
+            let ident = segments.last().map(|s| s.ident.to_string()).unwrap_or_default();
+            match ident.as_str() {
+                "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "usize" | "isize" | "f32" | "f64"
+                | "bool" | "String" => true,
+                "Decimal" => segments.len() == 2 && segments[0].ident == "rust_decimal",
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
 fn last_path_segment(ty: &Type) -> Option<&syn::PathSegment> {
     match ty {
         Type::Path(p) => p.path.segments.last(),
@@ -485,3 +626,41 @@ fn load_mapper(xml_path: &str) -> syn::Result<hirust_mapper_core::Mapper> {
         )
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_marker() {
+        let args = parse_marker(&syn::parse_quote!(#[mapper_query])).unwrap();
+        assert!(args.id.is_none() && args.kind.is_none() && args.map_key.is_none());
+
+        let args = parse_marker(&syn::parse_quote!(
+            #[mapper_query(id = "findAll", map_key = "id", on_duplicate = "first")]
+        ))
+        .unwrap();
+        assert_eq!(args.id.as_deref(), Some("findAll"));
+        assert_eq!(args.map_key.as_deref(), Some("id"));
+        assert_eq!(args.on_duplicate.unwrap().to_string(), "FirstWins");
+    }
+
+    #[test]
+    fn test_scalar_return_decimal_requires_path() {
+        let scalar = |ty: Type| scalar_return(&ty);
+        assert!(matches!(scalar(syn::parse_quote!(rust_decimal::Decimal)), Some(ScalarReturn::One)));
+        assert!(matches!(scalar(syn::parse_quote!(Option<rust_decimal::Decimal>)), Some(ScalarReturn::Optional)));
+        assert!(scalar(syn::parse_quote!(Decimal)).is_none(), "同名的用户类型不视为标量");
+        assert!(scalar(syn::parse_quote!(Vec<Decimal>)).is_none());
+        assert!(matches!(scalar(syn::parse_quote!(Vec<(i64, String)>)), Some(ScalarReturn::Many)));
+    }
+
+    #[test]
+    fn test_parse_marker_rejects_invalid_args() {
+        let err = |attr: Attribute| parse_marker(&attr).err().expect("应报错").to_string();
+        assert!(err(syn::parse_quote!(#[mapper_query(on_duplicate = "frist")])).contains("非法 on_duplicate 'frist'"));
+        assert!(err(syn::parse_quote!(#[mapper_query(map_key = id)])).contains("expected string literal"));
+        assert!(err(syn::parse_quote!(#[mapper_query(kind = "upsert")])).contains("非法 kind 'upsert'"));
+        assert!(err(syn::parse_quote!(#[mapper_query(name = "x")])).contains("未知参数"));
+    }
+}
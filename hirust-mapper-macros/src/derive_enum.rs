@@ -0,0 +1,97 @@
+//! `#[derive(MapperEnum)]` 派生宏实现
+//!
+//! 为单元变体枚举生成 `MapperEnum` 实现（变体名 ↔ 序号对照表），
+//! 供 `EnumStringHandler` / `EnumOrdinalHandler` 使用。
+//!
+//! - 变体名默认为标识符，可用 `#[mapper(name = "...")]` 覆盖（须与 serde 表示一致）
+//! - 序号依次取 `#[mapper(ordinal = N)]`、显式判别值，否则为上一变体序号 + 1（首个为 0）
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Expr, LitStr};
+
+/// 派生宏入口
+pub fn derive_mapper_enum_impl(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // 仅支持枚举
+    let data = match &input.data {
+        syn::Data::Enum(e) => e,
+        _ => return Err(syn::Error::new_spanned(input, "MapperEnum 仅支持 enum")),
+    };
+
+    let mut variants = Vec::new();
+    let mut next_ordinal: i64 = 0;
+    for variant in &data.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new_spanned(variant, "MapperEnum 仅支持无字段的单元变体"));
+        }
+
+        let mut variant_name = variant.ident.to_string();
+        let mut ordinal: Option<i64> = None;
+        for attr in &variant.attrs {
+            if !attr.path().is_ident("mapper") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let s: LitStr = meta.value()?.parse()?;
+                    variant_name = s.value();
+                } else if meta.path.is_ident("ordinal") {
+                    let expr: Expr = meta.value()?.parse()?;
+                    ordinal = Some(int_literal(&expr)?);
+                } else {
+                    return Err(meta.error("未知属性，支持 `name` 与 `ordinal`"));
+                }
+                Ok(())
+            })?;
+        }
+        let ordinal = match (ordinal, &variant.discriminant) {
+            (Some(o), _) => o,
+            (None, Some((_, expr))) => int_literal(expr)?,
+            (None, None) => next_ordinal,
+        };
+        if variants.iter().any(|(n, _)| *n == variant_name) {
+            return Err(syn::Error::new_spanned(variant, format!("变体名 '{}' 重复", variant_name)));
+        }
+        if variants.iter().any(|(_, o)| *o == ordinal) {
+            return Err(syn::Error::new_spanned(variant, format!("序号 {} 重复", ordinal)));
+        }
+        next_ordinal = ordinal.wrapping_add(1);
+        variants.push((variant_name, ordinal));
+    }
+
+    let type_name = name.to_string();
+    let entries = variants.iter().map(|(n, o)| quote! { (#n, #o) });
+    Ok(quote! {
+        impl #impl_generics ::hirust_mapper_runtime::MapperEnum for #name #ty_generics #where_clause {
+            const TYPE_NAME: &'static str = #type_name;
+            const VARIANTS: &'static [(&'static str, i64)] = &[#(#entries),*];
+        }
+    })
+}
+
+/// 整数字面量（可带负号）→ i64；其它表达式无法在宏展开期求值
+fn int_literal(expr: &Expr) -> syn::Result<i64> {
+    match expr {
+        Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }) => i.base10_parse(),
+        Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr: inner, .. }) => {
+            int_literal(inner).map(|v| -v)
+        }
+        Expr::Group(g) => int_literal(&g.expr),
+        Expr::Paren(p) => int_literal(&p.expr),
+        _ => Err(syn::Error::new_spanned(
+            expr,
+            "序号须为整数字面量，请改用 `#[mapper(ordinal = N)]` 声明",
+        )),
+    }
+}
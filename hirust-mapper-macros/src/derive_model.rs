@@ -1,7 +1,9 @@
 //! `#[derive(MapperModel)]` 派生宏实现
 //!
-//! 解析字段上的 `#[mapper(column = "...", type_handler = "...")]` 属性，
-//! 生成 `column_mappings()` 内省方法（字段名 → 列名），供高级映射场景使用。
+//! 解析字段上的 `#[mapper(column = "...", type_handler = "...", redact)]` 属性，
+//! 生成 `column_mappings()` 内省方法（字段名 → 列名），并实现运行时的 `MapperModel` trait——
+//! 经 `SqlSessionFactory::builder(..).model::<T>()` 注册后，`resultType` 为该类型的查询
+//! 按注解改名列、经 `type_handler` 声明的处理器解码列；`redact` 字段名作为敏感参数名在 SQL 日志与事件中打码。
 
 use proc_macro::TokenStream;
 use quote::quote;
@@ -26,6 +28,7 @@ pub fn derive_mapper_model_impl(input: TokenStream) -> TokenStream {
     // 收集 (字段名, 列名, 可选 type_handler)
     let mut mappings = Vec::new();
     let mut handlers = Vec::new();
+    let mut redacted = Vec::new();
     for field in fields.iter() {
         let field_ident = match &field.ident {
             Some(i) => i,
@@ -48,8 +51,10 @@ pub fn derive_mapper_model_impl(input: TokenStream) -> TokenStream {
                     let v = meta.value()?;
                     let s: LitStr = v.parse()?;
                     type_handler = Some(s.value());
+                } else if meta.path.is_ident("redact") {
+                    redacted.push(field_name.clone());
                 } else {
-                    return Err(meta.error("未知属性，支持 `column` 与 `type_handler`"));
+                    return Err(meta.error("未知属性，支持 `column`、`type_handler` 与 `redact`"));
                 }
                 Ok(())
             });
@@ -61,6 +66,7 @@ pub fn derive_mapper_model_impl(input: TokenStream) -> TokenStream {
         }
     }
 
+    let type_name = name.to_string();
     let expanded = quote! {
         impl #impl_generics #name #ty_generics #where_clause {
             /// 字段名 → 列名映射（未声明 column 时默认与字段名相同）
@@ -72,6 +78,27 @@ pub fn derive_mapper_model_impl(input: TokenStream) -> TokenStream {
             pub fn type_handlers() -> &'static [(&'static str, &'static str)] {
                 &[#(#handlers),*]
             }
+
+            /// 声明了 redact 的字段（敏感参数名）
+            pub fn redacted_fields() -> &'static [&'static str] {
+                &[#(#redacted),*]
+            }
+        }
+
+        impl #impl_generics ::hirust_mapper_runtime::MapperModel for #name #ty_generics #where_clause {
+            const TYPE_NAME: &'static str = #type_name;
+
+            fn column_mappings() -> &'static [(&'static str, &'static str)] {
+                <Self>::column_mappings()
+            }
+
+            fn type_handlers() -> &'static [(&'static str, &'static str)] {
+                <Self>::type_handlers()
+            }
+
+            fn redacted_fields() -> &'static [&'static str] {
+                <Self>::redacted_fields()
+            }
         }
     };
     expanded.into()
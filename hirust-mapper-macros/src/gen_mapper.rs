@@ -109,7 +109,7 @@ pub fn gen_mapper_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                 where
                     T: ::serde::de::DeserializeOwned + Send,
                 {
-                    let mut session = self.__hm_factory.open_session();
+                    let mut session = self.__hm_factory.current_session().await;
                     session.select_list(#ns_lit, #id_lit, params).await
                 }
             },
@@ -119,7 +119,7 @@ pub fn gen_mapper_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                     &self,
                     params: &T,
                 ) -> ::hirust_mapper_runtime::Result<::std::option::Option<i64>> {
-                    let mut session = self.__hm_factory.open_session();
+                    let mut session = self.__hm_factory.current_session().await;
                     session.insert(#ns_lit, #id_lit, params).await
                 }
             },
@@ -129,7 +129,7 @@ pub fn gen_mapper_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                     &self,
                     params: &T,
                 ) -> ::hirust_mapper_runtime::Result<u64> {
-                    let mut session = self.__hm_factory.open_session();
+                    let mut session = self.__hm_factory.current_session().await;
                     session.update(#ns_lit, #id_lit, params).await
                 }
             },
@@ -139,7 +139,7 @@ pub fn gen_mapper_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
                     &self,
                     params: &T,
                 ) -> ::hirust_mapper_runtime::Result<u64> {
-                    let mut session = self.__hm_factory.open_session();
+                    let mut session = self.__hm_factory.current_session().await;
                     session.delete(#ns_lit, #id_lit, params).await
                 }
             },
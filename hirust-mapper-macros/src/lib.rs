@@ -4,8 +4,10 @@
 //!
 //! - `#[hirust_mapper(xml = "...")]` — 编译时加载并解析 mapper XML，校验合法性，
 //!   为每个语句生成类型化方法（委托 `SqlSession`）。
-//! - `#[derive(MapperModel)]` — 解析 `#[mapper(column, type_handler)]` 属性，
+//! - `#[derive(MapperModel)]` — 解析 `#[mapper(column, type_handler, redact)]` 属性，
 //!   生成列映射内省方法。
+//! - `#[derive(MapperEnum)]` — 为单元变体枚举生成名称 ↔ 序号对照表，
+//!   配合 `EnumStringHandler` / `EnumOrdinalHandler` 读写枚举列。
 //! - `#[dao]` + `#[mapper_query]` — 签名驱动的类型化 DAO：方法名→statement_id、
 //!   模块路径→namespace、形参名→SQL 参数键、返回类型→select/insert/...。
 
@@ -16,6 +18,7 @@
 #![allow(linker_messages)]
 
 mod dao;
+mod derive_enum;
 mod derive_model;
 mod gen_mapper;
 
@@ -41,8 +44,10 @@ pub fn hirust_mapper(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// 自动行映射模型派生。
 ///
-/// 解析字段上的 `#[mapper(column = "...", type_handler = "...")]` 属性，
-/// 生成 `column_mappings()` 与 `type_handlers()` 内省方法。
+/// 解析字段上的 `#[mapper(column = "...", type_handler = "...", redact)]` 属性，
+/// 生成 `column_mappings()`、`type_handlers()` 与 `redacted_fields()` 内省方法，并实现
+/// `hirust_mapper_runtime::MapperModel`（经 `SqlSessionFactory::builder(..).model::<T>()` 注册后作用于
+/// `resultType="T"` 的查询；`redact` 字段名在 SQL 日志与事件中作为敏感参数打码）。
 ///
 /// ```ignore
 /// #[derive(MapperModel, Deserialize)]
@@ -50,6 +55,8 @@ pub fn hirust_mapper(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     #[mapper(column = "user_name")]
 ///     name: String,
 ///     age: i64,
+///     #[mapper(redact)]
+///     password: String,
 /// }
 /// ```
 #[proc_macro_derive(MapperModel, attributes(mapper))]
@@ -57,6 +64,30 @@ pub fn derive_mapper_model(item: TokenStream) -> TokenStream {
     derive_model::derive_mapper_model_impl(item)
 }
 
+/// 枚举映射派生。
+///
+/// 仅支持单元变体枚举，生成 `hirust_mapper_runtime::MapperEnum` 实现。变体可用
+/// `#[mapper(name = "...", ordinal = N)]` 覆盖名称（须与 serde 表示一致）与序号；
+/// 未声明序号时取显式判别值，否则依次递增（首个为 0）。
+///
+/// ```ignore
+/// #[derive(MapperEnum, Serialize, Deserialize)]
+/// enum Status {
+///     Active,              // ("Active", 0)
+///     #[mapper(ordinal = 9)]
+///     Banned,              // ("Banned", 9)
+/// }
+///
+/// let factory = SqlSessionFactory::builder(config, ".")
+///     .type_handler("Status", EnumOrdinalHandler::<Status>::new())
+///     .build()
+///     .await?;
+/// ```
+#[proc_macro_derive(MapperEnum, attributes(mapper))]
+pub fn derive_mapper_enum(item: TokenStream) -> TokenStream {
+    derive_enum::derive_mapper_enum_impl(item)
+}
+
 /// 类型化 DAO 属性宏。
 ///
 /// 双用途（按 item 类型分派）：
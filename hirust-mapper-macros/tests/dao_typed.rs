@@ -1,10 +1,15 @@
 //! `#[dao]` + `#[mapper_query]` 类型化 DAO 集成测试。
 //!
 //! 验证：方法名→statement_id、形参名→SQL 参数、返回类型分派（Option/Vec/insert/update/delete）、
-//! 编译期 XML statement 校验（`xml=`）、foreach 集合参数。
+//! 编译期 XML statement 校验（`xml=`）、foreach 集合参数、`Page<T>` 分页返回、`HashMap<K, V>` 按键返回、
+//! 标量 / 元组返回。
+
+use std::collections::HashMap;
 
 use hirust_mapper_macros::dao;
-use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, Result, SqlSessionFactory};
+use hirust_mapper_runtime::{
+    EnvironmentConfig, HirustMapperConfig, Page, PageRequest, Result, SqlSessionFactory,
+};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -27,6 +32,30 @@ impl PrivilegeDao {
     #[mapper_query]
     pub async fn list_by_status(&self, status: i64) -> Result<Vec<User>> {}
 
+    // 分页：PageRequest 形参不进入 SQL 参数表
+    #[mapper_query]
+    pub async fn page_by_status(&self, status: i64, page: PageRequest) -> Result<Page<User>> {}
+
+    // HashMap：按属性建键；ResultMap 语句按映射后的属性名取键
+    #[mapper_query(id = "list_by_status", map_key = "id")]
+    pub async fn map_by_id(&self, status: i64) -> Result<HashMap<i64, User>> {}
+
+    #[mapper_query(id = "list_mapped", map_key = "status")]
+    pub async fn map_by_status(&self) -> Result<HashMap<i64, User>> {}
+
+    #[mapper_query(id = "list_mapped", map_key = "status", on_duplicate = "last")]
+    pub async fn latest_by_status(&self) -> Result<HashMap<i64, User>> {}
+
+    // 标量 / 元组：按列位置映射
+    #[mapper_query]
+    pub async fn count_by_status(&self, status: i64) -> Result<i64> {}
+
+    #[mapper_query]
+    pub async fn name_of(&self, id: i64) -> Result<Option<String>> {}
+
+    #[mapper_query]
+    pub async fn id_names(&self) -> Result<Vec<(i64, String)>> {}
+
     #[mapper_query(kind = "insert")]
     pub async fn create(&self, name: String, status: i64) -> Result<i64> {}
 
@@ -89,6 +118,52 @@ async fn dao_select_list_and_foreach() {
     assert_eq!(ids.len(), 3);
 }
 
+#[tokio::test]
+async fn dao_select_page() {
+    let dao = setup().await;
+    for name in ["a", "b", "c", "d", "e"] {
+        dao.create(name.into(), 3).await.unwrap();
+    }
+    dao.create("x".into(), 4).await.unwrap();
+
+    let page = dao.page_by_status(3, PageRequest::new(2, 2)).await.unwrap();
+    assert_eq!(page.total, 5);
+    let names: Vec<_> = page.items.iter().map(|u| u.name.as_str()).collect();
+    assert_eq!(names, vec!["c", "d"]);
+}
+
+#[tokio::test]
+async fn dao_select_map() {
+    let dao = setup().await;
+    dao.create("a".into(), 5).await.unwrap();
+    dao.create("b".into(), 5).await.unwrap();
+    dao.create("c".into(), 9).await.unwrap();
+
+    let by_id = dao.map_by_id(5).await.unwrap();
+    assert_eq!(by_id.len(), 2);
+    assert_eq!(by_id[&2].name, "b");
+
+    // 默认重复键报错；on_duplicate = "last" 时后出现的行覆盖
+    let err = dao.map_by_status().await.unwrap_err();
+    assert!(matches!(err, hirust_mapper_runtime::MapperRuntimeError::DuplicateMapKey { .. }), "{}", err);
+    let latest = dao.latest_by_status().await.unwrap();
+    assert_eq!(latest[&5].name, "b");
+    assert_eq!(latest[&9].name, "c");
+}
+
+#[tokio::test]
+async fn dao_select_scalars() {
+    let dao = setup().await;
+    dao.create("a".into(), 5).await.unwrap();
+    dao.create("b".into(), 5).await.unwrap();
+
+    assert_eq!(dao.count_by_status(5).await.unwrap(), 2);
+    assert_eq!(dao.count_by_status(6).await.unwrap(), 0);
+    assert_eq!(dao.name_of(2).await.unwrap().as_deref(), Some("b"));
+    assert_eq!(dao.name_of(9).await.unwrap(), None);
+    assert_eq!(dao.id_names().await.unwrap(), vec![(1, "a".to_string()), (2, "b".to_string())]);
+}
+
 #[tokio::test]
 async fn dao_insert_update_delete() {
     let dao = setup().await;
@@ -114,6 +189,23 @@ async fn dao_multiple_params_map_by_name() {
     assert_eq!(u.status, 42);
 }
 
+#[tokio::test]
+async fn dao_calls_share_transaction() {
+    // 事务闭包内的多个 DAO 调用加入同一事务，闭包失败时一并回滚
+    let dao = setup().await;
+    let factory = dao.factory().clone();
+    let res: Result<()> = factory
+        .transaction(|_tx| async {
+            let id = dao.create("事务内".into(), 1).await?;
+            dao.set_status(id, 2).await?;
+            assert_eq!(dao.find_by_id(id).await?.unwrap().status, 2);
+            Err(hirust_mapper_runtime::MapperRuntimeError::Transaction("回滚".into()))
+        })
+        .await;
+    assert!(res.is_err());
+    assert!(dao.list_by_status(2).await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn dao_factory_accessor() {
     let dao = setup().await;
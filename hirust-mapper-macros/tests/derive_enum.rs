@@ -0,0 +1,124 @@
+//! `#[derive(MapperEnum)]` 测试：名称 ↔ 序号表生成，及经枚举处理器的 sqlite 往返。
+
+use std::collections::HashMap;
+
+use hirust_mapper_macros::MapperEnum;
+use hirust_mapper_runtime::{
+    EnumOrdinalHandler, EnumStringHandler, EnvironmentConfig, HirustMapperConfig, MapperEnum,
+    SqlSessionFactory,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, MapperEnum)]
+enum Status {
+    Active,
+    #[serde(rename = "locked")]
+    #[mapper(name = "locked")]
+    Locked,
+    Banned = 7,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, MapperEnum)]
+enum Priority {
+    #[mapper(ordinal = 10)]
+    Low,
+    Normal,
+    #[mapper(ordinal = -1)]
+    Urgent,
+}
+
+#[test]
+fn variant_tables() {
+    assert_eq!(Status::TYPE_NAME, "Status");
+    assert_eq!(
+        Status::VARIANTS,
+        &[("Active", 0), ("locked", 1), ("Banned", 7), ("Deleted", 8)]
+    );
+    assert_eq!(Priority::VARIANTS, &[("Low", 10), ("Normal", 11), ("Urgent", -1)]);
+    assert_eq!(Priority::ordinal_of("Normal"), Some(11));
+    assert_eq!(Priority::name_of(-1), Some("Urgent"));
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Ticket {
+    id: i64,
+    status: Status,
+    priority: Priority,
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="t">
+    <resultMap id="ticketMap" type="Ticket">
+        <id property="id" column="id"/>
+        <result property="status" column="status" typeHandler="Status"/>
+        <result property="priority" column="priority" typeHandler="Priority"/>
+    </resultMap>
+    <insert id="insert">
+        INSERT INTO tickets (id, status, priority)
+        VALUES (#{id}, #{status}, #{priority, rustType=Priority})
+    </insert>
+    <select id="findByPriority" resultMap="ticketMap">
+        SELECT id, status, priority FROM tickets WHERE priority = #{priority, typeHandler=Priority}
+    </select>
+    <select id="findAll" resultMap="ticketMap">SELECT id, status, priority FROM tickets ORDER BY id</select>
+</mapper>"#;
+
+#[tokio::test]
+async fn enum_handlers_roundtrip() {
+    let temp = std::env::temp_dir().join("hirust_derive_enum");
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("T.xml"), MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::builder(config, &temp)
+        .type_handler("Status", EnumStringHandler::<Status>::new())
+        .type_handler("Priority", EnumOrdinalHandler::<Priority>::new())
+        .build()
+        .await
+        .unwrap();
+    let pool = factory.environment().pool();
+    sqlx::query("CREATE TABLE tickets (id INTEGER, status TEXT, priority INTEGER)")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let ticket = Ticket { id: 1, status: Status::Locked, priority: Priority::Urgent };
+    let mut session = factory.open_session();
+    session.insert("t", "insert", &ticket).await.unwrap();
+
+    // status 以变体名存储，priority 以序号存储
+    let raw: (String, i64) = sqlx::query_as("SELECT status, priority FROM tickets WHERE id = 1")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(raw, ("locked".to_string(), -1));
+
+    // 查询条件可传变体名或序号；结果列经处理器还原为枚举
+    for priority in [json!("Urgent"), json!(-1)] {
+        let found: Vec<Ticket> = session
+            .select_list("t", "findByPriority", &HashMap::from([("priority".to_string(), priority)]))
+            .await
+            .unwrap();
+        assert_eq!(found, vec![Ticket { id: 1, status: Status::Locked, priority: Priority::Urgent }]);
+    }
+
+    // 库中存在未知序号时报错而非静默映射
+    sqlx::query("INSERT INTO tickets VALUES (2, 'Active', 99)").execute(pool).await.unwrap();
+    let err = session
+        .select_list::<Value>("t", "findAll", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("序号 99 不对应 Priority"), "{}", err);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -1,4 +1,4 @@
-//! P9 `#[derive(MapperModel)]` 测试：列映射内省。
+//! P9 `#[derive(MapperModel)]` 测试：列映射内省，及经构建器注册后作用于 `resultType` 的结果映射。
 
 use hirust_mapper_macros::MapperModel;
 
@@ -11,6 +11,8 @@ struct User {
     #[mapper(column = "created_at", type_handler = "chrono::DateTime<chrono::Utc>")]
     created: String,
     no_attr: bool,
+    #[mapper(column = "pwd_hash", redact)]
+    password: String,
 }
 
 #[test]
@@ -31,10 +33,68 @@ fn type_handlers_collected() {
     assert!(!handlers.iter().any(|(f, _)| *f == "name"));
 }
 
+#[test]
+fn redacted_fields_collected() {
+    assert_eq!(User::redacted_fields(), &["password"]);
+    assert!(User::column_mappings().contains(&("password", "pwd_hash")), "redact 可与 column 同用");
+}
+
 #[test]
 fn empty_struct_mappings() {
     #[derive(MapperModel)]
     struct Empty;
     assert!(Empty::column_mappings().is_empty());
     assert!(Empty::type_handlers().is_empty());
+    assert!(Empty::redacted_fields().is_empty());
+}
+
+#[derive(MapperModel, serde::Deserialize, Debug, PartialEq)]
+struct Flag {
+    id: i64,
+    #[mapper(column = "is_enabled", type_handler = "bool")]
+    enabled: bool,
+}
+
+#[tokio::test]
+async fn registered_model_maps_result_type() {
+    use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, SqlSessionFactory};
+
+    let temp = std::env::temp_dir().join("hirust_derive_model");
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(
+        temp.join("mappers").join("F.xml"),
+        r#"<mapper namespace="f">
+            <select id="findAll" resultType="Flag">SELECT id, is_enabled FROM flags ORDER BY id</select>
+        </mapper>"#,
+    )
+    .unwrap();
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::builder(config, &temp)
+        .model::<Flag>()
+        .build()
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE flags (id INTEGER, is_enabled INTEGER); INSERT INTO flags VALUES (1, 1), (2, 0)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    // is_enabled → enabled，整数列经 bool 处理器解码
+    let mut session = factory.open_session();
+    let flags: Vec<Flag> = session
+        .select_list("f", "findAll", &std::collections::HashMap::new())
+        .await
+        .unwrap();
+    assert_eq!(flags, vec![Flag { id: 1, enabled: true }, Flag { id: 2, enabled: false }]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
 }
@@ -32,11 +32,28 @@
 //! | `HIRUST_MAPPER_REFRESH_MS` | `settings.mapper_refresh_interval_ms` | u64 |
 //! | `HIRUST_MAPPER_SQL_LOG` | `settings.sql_log` | 布尔（true/1/yes/false/0/no） |
 //! | `HIRUST_MAPPER_SQL_LOG_SLOW_MS` | `settings.sql_log_slow_threshold_ms` | u64 |
+//! | `HIRUST_MAPPER_SQL_LOG_FORMAT` | `settings.sql_log_format` | `text` / `json` |
+//! | `HIRUST_MAPPER_SQL_LOG_SAMPLE_RATE` | `settings.sql_log_sample_rate` | f64（0.0 ~ 1.0） |
+//! | `HIRUST_MAPPER_SQL_LOG_LEVELS` | `settings.sql_log_levels` | 逗号分隔 `namespace=level`（合并） |
+//! | `HIRUST_MAPPER_STATEMENT_TIMEOUT_MS` | `settings.default_statement_timeout_ms` | u64 |
+//! | `HIRUST_MAPPER_RETRY_MAX_ATTEMPTS` | `settings.retry.max_attempts` | u32 |
+//! | `HIRUST_MAPPER_METRICS` | `settings.metrics` | 布尔 |
+//! | `HIRUST_MAPPER_SLOW_QUERY_EXPLAIN` | `settings.slow_query_explain` | 布尔 |
+//! | `HIRUST_MAPPER_SLOW_QUERY_EXPLAIN_INTERVAL_MS` | `settings.slow_query_explain_interval_ms` | u64 |
+//! | `HIRUST_MAPPER_REDACT_PATTERNS` | `settings.redaction.patterns` | 逗号分隔列表（整体替换） |
+//! | `HIRUST_MAPPER_REDACT_MASK` | `settings.redaction.mask` | 字符串 |
+//! | `HIRUST_MAPPER_SQL_COMMENT` | `settings.sql_comment.enabled` | 布尔 |
+//! | `HIRUST_MAPPER_SQL_COMMENT_APP` | `settings.sql_comment.app` | 字符串 |
 //! | `HIRUST_MAPPER_TYPE_ALIASES` | `type_aliases` | 逗号分隔 `name=type`（合并） |
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use crate::error::{MapperRuntimeError, Result};
+use crate::redact::RedactionPolicy;
+use crate::retry::RetryPolicy;
+use crate::sql_comment::SqlCommentConfig;
+use crate::sql_log::{SqlLogFormat, SqlLogLevel};
+use crate::type_handler::BinaryEncoding;
 
 /// 根配置结构，对应 `hirust-mapper.toml`
 #[derive(Debug, Clone, Default, serde::Deserialize)]
@@ -103,12 +120,54 @@ pub struct SettingsConfig {
     /// 慢查询阈值（毫秒）；仅记录耗时 ≥ 此值的 SQL。`0` 表示记录全部执行的 SQL。
     #[serde(default)]
     pub sql_log_slow_threshold_ms: u64,
+    /// SQL 日志格式：`"text"`（默认，人读）或 `"json"`（每次执行一行 JSON）
+    #[serde(default)]
+    pub sql_log_format: SqlLogFormat,
+    /// SQL 日志采样率（`0.0` ~ `1.0`，默认 `1.0`）；失败与慢查询总是记录
+    #[serde(default = "default_sql_log_sample_rate")]
+    pub sql_log_sample_rate: f64,
+    /// 按命名空间（或其点分前缀）覆盖 SQL 日志级别（`[settings.sql_log_levels]`，`off` 关闭）
+    #[serde(default)]
+    pub sql_log_levels: HashMap<String, SqlLogLevel>,
+    /// 语句默认执行超时（毫秒），`0` 表示不限时。XML `timeout` 属性（秒）优先。
+    #[serde(default)]
+    pub default_statement_timeout_ms: u64,
+    /// 瞬时错误重试策略（`[settings.retry]`，默认不重试）
+    #[serde(default)]
+    pub retry: RetryPolicy,
+    /// Blob 列的结果表示：`"base64"`（默认）或 `"array"`（字节数组）
+    #[serde(default)]
+    pub binary_encoding: BinaryEncoding,
+    /// 是否采集语句级指标（见 [`MetricsRegistry`](crate::MetricsRegistry)，默认关闭）
+    #[serde(default)]
+    pub metrics: bool,
+    /// 慢查询（达到 `sql_log_slow_threshold_ms`）时是否在独立的池连接上捕获执行计划，
+    /// 写入 SQL 日志与 [`SlowQueryEvent`](crate::SlowQueryEvent)（默认关闭）
+    #[serde(default)]
+    pub slow_query_explain: bool,
+    /// 两次执行计划捕获的最小间隔（毫秒，默认 10000），`0` 表示不限流
+    #[serde(default = "default_slow_query_explain_interval_ms")]
+    pub slow_query_explain_interval_ms: u64,
+    /// SQL 日志与事件中的敏感参数打码规则（`[settings.redaction]`，见 [`Redactor`](crate::Redactor)）
+    #[serde(default)]
+    pub redaction: RedactionPolicy,
+    /// SQL 来源注释（`[settings.sql_comment]`，见 [`SqlCommenter`](crate::SqlCommenter)，默认关闭）
+    #[serde(default)]
+    pub sql_comment: SqlCommentConfig,
 }
 
 fn default_mapper_paths() -> Vec<String> {
     vec!["mappers/**/*.xml".to_string()]
 }
 
+fn default_sql_log_sample_rate() -> f64 {
+    1.0
+}
+
+fn default_slow_query_explain_interval_ms() -> u64 {
+    10_000
+}
+
 impl Default for SettingsConfig {
     fn default() -> Self {
         Self {
@@ -116,11 +175,25 @@ impl Default for SettingsConfig {
             mapper_refresh_interval_ms: 0,
             sql_log: false,
             sql_log_slow_threshold_ms: 0,
+            sql_log_format: SqlLogFormat::default(),
+            sql_log_sample_rate: default_sql_log_sample_rate(),
+            sql_log_levels: HashMap::new(),
+            default_statement_timeout_ms: 0,
+            retry: RetryPolicy::default(),
+            binary_encoding: BinaryEncoding::default(),
+            metrics: false,
+            slow_query_explain: false,
+            slow_query_explain_interval_ms: default_slow_query_explain_interval_ms(),
+            redaction: RedactionPolicy::default(),
+            sql_comment: SqlCommentConfig::default(),
         }
     }
 }
 
-/// 自定义类型处理器注册项
+/// 自定义类型处理器注册项（`[[type_handlers]]`）
+///
+/// `handler` 须与 [`SqlSessionFactoryBuilder::type_handler`](crate::SqlSessionFactoryBuilder::type_handler)
+/// 注册的名称一致（或为其全限定路径），否则工厂构建失败。
 #[derive(Debug, Clone, serde::Deserialize)]
 pub struct TypeHandlerEntry {
     /// Rust 类型全限定路径
@@ -194,6 +267,15 @@ impl HirustMapperConfig {
         self
     }
 
+    /// 追加一条 `[[type_handlers]]` 注册项（Rust 类型路径 → 处理器名称）
+    pub fn with_type_handler(mut self, type_path: impl Into<String>, handler_path: impl Into<String>) -> Self {
+        self.type_handlers.push(TypeHandlerEntry {
+            type_path: type_path.into(),
+            handler_path: handler_path.into(),
+        });
+        self
+    }
+
     /// 设置 mapper 路径
     pub fn with_mapper_paths(mut self, paths: Vec<String>) -> Self {
         self.settings.mapper_paths = paths;
@@ -221,6 +303,78 @@ impl HirustMapperConfig {
         self
     }
 
+    /// 设置 SQL 日志格式（等价于 toml `[settings] sql_log_format = "json"`）
+    pub fn with_sql_log_format(mut self, format: SqlLogFormat) -> Self {
+        self.settings.sql_log_format = format;
+        self
+    }
+
+    /// 设置 SQL 日志采样率（`0.0` ~ `1.0`）；失败与慢查询不参与采样
+    pub fn with_sql_log_sample_rate(mut self, rate: f64) -> Self {
+        self.settings.sql_log_sample_rate = rate;
+        self
+    }
+
+    /// 覆盖某命名空间（或其点分前缀）的 SQL 日志级别（等价于 toml `[settings.sql_log_levels]` 的一项）
+    pub fn with_sql_log_level(mut self, namespace: impl Into<String>, level: SqlLogLevel) -> Self {
+        self.settings.sql_log_levels.insert(namespace.into(), level);
+        self
+    }
+
+    /// 设置语句默认执行超时（毫秒），`0` 表示不限时。
+    ///
+    /// 等价于 toml `[settings] default_statement_timeout_ms`；XML `timeout` 属性优先。
+    pub fn with_default_statement_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.settings.default_statement_timeout_ms = timeout_ms;
+        self
+    }
+
+    /// 设置瞬时错误重试策略（等价于 toml `[settings.retry]`）
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.settings.retry = policy;
+        self
+    }
+
+    /// 设置 Blob 列的结果表示（base64 字符串 / 字节数组）
+    pub fn with_binary_encoding(mut self, encoding: BinaryEncoding) -> Self {
+        self.settings.binary_encoding = encoding;
+        self
+    }
+
+    /// 开启语句级指标采集，经 [`SqlSessionFactory::metrics_snapshot`](crate::SqlSessionFactory::metrics_snapshot) 读取。
+    ///
+    /// 等价于 toml `[settings] metrics = true`。
+    pub fn with_metrics(mut self, enabled: bool) -> Self {
+        self.settings.metrics = enabled;
+        self
+    }
+
+    /// 开启慢查询执行计划捕获（需同时设置 [`with_sql_log_slow_threshold_ms`](Self::with_sql_log_slow_threshold_ms)）。
+    ///
+    /// 等价于 toml `[settings] slow_query_explain = true`。
+    pub fn with_slow_query_explain(mut self, enabled: bool) -> Self {
+        self.settings.slow_query_explain = enabled;
+        self
+    }
+
+    /// 设置两次执行计划捕获的最小间隔（毫秒，`0` 表示不限流）
+    pub fn with_slow_query_explain_interval_ms(mut self, interval_ms: u64) -> Self {
+        self.settings.slow_query_explain_interval_ms = interval_ms;
+        self
+    }
+
+    /// 设置敏感参数打码规则（等价于 toml `[settings.redaction]`）
+    pub fn with_redaction(mut self, policy: RedactionPolicy) -> Self {
+        self.settings.redaction = policy;
+        self
+    }
+
+    /// 设置 SQL 来源注释（等价于 toml `[settings.sql_comment]`）
+    pub fn with_sql_comment(mut self, config: SqlCommentConfig) -> Self {
+        self.settings.sql_comment = config;
+        self
+    }
+
     // ─── 环境变量覆盖层 ─────────────────────────────────────────────
 
     /// 从指定 env 源应用环境变量覆盖（仅覆盖已设置的变量）。
@@ -259,6 +413,50 @@ impl HirustMapperConfig {
         if let Some(v) = src.get(ENV_SQL_LOG_SLOW_MS) {
             self.settings.sql_log_slow_threshold_ms = parse_u64(&v, ENV_SQL_LOG_SLOW_MS)?;
         }
+        if let Some(v) = src.get(ENV_SQL_LOG_FORMAT) {
+            self.settings.sql_log_format = SqlLogFormat::parse(&v).ok_or_else(|| {
+                config_err(format!("环境变量 {} 期望 text 或 json，实际 '{}'", ENV_SQL_LOG_FORMAT, v))
+            })?;
+        }
+        if let Some(v) = src.get(ENV_SQL_LOG_SAMPLE_RATE) {
+            self.settings.sql_log_sample_rate = parse_rate(&v, ENV_SQL_LOG_SAMPLE_RATE)?;
+        }
+        if let Some(v) = src.get(ENV_SQL_LOG_LEVELS) {
+            for (namespace, level) in parse_levels(&v)? {
+                self.settings.sql_log_levels.insert(namespace, level);
+            }
+        }
+        if let Some(v) = src.get(ENV_STATEMENT_TIMEOUT_MS) {
+            self.settings.default_statement_timeout_ms = parse_u64(&v, ENV_STATEMENT_TIMEOUT_MS)?;
+        }
+        if let Some(v) = src.get(ENV_RETRY_MAX_ATTEMPTS) {
+            self.settings.retry.max_attempts = parse_u32(&v, ENV_RETRY_MAX_ATTEMPTS)?;
+        }
+        if let Some(v) = src.get(ENV_METRICS) {
+            self.settings.metrics = parse_bool(&v, ENV_METRICS)?;
+        }
+        if let Some(v) = src.get(ENV_SLOW_QUERY_EXPLAIN) {
+            self.settings.slow_query_explain = parse_bool(&v, ENV_SLOW_QUERY_EXPLAIN)?;
+        }
+        if let Some(v) = src.get(ENV_SLOW_QUERY_EXPLAIN_INTERVAL_MS) {
+            self.settings.slow_query_explain_interval_ms = parse_u64(&v, ENV_SLOW_QUERY_EXPLAIN_INTERVAL_MS)?;
+        }
+        if let Some(v) = src.get(ENV_REDACT_PATTERNS) {
+            self.settings.redaction.patterns = v
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+        if let Some(v) = src.get(ENV_REDACT_MASK) {
+            self.settings.redaction.mask = v;
+        }
+        if let Some(v) = src.get(ENV_SQL_COMMENT) {
+            self.settings.sql_comment.enabled = parse_bool(&v, ENV_SQL_COMMENT)?;
+        }
+        if let Some(v) = src.get(ENV_SQL_COMMENT_APP) {
+            self.settings.sql_comment.app = Some(v);
+        }
         if let Some(v) = src.get(ENV_TYPE_ALIASES) {
             for (k, t) in parse_aliases(&v)? {
                 self.type_aliases.insert(k, t);
@@ -349,6 +547,18 @@ const ENV_PATHS: &str = "HIRUST_MAPPER_PATHS";
 const ENV_REFRESH_MS: &str = "HIRUST_MAPPER_REFRESH_MS";
 const ENV_SQL_LOG: &str = "HIRUST_MAPPER_SQL_LOG";
 const ENV_SQL_LOG_SLOW_MS: &str = "HIRUST_MAPPER_SQL_LOG_SLOW_MS";
+const ENV_SQL_LOG_FORMAT: &str = "HIRUST_MAPPER_SQL_LOG_FORMAT";
+const ENV_SQL_LOG_SAMPLE_RATE: &str = "HIRUST_MAPPER_SQL_LOG_SAMPLE_RATE";
+const ENV_SQL_LOG_LEVELS: &str = "HIRUST_MAPPER_SQL_LOG_LEVELS";
+const ENV_STATEMENT_TIMEOUT_MS: &str = "HIRUST_MAPPER_STATEMENT_TIMEOUT_MS";
+const ENV_RETRY_MAX_ATTEMPTS: &str = "HIRUST_MAPPER_RETRY_MAX_ATTEMPTS";
+const ENV_METRICS: &str = "HIRUST_MAPPER_METRICS";
+const ENV_SLOW_QUERY_EXPLAIN: &str = "HIRUST_MAPPER_SLOW_QUERY_EXPLAIN";
+const ENV_SLOW_QUERY_EXPLAIN_INTERVAL_MS: &str = "HIRUST_MAPPER_SLOW_QUERY_EXPLAIN_INTERVAL_MS";
+const ENV_REDACT_PATTERNS: &str = "HIRUST_MAPPER_REDACT_PATTERNS";
+const ENV_REDACT_MASK: &str = "HIRUST_MAPPER_REDACT_MASK";
+const ENV_SQL_COMMENT: &str = "HIRUST_MAPPER_SQL_COMMENT";
+const ENV_SQL_COMMENT_APP: &str = "HIRUST_MAPPER_SQL_COMMENT_APP";
 const ENV_TYPE_ALIASES: &str = "HIRUST_MAPPER_TYPE_ALIASES";
 
 fn config_err(msg: impl Into<String>) -> MapperRuntimeError {
@@ -367,6 +577,36 @@ fn parse_u64(v: &str, var: &str) -> Result<u64> {
     })
 }
 
+/// 解析比例：`0.0` ~ `1.0` 的小数
+fn parse_rate(v: &str, var: &str) -> Result<f64> {
+    match v.trim().parse::<f64>() {
+        Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(rate),
+        _ => Err(config_err(format!("环境变量 {} 期望 0.0 ~ 1.0 的小数，实际 '{}'", var, v))),
+    }
+}
+
+/// 解析命名空间日志级别：`app.UserDao=debug,app.AuditDao=off` → [(app.UserDao, Debug), (app.AuditDao, Off)]
+fn parse_levels(v: &str) -> Result<Vec<(String, SqlLogLevel)>> {
+    let mut out = Vec::new();
+    for pair in v.split(',') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+        let level = pair
+            .split_once('=')
+            .and_then(|(ns, level)| Some((ns.trim().to_string(), SqlLogLevel::parse(level)?)));
+        let pair = level.ok_or_else(|| {
+            config_err(format!(
+                "环境变量 {} 期望 'namespace=level' 形式（逗号分隔，level 为 off/error/warn/info/debug/trace），无效项 '{}'",
+                ENV_SQL_LOG_LEVELS, pair
+            ))
+        })?;
+        out.push(pair);
+    }
+    Ok(out)
+}
+
 /// 解析布尔：true/1/yes/on → true；false/0/no/off → false（大小写不敏感）
 fn parse_bool(v: &str, var: &str) -> Result<bool> {
     match v.trim().to_ascii_lowercase().as_str() {
@@ -401,6 +641,7 @@ fn parse_aliases(v: &str) -> Result<Vec<(String, String)>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::retry::RetryErrorClass;
 
     #[test]
     fn test_parse_toml_basic() {
@@ -528,10 +769,14 @@ url = "sqlite::memory:"
 [settings]
 sql_log = true
 sql_log_slow_threshold_ms = 100
+slow_query_explain = true
+slow_query_explain_interval_ms = 2000
 "#;
         let config = HirustMapperConfig::parse_toml(toml_str).unwrap();
         assert!(config.settings.sql_log);
         assert_eq!(config.settings.sql_log_slow_threshold_ms, 100);
+        assert!(config.settings.slow_query_explain);
+        assert_eq!(config.settings.slow_query_explain_interval_ms, 2000);
 
         // 默认（缺省字段）应为关闭、阈值 0
         let minimal = HirustMapperConfig::parse_toml(
@@ -542,6 +787,139 @@ url = "sqlite::memory:""#,
         .unwrap();
         assert!(!minimal.settings.sql_log);
         assert_eq!(minimal.settings.sql_log_slow_threshold_ms, 0);
+        assert!(!minimal.settings.slow_query_explain);
+        assert_eq!(minimal.settings.slow_query_explain_interval_ms, 10_000);
+    }
+
+    #[test]
+    fn test_statement_timeout_setting() {
+        let config = HirustMapperConfig::parse_toml(
+            r#"[environment]
+driver = "sqlite"
+url = "sqlite::memory:"
+
+[settings]
+default_statement_timeout_ms = 3000"#,
+        )
+        .unwrap();
+        assert_eq!(config.settings.default_statement_timeout_ms, 3000);
+        assert_eq!(SettingsConfig::default().default_statement_timeout_ms, 0);
+
+        let config = HirustMapperConfig::new().with_default_statement_timeout_ms(250);
+        assert_eq!(config.settings.default_statement_timeout_ms, 250);
+    }
+
+    #[test]
+    fn test_retry_settings() {
+        let config = HirustMapperConfig::parse_toml(
+            r#"[environment]
+driver = "sqlite"
+url = "sqlite::memory:"
+
+[settings.retry]
+max_attempts = 5
+max_backoff_ms = 200
+error_classes = ["busy", "serialization_failure"]"#,
+        )
+        .unwrap();
+        let retry = &config.settings.retry;
+        assert_eq!(retry.max_attempts, 5);
+        assert_eq!(retry.initial_backoff_ms, 20);
+        assert_eq!(retry.max_backoff_ms, 200);
+        assert_eq!(
+            retry.error_classes,
+            vec![RetryErrorClass::Busy, RetryErrorClass::SerializationFailure]
+        );
+        assert!(!SettingsConfig::default().retry.is_enabled());
+
+        let config = HirustMapperConfig::new().with_retry_policy(RetryPolicy::new(2));
+        assert!(config.settings.retry.is_enabled());
+    }
+
+    #[test]
+    fn test_sql_log_structured_settings() {
+        let toml_str = r#"
+[environment]
+driver = "sqlite"
+url = "sqlite::memory:"
+
+[settings]
+sql_log = true
+sql_log_format = "json"
+sql_log_sample_rate = 0.1
+
+[settings.sql_log_levels]
+"app" = "debug"
+"app.AuditDao" = "off"
+"#;
+        let config = HirustMapperConfig::parse_toml(toml_str).unwrap();
+        assert_eq!(config.settings.sql_log_format, SqlLogFormat::Json);
+        assert_eq!(config.settings.sql_log_sample_rate, 0.1);
+        assert_eq!(config.settings.sql_log_levels.get("app"), Some(&SqlLogLevel::Debug));
+        assert_eq!(config.settings.sql_log_levels.get("app.AuditDao"), Some(&SqlLogLevel::Off));
+        assert!(HirustMapperConfig::parse_toml("[settings.sql_log_levels]\napp = \"loud\"").is_err());
+
+        let defaults = SettingsConfig::default();
+        assert_eq!(defaults.sql_log_format, SqlLogFormat::Text);
+        assert_eq!(defaults.sql_log_sample_rate, 1.0);
+        assert!(defaults.sql_log_levels.is_empty());
+
+        let config = HirustMapperConfig::new()
+            .with_sql_log_format(SqlLogFormat::Json)
+            .with_sql_log_sample_rate(0.5)
+            .with_sql_log_level("app.UserDao", SqlLogLevel::Warn);
+        assert_eq!(config.settings.sql_log_sample_rate, 0.5);
+        assert_eq!(config.settings.sql_log_levels.get("app.UserDao"), Some(&SqlLogLevel::Warn));
+    }
+
+    #[test]
+    fn test_sql_comment_settings() {
+        let toml_str = r#"
+[environment]
+driver = "sqlite"
+url = "sqlite::memory:"
+
+[settings.sql_comment]
+enabled = true
+app = "order-service"
+"#;
+        let config = HirustMapperConfig::parse_toml(toml_str).unwrap();
+        assert!(config.settings.sql_comment.enabled);
+        assert_eq!(config.settings.sql_comment.app.as_deref(), Some("order-service"));
+        assert_eq!(SettingsConfig::default().sql_comment, SqlCommentConfig::default());
+
+        let config = HirustMapperConfig::new().with_sql_comment(SqlCommentConfig::enabled());
+        assert!(config.settings.sql_comment.enabled);
+    }
+
+    #[test]
+    fn test_redaction_settings() {
+        let toml_str = r#"
+[environment]
+driver = "sqlite"
+url = "sqlite::memory:"
+
+[settings.redaction]
+patterns = ["password", "*token*"]
+"#;
+        let config = HirustMapperConfig::parse_toml(toml_str).unwrap();
+        assert_eq!(config.settings.redaction.patterns, vec!["password", "*token*"]);
+        assert_eq!(config.settings.redaction.mask, crate::redact::DEFAULT_MASK);
+        assert!(SettingsConfig::default().redaction.patterns.is_empty());
+
+        let config = HirustMapperConfig::new().with_redaction(RedactionPolicy::new().with_mask("***"));
+        assert_eq!(config.settings.redaction.mask, "***");
+    }
+
+    #[test]
+    fn test_binary_encoding_setting() {
+        assert_eq!(SettingsConfig::default().binary_encoding, BinaryEncoding::Base64);
+        let config = HirustMapperConfig::parse_toml(
+            "[environment]\ndriver = \"sqlite\"\nurl = \"sqlite::memory:\"\n\n[settings]\nbinary_encoding = \"array\"",
+        )
+        .unwrap();
+        assert_eq!(config.settings.binary_encoding, BinaryEncoding::Array);
+        assert!(HirustMapperConfig::parse_toml("[settings]\nbinary_encoding = \"hex\"").is_err());
     }
 
     #[test]
@@ -603,7 +981,7 @@ url = "sqlite::memory:""#,
         map.insert("dec".to_string(), "f64".to_string());
         let config = config.with_type_aliases(map);
         assert_eq!(config.type_aliases.len(), 1);
-        assert!(config.type_aliases.get("int").is_none());
+        assert!(!config.type_aliases.contains_key("int"));
         assert_eq!(config.type_aliases.get("dec"), Some(&"f64".to_string()));
     }
 
@@ -635,6 +1013,18 @@ url = "sqlite::memory:""#,
             .set(ENV_REFRESH_MS, "1234")
             .set(ENV_SQL_LOG, "true")
             .set(ENV_SQL_LOG_SLOW_MS, "200")
+            .set(ENV_SQL_LOG_FORMAT, "JSON")
+            .set(ENV_SQL_LOG_SAMPLE_RATE, "0.25")
+            .set(ENV_SQL_LOG_LEVELS, "app.UserDao=debug, app.AuditDao=off")
+            .set(ENV_STATEMENT_TIMEOUT_MS, "1500")
+            .set(ENV_RETRY_MAX_ATTEMPTS, "3")
+            .set(ENV_METRICS, "1")
+            .set(ENV_SLOW_QUERY_EXPLAIN, "yes")
+            .set(ENV_SLOW_QUERY_EXPLAIN_INTERVAL_MS, "500")
+            .set(ENV_REDACT_PATTERNS, "password, *token*")
+            .set(ENV_REDACT_MASK, "[x]")
+            .set(ENV_SQL_COMMENT, "on")
+            .set(ENV_SQL_COMMENT_APP, "order-service")
             .set(ENV_TYPE_ALIASES, "int=i32, long=i64");
 
         let mut config = HirustMapperConfig::new();
@@ -648,6 +1038,18 @@ url = "sqlite::memory:""#,
         assert_eq!(config.settings.mapper_refresh_interval_ms, 1234);
         assert!(config.settings.sql_log);
         assert_eq!(config.settings.sql_log_slow_threshold_ms, 200);
+        assert_eq!(config.settings.sql_log_format, SqlLogFormat::Json);
+        assert_eq!(config.settings.sql_log_sample_rate, 0.25);
+        assert_eq!(config.settings.sql_log_levels.get("app.UserDao"), Some(&SqlLogLevel::Debug));
+        assert_eq!(config.settings.sql_log_levels.get("app.AuditDao"), Some(&SqlLogLevel::Off));
+        assert_eq!(config.settings.default_statement_timeout_ms, 1500);
+        assert_eq!(config.settings.retry.max_attempts, 3);
+        assert!(config.settings.metrics);
+        assert!(config.settings.slow_query_explain);
+        assert_eq!(config.settings.slow_query_explain_interval_ms, 500);
+        assert_eq!(config.settings.redaction.patterns, vec!["password", "*token*"]);
+        assert_eq!(config.settings.redaction.mask, "[x]");
+        assert_eq!(config.settings.sql_comment, SqlCommentConfig::enabled().with_app("order-service"));
         assert_eq!(config.type_aliases.get("int"), Some(&"i32".to_string()));
         assert_eq!(config.type_aliases.get("long"), Some(&"i64".to_string()));
     }
@@ -744,6 +1146,21 @@ url = "sqlite::memory:""#,
         assert_eq!(config.settings.mapper_paths, vec!["a.xml", "b.xml"]);
     }
 
+    #[test]
+    fn test_env_invalid_sql_log_options_error() {
+        for (var, value) in [
+            (ENV_SQL_LOG_FORMAT, "xml"),
+            (ENV_SQL_LOG_SAMPLE_RATE, "1.5"),
+            (ENV_SQL_LOG_SAMPLE_RATE, "often"),
+            (ENV_SQL_LOG_LEVELS, "app.UserDao=loud"),
+            (ENV_SQL_LOG_LEVELS, "app.UserDao"),
+        ] {
+            let env = TestEnv::default().set(var, value);
+            let err = HirustMapperConfig::new().apply_env_overrides_from(&env).unwrap_err();
+            assert!(err.to_string().contains(var), "{var}={value}: {err}");
+        }
+    }
+
     #[test]
     fn test_env_invalid_alias_format_errors() {
         let env = TestEnv::default().set(ENV_TYPE_ALIASES, "noequals");
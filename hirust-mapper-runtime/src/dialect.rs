@@ -0,0 +1,194 @@
+//! 数据库方言
+//!
+//! [`Dialect`] 汇集各后端在 SQL 语法上的差异点（分页子句、事务开启语句等），由 [`crate::environment::Environment`]
+//! 按 `driver` 解析后持有，运行时按需查询，避免在各执行点散落 `match driver` 字符串比较。
+
+use crate::error::{MapperRuntimeError, Result};
+use crate::transaction::{IsolationLevel, TransactionOptions};
+
+/// 支持的数据库方言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    MySql,
+    Postgres,
+    Sqlite,
+}
+
+impl Dialect {
+    /// 由 driver 标识解析（大小写不敏感，忽略首尾空白）
+    pub fn from_driver(driver: &str) -> Result<Self> {
+        match driver.trim().to_ascii_lowercase().as_str() {
+            "mysql" => Ok(Dialect::MySql),
+            "postgres" => Ok(Dialect::Postgres),
+            "sqlite" => Ok(Dialect::Sqlite),
+            other => Err(MapperRuntimeError::Config(format!(
+                "不支持的数据库驱动: '{}'，支持的选项: mysql, postgres, sqlite",
+                other
+            ))),
+        }
+    }
+
+    /// driver 标识（与配置中的 `driver` 取值一致）
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::MySql => "mysql",
+            Dialect::Postgres => "postgres",
+            Dialect::Sqlite => "sqlite",
+        }
+    }
+
+    /// 分页子句（两个 `?` 占位符）及其参数顺序。
+    ///
+    /// 返回 `(子句, offset 在前)`：mysql 用 `LIMIT ?, ?`（offset, size），
+    /// postgres / sqlite 用 `LIMIT ? OFFSET ?`（size, offset）。
+    pub fn limit_offset_clause(&self) -> (&'static str, bool) {
+        match self {
+            Dialect::MySql => ("LIMIT ?, ?", true),
+            Dialect::Postgres | Dialect::Sqlite => ("LIMIT ? OFFSET ?", false),
+        }
+    }
+
+    /// 取 `sql` 执行计划的语句：sqlite 用 `EXPLAIN QUERY PLAN`（`EXPLAIN` 输出的是字节码），
+    /// mysql / postgres 用 `EXPLAIN`。占位符原样保留，执行时绑定与原语句相同的参数。
+    pub fn explain_sql(&self, sql: &str) -> String {
+        match self {
+            Dialect::MySql | Dialect::Postgres => format!("EXPLAIN {}", sql),
+            Dialect::Sqlite => format!("EXPLAIN QUERY PLAN {}", sql),
+        }
+    }
+}
+
+/// 按 [`TransactionOptions`] 开启事务所需的语句序列
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeginStatements {
+    /// 开启事务前在同一连接上执行（mysql 的 `SET TRANSACTION` 作用于下一个事务）
+    pub before: Option<String>,
+    /// 开启事务的语句
+    pub begin: String,
+    /// 开启事务后、首条业务 SQL 前执行（postgres 的 `SET TRANSACTION`）
+    pub after: Option<String>,
+}
+
+impl Dialect {
+    /// 事务开启语句
+    ///
+    /// | 方言 | 隔离级别 | 只读 | deferrable |
+    /// |------|---------|------|-----------|
+    /// | mysql | 事务前 `SET TRANSACTION ISOLATION LEVEL ...` | `START TRANSACTION READ ONLY` | 忽略 |
+    /// | postgres | `BEGIN` 后 `SET TRANSACTION ISOLATION LEVEL ... [READ ONLY] [DEFERRABLE]` | 同左 | 同左 |
+    /// | sqlite | `SERIALIZABLE` → `BEGIN EXCLUSIVE`，其余 → `BEGIN IMMEDIATE` | `BEGIN DEFERRED` | 忽略 |
+    ///
+    /// sqlite 本身总是可串行化，隔离级别只决定加锁时机：`IMMEDIATE` 在开启时即取写锁，
+    /// `EXCLUSIVE` 同时阻止其他连接读取。
+    pub fn begin_statements(&self, options: &TransactionOptions) -> BeginStatements {
+        match self {
+            Dialect::MySql => BeginStatements {
+                before: options
+                    .isolation
+                    .map(|level| format!("SET TRANSACTION ISOLATION LEVEL {}", level.as_sql())),
+                begin: if options.read_only {
+                    "START TRANSACTION READ ONLY".to_string()
+                } else {
+                    "BEGIN".to_string()
+                },
+                after: None,
+            },
+            Dialect::Postgres => {
+                let mut modes = Vec::new();
+                if let Some(level) = options.isolation {
+                    modes.push(format!("ISOLATION LEVEL {}", level.as_sql()));
+                }
+                if options.read_only {
+                    modes.push("READ ONLY".to_string());
+                }
+                if options.deferrable {
+                    modes.push("DEFERRABLE".to_string());
+                }
+                BeginStatements {
+                    before: None,
+                    begin: "BEGIN".to_string(),
+                    after: (!modes.is_empty())
+                        .then(|| format!("SET TRANSACTION {}", modes.join(" "))),
+                }
+            }
+            Dialect::Sqlite => BeginStatements {
+                before: None,
+                begin: match (options.read_only, options.isolation) {
+                    (true, _) => "BEGIN DEFERRED",
+                    (false, Some(IsolationLevel::Serializable)) => "BEGIN EXCLUSIVE",
+                    (false, Some(_)) => "BEGIN IMMEDIATE",
+                    (false, None) => "BEGIN",
+                }
+                .to_string(),
+                after: None,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Dialect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_driver() {
+        assert_eq!(Dialect::from_driver("mysql").unwrap(), Dialect::MySql);
+        assert_eq!(Dialect::from_driver(" Postgres ").unwrap(), Dialect::Postgres);
+        assert_eq!(Dialect::from_driver("SQLITE").unwrap(), Dialect::Sqlite);
+        let err = Dialect::from_driver("oracle").unwrap_err().to_string();
+        assert!(err.contains("oracle"));
+    }
+
+    #[test]
+    fn test_limit_offset_clause() {
+        assert_eq!(Dialect::MySql.limit_offset_clause(), ("LIMIT ?, ?", true));
+        assert_eq!(Dialect::Sqlite.limit_offset_clause(), ("LIMIT ? OFFSET ?", false));
+        assert_eq!(Dialect::Postgres.to_string(), "postgres");
+    }
+
+    #[test]
+    fn test_explain_sql() {
+        let sql = "SELECT * FROM users WHERE id = ?";
+        assert_eq!(Dialect::Sqlite.explain_sql(sql), "EXPLAIN QUERY PLAN SELECT * FROM users WHERE id = ?");
+        assert_eq!(Dialect::MySql.explain_sql(sql), "EXPLAIN SELECT * FROM users WHERE id = ?");
+        assert_eq!(Dialect::Postgres.explain_sql(sql), "EXPLAIN SELECT * FROM users WHERE id = ?");
+    }
+
+    #[test]
+    fn test_begin_statements() {
+        let opts = TransactionOptions::new()
+            .with_isolation(IsolationLevel::Serializable)
+            .with_read_only(true)
+            .with_deferrable(true);
+
+        let mysql = Dialect::MySql.begin_statements(&opts);
+        assert_eq!(mysql.before.as_deref(), Some("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE"));
+        assert_eq!(mysql.begin, "START TRANSACTION READ ONLY");
+        assert_eq!(mysql.after, None);
+
+        let pg = Dialect::Postgres.begin_statements(&opts);
+        assert_eq!(pg.begin, "BEGIN");
+        assert_eq!(
+            pg.after.as_deref(),
+            Some("SET TRANSACTION ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE")
+        );
+
+        assert_eq!(Dialect::Sqlite.begin_statements(&opts).begin, "BEGIN DEFERRED");
+        let write = TransactionOptions::new().with_isolation(IsolationLevel::Serializable);
+        assert_eq!(Dialect::Sqlite.begin_statements(&write).begin, "BEGIN EXCLUSIVE");
+        let write = TransactionOptions::new().with_isolation(IsolationLevel::ReadCommitted);
+        assert_eq!(Dialect::Sqlite.begin_statements(&write).begin, "BEGIN IMMEDIATE");
+
+        // 默认选项：各方言均为普通 BEGIN，无附加语句
+        for dialect in [Dialect::MySql, Dialect::Postgres, Dialect::Sqlite] {
+            let plain = dialect.begin_statements(&TransactionOptions::default());
+            assert_eq!(plain, BeginStatements { before: None, begin: "BEGIN".into(), after: None });
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //! 支持通过 `EnvironmentConfig` 构造，按 driver 字段自动选择数据库后端。
 
 use crate::config::EnvironmentConfig;
+use crate::dialect::Dialect;
 use crate::error::{MapperRuntimeError, Result};
 use sqlx::AnyPool;
 use std::collections::HashMap;
@@ -18,6 +19,8 @@ pub struct Environment {
     pool: AnyPool,
     /// 驱动标识（"mysql" | "postgres" | "sqlite"）
     driver: String,
+    /// 数据库方言（由 driver 解析）
+    dialect: Dialect,
     /// 连接 URL
     url: String,
 }
@@ -34,12 +37,7 @@ impl Environment {
         sqlx::any::install_default_drivers();
 
         // 校验 driver 合法性
-        if !matches!(driver.as_str(), "mysql" | "postgres" | "sqlite") {
-            return Err(MapperRuntimeError::Config(format!(
-                "不支持的数据库驱动: '{}'，支持的选项: mysql, postgres, sqlite",
-                driver
-            )));
-        }
+        let dialect = Dialect::from_driver(&driver)?;
 
         if url.is_empty() {
             return Err(MapperRuntimeError::Config(
@@ -75,6 +73,7 @@ impl Environment {
         Ok(Self {
             pool,
             driver,
+            dialect,
             url: url.to_string(),
         })
     }
@@ -89,6 +88,11 @@ impl Environment {
         &self.driver
     }
 
+    /// 获取数据库方言
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
     /// 获取连接 URL
     pub fn url(&self) -> &str {
         &self.url
@@ -153,6 +157,7 @@ mod tests {
 
         let env = Environment::from_config(&config).await.unwrap();
         assert_eq!(env.driver(), "sqlite");
+        assert_eq!(env.dialect(), Dialect::Sqlite);
         assert!(env.pool().size() >= 1);
 
         // 验证连接池可用
@@ -39,6 +39,22 @@ pub enum MapperRuntimeError {
     #[error("返回行数过多: 期望 1, 实际 {actual}")]
     TooManyRows { actual: usize },
 
+    /// 流式分组时父对象的 `<id>` 在其分组结束后再次出现（结果未按 id 排序）
+    #[error("结果未按 <id> 排序: ResultMap '{result_map}' 的父对象 {key} 在分组结束后再次出现")]
+    UnorderedGroup { result_map: String, key: String },
+
+    /// `select_map` 的键属性出现重复值（[`DuplicateKeyPolicy::Error`](crate::DuplicateKeyPolicy::Error)）
+    #[error("select_map 键重复: 属性 '{property}' 的值 {key} 出现多次")]
+    DuplicateMapKey { property: String, key: String },
+
+    /// 语句执行超时（连接已丢弃，不归还连接池）
+    #[error("语句执行超时: {namespace}.{id}")]
+    Timeout { namespace: String, id: String },
+
+    /// 只读事务中执行写操作
+    #[error("只读事务中禁止写操作: {namespace}.{id}")]
+    ReadOnlyViolation { namespace: String, id: String },
+
     /// 找不到指定 namespace 的 Mapper
     #[error("Mapper 不存在: {0}")]
     MapperNotFound(String),
@@ -0,0 +1,321 @@
+//! 异步监听器与后台派发队列
+//!
+//! 同步监听器在 [`EventBus::dispatch`] 内联执行，耗时的审计 / 消息推送会拖慢每条 SQL。
+//! 经 [`EventBus::on_async`] 注册的 [`AsyncListener`] 改为：派发点只克隆事件放入该监听器独享的**有界队列**，
+//! 由一个 tokio 任务按入队顺序逐个取出并 `await` 处理。
+//!
+//! - 队列容量与队列满时的策略由 [`AsyncListenerOptions`] 指定：[`OverflowPolicy::Drop`]（默认）丢弃新事件并计数，
+//!   [`OverflowPolicy::Block`] 阻塞派发线程直到有空位（需多线程运行时；current-thread 运行时内无法阻塞，退化为丢弃）。
+//! - [`EventBus::async_stats`] 返回总线上全部异步监听器的入队 / 处理 / 丢弃计数；首次丢弃时输出运行时诊断告警。
+//! - 取消订阅后队列中已有的事件仍会处理完，随后后台任务退出；监听器 panic 被捕获并告警，不影响后续事件。
+//! - 异步监听器与同步监听器一同计入监听器总数：无监听器时派发仍是零开销快路径，
+//!   事件只在存在异步监听器时克隆（因此事件类型须实现 `Clone`）。
+//!
+//! [`EventBus::dispatch`]: super::EventBus::dispatch
+//! [`EventBus::on_async`]: super::EventBus::on_async
+//! [`EventBus::async_stats`]: super::EventBus::async_stats
+
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use futures_util::FutureExt;
+use futures_util::future::BoxFuture;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use super::{Event, Listener};
+
+/// 异步事件监听器：在后台任务中处理事件 `E`（收到事件的克隆）。
+pub trait AsyncListener<E: Event>: Send + Sync + 'static {
+    /// 处理事件；同一监听器的事件按入队顺序串行处理。
+    fn handle(&self, event: E) -> BoxFuture<'_, ()>;
+}
+
+// 返回 Future 的闭包自动实现 AsyncListener —— 让 `bus.on_async(|e: E| async move {...})` 可用。
+impl<E, F, Fut> AsyncListener<E> for F
+where
+    E: Event,
+    F: Fn(E) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    fn handle(&self, event: E) -> BoxFuture<'_, ()> {
+        Box::pin(self(event))
+    }
+}
+
+/// 队列满时的处理策略
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// 丢弃新事件并计入 [`AsyncDispatchStats::dropped`]（默认，派发点从不阻塞）
+    #[default]
+    Drop,
+    /// 阻塞派发线程直到队列有空位（不丢事件，但慢监听器会反压到 SQL 执行）
+    Block,
+}
+
+/// 异步订阅选项（见 [`EventBus::on_async_with`](super::EventBus::on_async_with)）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AsyncListenerOptions {
+    /// 队列容量（至少为 1，默认 [`DEFAULT_CAPACITY`](Self::DEFAULT_CAPACITY)）
+    pub capacity: usize,
+    /// 队列满时的策略
+    pub overflow: OverflowPolicy,
+}
+
+impl Default for AsyncListenerOptions {
+    fn default() -> Self {
+        Self { capacity: Self::DEFAULT_CAPACITY, overflow: OverflowPolicy::Drop }
+    }
+}
+
+impl AsyncListenerOptions {
+    /// 默认队列容量
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// 默认选项：容量 1024，队列满时丢弃
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置队列容量
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// 设置队列满时的策略
+    pub fn with_overflow(mut self, overflow: OverflowPolicy) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// 异步派发计数快照（总线上全部异步监听器合计）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AsyncDispatchStats {
+    /// 成功入队的事件数
+    pub enqueued: u64,
+    /// 已处理完的事件数（含 panic 的）
+    pub processed: u64,
+    /// 因队列满或后台任务已退出而丢弃的事件数
+    pub dropped: u64,
+}
+
+impl AsyncDispatchStats {
+    /// 尚在队列中或处理中的事件数
+    pub fn pending(&self) -> u64 {
+        self.enqueued.saturating_sub(self.processed)
+    }
+}
+
+/// 异步派发计数（总线持有，各队列共享）
+#[derive(Debug, Default)]
+pub(super) struct AsyncCounters {
+    enqueued: AtomicU64,
+    processed: AtomicU64,
+    dropped: AtomicU64,
+}
+
+impl AsyncCounters {
+    pub(super) fn snapshot(&self) -> AsyncDispatchStats {
+        AsyncDispatchStats {
+            enqueued: self.enqueued.load(Ordering::Relaxed),
+            processed: self.processed.load(Ordering::Relaxed),
+            dropped: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// 注册到总线上的同步入队端：克隆事件放入队列（发送端随订阅移除而 drop，后台任务随之退出）
+pub(super) struct Enqueue<E> {
+    tx: mpsc::Sender<E>,
+    overflow: OverflowPolicy,
+    counters: Arc<AsyncCounters>,
+    /// 是否已告警过丢弃（每个订阅只告警一次，后续仅计数）
+    warned: AtomicBool,
+}
+
+impl<E: Event + Clone> Enqueue<E> {
+    /// 启动后台任务并返回入队端；须在 tokio 运行时内调用
+    pub(super) fn spawn(
+        listener: Arc<dyn AsyncListener<E>>,
+        options: AsyncListenerOptions,
+        counters: Arc<AsyncCounters>,
+    ) -> Self {
+        let (tx, mut rx) = mpsc::channel::<E>(options.capacity.max(1));
+        let worker_counters = Arc::clone(&counters);
+        tokio::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                let handled = AssertUnwindSafe(async { listener.handle(event).await }).catch_unwind().await;
+                if handled.is_err() {
+                    crate::telemetry::warn(&format!("异步监听器处理 {} 时 panic", std::any::type_name::<E>()));
+                }
+                worker_counters.processed.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+        Self { tx, overflow: options.overflow, counters, warned: AtomicBool::new(false) }
+    }
+
+    /// 队列满时阻塞发送；无法阻塞（current-thread 运行时）或后台任务已退出时返回 `false`
+    fn send_blocking(&self, event: E) -> bool {
+        match Handle::try_current() {
+            Err(_) => self.tx.blocking_send(event).is_ok(),
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                tokio::task::block_in_place(|| self.tx.blocking_send(event)).is_ok()
+            }
+            Ok(_) => false,
+        }
+    }
+
+    fn record_drop(&self, reason: &str) {
+        self.counters.dropped.fetch_add(1, Ordering::Relaxed);
+        if !self.warned.swap(true, Ordering::Relaxed) {
+            crate::telemetry::warn(&format!(
+                "异步监听器{}，{} 事件被丢弃（后续丢弃仅计数）",
+                reason,
+                std::any::type_name::<E>()
+            ));
+        }
+    }
+}
+
+impl<E: Event + Clone> Listener<E> for Enqueue<E> {
+    fn handle(&self, event: &E) {
+        let sent = match self.tx.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(event)) if self.overflow == OverflowPolicy::Block => self.send_blocking(event),
+            Err(TrySendError::Full(_)) => false,
+            Err(TrySendError::Closed(_)) => {
+                self.record_drop("后台任务已退出");
+                return;
+            }
+        };
+        if sent {
+            self.counters.enqueued.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.record_drop("队列已满");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::EventBus;
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use tokio::sync::{Notify, Semaphore};
+
+    #[derive(Debug, Clone)]
+    struct AuditEvent(u32);
+    impl Event for AuditEvent {}
+
+    async fn wait_until(mut done: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if done() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        panic!("等待超时");
+    }
+
+    #[tokio::test]
+    async fn test_async_listener_runs_in_background_in_order() {
+        let bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let gate = Arc::new(Semaphore::new(0));
+        let (s, g) = (Arc::clone(&seen), Arc::clone(&gate));
+        let handle = bus.on_async(move |e: AuditEvent| {
+            let (s, g) = (Arc::clone(&s), Arc::clone(&g));
+            async move {
+                g.acquire().await.unwrap().forget();
+                s.lock().unwrap().push(e.0);
+            }
+        });
+        assert!(bus.has_listeners::<AuditEvent>());
+        for i in 0..3 {
+            bus.dispatch(&AuditEvent(i));
+        }
+        // 监听器阻塞在 gate 上，派发仍立即返回
+        assert!(seen.lock().unwrap().is_empty());
+        assert_eq!(bus.async_stats().pending(), 3);
+
+        gate.add_permits(3);
+        wait_until(|| bus.async_stats().processed == 3).await;
+        assert_eq!(*seen.lock().unwrap(), vec![0, 1, 2]);
+
+        drop(handle);
+        assert_eq!(bus.total_listeners(), 0);
+        bus.dispatch(&AuditEvent(9));
+        assert_eq!(bus.async_stats(), AsyncDispatchStats { enqueued: 3, processed: 3, dropped: 0 });
+    }
+
+    #[tokio::test]
+    async fn test_drop_policy_counts_dropped_events() {
+        let bus = EventBus::new();
+        let started = Arc::new(Notify::new());
+        let release = Arc::new(Notify::new());
+        let (s, r) = (Arc::clone(&started), Arc::clone(&release));
+        bus.on_async_with(AsyncListenerOptions::new().with_capacity(2), move |_: AuditEvent| {
+            let (s, r) = (Arc::clone(&s), Arc::clone(&r));
+            async move {
+                s.notify_one();
+                r.notified().await;
+            }
+        })
+        .detach();
+
+        bus.dispatch(&AuditEvent(0));
+        started.notified().await; // 首个事件已出队、处理中
+        for i in 1..6 {
+            bus.dispatch(&AuditEvent(i));
+        }
+        let stats = bus.async_stats();
+        assert_eq!((stats.enqueued, stats.dropped), (3, 3), "容量 2：再入队 2 个，其余 3 个丢弃");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_block_policy_applies_backpressure() {
+        let bus = EventBus::new();
+        let count = Arc::new(AtomicU64::new(0));
+        let c = Arc::clone(&count);
+        let options = AsyncListenerOptions::new().with_capacity(1).with_overflow(OverflowPolicy::Block);
+        bus.on_async_with(options, move |_: AuditEvent| {
+            let c = Arc::clone(&c);
+            async move {
+                tokio::time::sleep(Duration::from_millis(2)).await;
+                c.fetch_add(1, Ordering::Relaxed);
+            }
+        })
+        .detach();
+
+        for i in 0..10 {
+            bus.dispatch(&AuditEvent(i));
+        }
+        wait_until(|| count.load(Ordering::Relaxed) == 10).await;
+        assert_eq!(bus.async_stats(), AsyncDispatchStats { enqueued: 10, processed: 10, dropped: 0 });
+    }
+
+    #[tokio::test]
+    async fn test_panicking_listener_keeps_processing() {
+        let bus = EventBus::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let s = Arc::clone(&seen);
+        bus.on_async(move |e: AuditEvent| {
+            let s = Arc::clone(&s);
+            async move {
+                assert_ne!(e.0, 1, "模拟监听器 panic");
+                s.lock().unwrap().push(e.0);
+            }
+        })
+        .detach();
+        for i in 0..3 {
+            bus.dispatch(&AuditEvent(i));
+        }
+        wait_until(|| bus.async_stats().processed == 3).await;
+        assert_eq!(*seen.lock().unwrap(), vec![0, 2]);
+    }
+}
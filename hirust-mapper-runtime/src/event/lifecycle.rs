@@ -1,21 +1,28 @@
 //! ORM 生命周期事件
 //!
-//! 在 SQL 执行前后由执行器自动派发，配合 [`super::EventBus`] 实现 SQL 层的观察者。
+//! 在 SQL 执行前后由执行器自动派发，事务开启 / 提交 / 回滚时由 [`SqlSession`](crate::SqlSession) 派发，
+//! 配合 [`super::EventBus`] 实现 SQL 层的观察者。
 //! 对应 ThinkPHP 模型事件的 `on_before_*` / `on_after_*` 思路（观察语义）。
 
 use std::time::Duration;
 
+use hirust_mapper_core::StatementType;
 use serde_json::Value;
 
 use super::Event;
+use crate::explain::QueryPlan;
+use crate::retry::RetryErrorClass;
+use crate::transaction::TransactionOptions;
 
-/// SQL 操作种类
+/// SQL 操作种类（取自语句的 XML 元素）
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SqlKind {
     Select,
     Insert,
     Update,
     Delete,
+    /// 不经 Mapper 语句执行的写 SQL（如 [`execute_rows_affected`](crate::executor::execute_rows_affected) 执行的 DDL）
+    Other,
 }
 
 impl std::fmt::Display for SqlKind {
@@ -25,24 +32,88 @@ impl std::fmt::Display for SqlKind {
             SqlKind::Insert => write!(f, "INSERT"),
             SqlKind::Update => write!(f, "UPDATE"),
             SqlKind::Delete => write!(f, "DELETE"),
+            SqlKind::Other => write!(f, "OTHER"),
         }
     }
 }
 
-/// 按首关键字（尽力而为）判定 SQL 种类，用于事件分类
-pub fn classify_sql(sql: &str) -> SqlKind {
-    let upper = sql.trim_start().trim_start_matches('(').trim_start();
-    let upper = upper.to_ascii_uppercase();
-    if upper.starts_with("SELECT") {
-        SqlKind::Select
-    } else if upper.starts_with("INSERT") || upper.starts_with("REPLACE") {
-        SqlKind::Insert
-    } else if upper.starts_with("UPDATE") {
-        SqlKind::Update
-    } else if upper.starts_with("DELETE") {
-        SqlKind::Delete
-    } else {
-        SqlKind::Select
+impl From<&StatementType> for SqlKind {
+    fn from(stmt_type: &StatementType) -> Self {
+        match stmt_type {
+            StatementType::Select => SqlKind::Select,
+            StatementType::Insert => SqlKind::Insert,
+            StatementType::Update => SqlKind::Update,
+            StatementType::Delete => SqlKind::Delete,
+        }
+    }
+}
+
+/// 语句上下文：SQL 所属的 Mapper 语句与执行它的会话，随生命周期事件与 SQL 日志输出
+///
+/// 直接经 [`SimpleExecutor`](crate::SimpleExecutor) 执行、不属于任何 Mapper 语句的 SQL
+/// 使用 [`unnamed`](Self::unnamed) 上下文（namespace / id 为空）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementContext {
+    /// Mapper namespace
+    pub namespace: String,
+    /// 语句 id
+    pub statement_id: String,
+    /// 语句类型（XML 元素）
+    pub kind: SqlKind,
+    /// 执行该语句的会话 id（见 [`SqlSession::id`](crate::SqlSession::id)）
+    pub session_id: Option<u64>,
+    /// 是否在事务中执行
+    pub in_transaction: bool,
+}
+
+impl StatementContext {
+    pub fn new(namespace: impl Into<String>, statement_id: impl Into<String>, kind: SqlKind) -> Self {
+        Self {
+            namespace: namespace.into(),
+            statement_id: statement_id.into(),
+            kind,
+            session_id: None,
+            in_transaction: false,
+        }
+    }
+
+    /// 不属于 Mapper 语句的 SQL
+    pub fn unnamed(kind: SqlKind) -> Self {
+        Self::new(String::new(), String::new(), kind)
+    }
+
+    /// 附加会话信息
+    pub fn with_session(mut self, session_id: u64, in_transaction: bool) -> Self {
+        self.session_id = Some(session_id);
+        self.in_transaction = in_transaction;
+        self
+    }
+
+    /// 是否属于 Mapper 语句
+    pub fn is_named(&self) -> bool {
+        !self.statement_id.is_empty()
+    }
+
+    /// `namespace.id`（便于按语句聚合）
+    pub fn qualified_id(&self) -> String {
+        format!("{}.{}", self.namespace, self.statement_id)
+    }
+}
+
+/// `app.UserDao.findById [SELECT] session=3 tx`（未命名语句省略 id）
+impl std::fmt::Display for StatementContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_named() {
+            write!(f, "{}.{} ", self.namespace, self.statement_id)?;
+        }
+        write!(f, "[{}]", self.kind)?;
+        if let Some(id) = self.session_id {
+            write!(f, " session={}", id)?;
+        }
+        if self.in_transaction {
+            write!(f, " tx")?;
+        }
+        Ok(())
     }
 }
 
@@ -53,8 +124,10 @@ pub struct BeforeSqlEvent {
     pub raw_sql: String,
     /// 绑定参数（按出现顺序）
     pub params: Vec<Value>,
-    /// 操作种类
+    /// 操作种类（同 `statement.kind`）
     pub kind: SqlKind,
+    /// 语句上下文
+    pub statement: StatementContext,
 }
 
 /// SQL 执行**后**事件（含耗时与结果摘要）
@@ -64,8 +137,10 @@ pub struct AfterSqlEvent {
     pub raw_sql: String,
     /// 绑定参数（按出现顺序）
     pub params: Vec<Value>,
-    /// 操作种类
+    /// 操作种类（同 `statement.kind`）
     pub kind: SqlKind,
+    /// 语句上下文
+    pub statement: StatementContext,
     /// 执行耗时
     pub elapsed: Duration,
     /// 结果摘要
@@ -90,21 +165,160 @@ impl SqlOutcome {
     }
 }
 
+/// 连接获取事件：事务外的语句从连接池取得连接后派发（事务内复用事务连接，不派发）
+#[derive(Debug, Clone)]
+pub struct PoolAcquireEvent {
+    /// 等待连接的语句
+    pub statement: StatementContext,
+    /// 等待连接池分配连接的时间
+    pub wait: Duration,
+}
+
+/// 慢查询事件：语句耗时达到 `[settings] sql_log_slow_threshold_ms`（非 0）时，在 [`AfterSqlEvent`] 之前派发
+///
+/// 与 SQL 日志开关无关。开启 `[settings] slow_query_explain` 时附带执行计划（限流、连接池无空闲连接、
+/// 执行失败的语句或流式查询时为 `None`）。
+#[derive(Debug, Clone)]
+pub struct SlowQueryEvent {
+    /// 含 `?` 占位符的原始 SQL
+    pub raw_sql: String,
+    /// 绑定参数（按出现顺序）
+    pub params: Vec<Value>,
+    /// 语句上下文
+    pub statement: StatementContext,
+    /// 执行耗时
+    pub elapsed: Duration,
+    /// 慢查询阈值
+    pub threshold: Duration,
+    /// 执行计划
+    pub plan: Option<QueryPlan>,
+}
+
+/// 重试对象
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RetryScope {
+    /// 事务外的单条查询语句
+    Statement { namespace: String, id: String },
+    /// 整个事务单元（闭包式事务）
+    Transaction,
+}
+
+impl std::fmt::Display for RetryScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetryScope::Statement { namespace, id } => write!(f, "语句 {}.{}", namespace, id),
+            RetryScope::Transaction => write!(f, "事务"),
+        }
+    }
+}
+
+/// 瞬时错误重试事件：每次重试（等待退避）前派发
+#[derive(Debug, Clone)]
+pub struct RetryEvent {
+    /// 重试对象
+    pub scope: RetryScope,
+    /// 刚失败的尝试序号（从 1 开始）
+    pub attempt: u32,
+    /// 最大尝试次数
+    pub max_attempts: u32,
+    /// 下一次尝试前的等待时间
+    pub delay: Duration,
+    /// 错误类别
+    pub class: RetryErrorClass,
+    /// 错误信息
+    pub error: String,
+}
+
+/// 事务开启事件：`begin` / `begin_with` 成功后派发
+#[derive(Debug, Clone)]
+pub struct TransactionBeginEvent {
+    /// 会话 id
+    pub session_id: u64,
+    /// 事务选项
+    pub options: TransactionOptions,
+}
+
+/// 事务提交事件：提交成功（数据已持久化）后、`after_commit` 回调之前派发
+#[derive(Debug, Clone)]
+pub struct TransactionCommitEvent {
+    /// 会话 id
+    pub session_id: u64,
+    /// 事务持续时间（自开启起）
+    pub elapsed: Duration,
+}
+
+/// 事务回滚原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RollbackReason {
+    /// 显式回滚（`rollback`，含闭包式事务失败或被标记为仅回滚）
+    Requested,
+    /// `close` 时回滚未提交的事务
+    Closed,
+    /// 事务连接上的语句超时，事务被中止
+    Timeout,
+    /// 提交被数据库拒绝（错误信息），事务未提交；连接中断等无法确定结果的提交失败见 [`TransactionOutcomeUnknownEvent`]
+    CommitFailed(String),
+    /// session 未提交即被 drop（事务连接被关闭，由数据库回滚）
+    Dropped,
+}
+
+impl std::fmt::Display for RollbackReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RollbackReason::Requested => write!(f, "rollback"),
+            RollbackReason::Closed => write!(f, "close"),
+            RollbackReason::Timeout => write!(f, "语句超时"),
+            RollbackReason::CommitFailed(e) => write!(f, "提交失败: {}", e),
+            RollbackReason::Dropped => write!(f, "session 被 drop"),
+        }
+    }
+}
+
+/// 事务回滚事件：事务以任何方式结束而未提交时、`after_rollback` 回调之前派发
+#[derive(Debug, Clone)]
+pub struct TransactionRollbackEvent {
+    /// 会话 id
+    pub session_id: u64,
+    /// 事务持续时间（自开启起）
+    pub elapsed: Duration,
+    /// 回滚原因
+    pub reason: RollbackReason,
+}
+
+/// 事务结果未知事件：提交时发生非数据库错误（如连接中断），无法确定提交是否已在数据库生效
+///
+/// 不派发提交或回滚事件，`after_commit` 与 `after_rollback` 回调均被丢弃，由业务自行核对。
+#[derive(Debug, Clone)]
+pub struct TransactionOutcomeUnknownEvent {
+    /// 会话 id
+    pub session_id: u64,
+    /// 事务持续时间（自开启起）
+    pub elapsed: Duration,
+    /// 提交时的错误信息
+    pub error: String,
+}
+
 impl Event for BeforeSqlEvent {}
 impl Event for AfterSqlEvent {}
+impl Event for PoolAcquireEvent {}
+impl Event for SlowQueryEvent {}
+impl Event for RetryEvent {}
+impl Event for TransactionBeginEvent {}
+impl Event for TransactionCommitEvent {}
+impl Event for TransactionRollbackEvent {}
+impl Event for TransactionOutcomeUnknownEvent {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_classify_sql() {
-        assert_eq!(classify_sql("SELECT * FROM t"), SqlKind::Select);
-        assert_eq!(classify_sql("  insert into t"), SqlKind::Insert);
-        assert_eq!(classify_sql("REPLACE INTO t"), SqlKind::Insert);
-        assert_eq!(classify_sql("(SELECT 1) UNION (SELECT 2)"), SqlKind::Select);
-        assert_eq!(classify_sql("UPDATE t SET"), SqlKind::Update);
-        assert_eq!(classify_sql("delete from t"), SqlKind::Delete);
+    fn test_statement_context_display() {
+        let ctx = StatementContext::new("app.UserDao", "findById", SqlKind::from(&StatementType::Select));
+        assert_eq!(ctx.to_string(), "app.UserDao.findById [SELECT]");
+        assert_eq!(ctx.qualified_id(), "app.UserDao.findById");
+        assert_eq!(ctx.with_session(3, true).to_string(), "app.UserDao.findById [SELECT] session=3 tx");
+        assert_eq!(StatementContext::unnamed(SqlKind::Other).to_string(), "[OTHER]");
     }
 
     #[test]
@@ -9,16 +9,21 @@
 //! - **观察者语义**：监听器收到 `&E`（不可变），用于日志/审计/指标/缓存失效等副作用，
 //!   不支持在事件中修改数据或取消操作（保持分发简单、无返回值串联）。
 //! - **同步回调**：监听器是同步的（`fn handle(&self, &E)`），在派发点内联调用。
-//!   耗时或异步工作请在监听器内部 `tokio::spawn`。
+//!   耗时或异步工作请用 [`EventBus::on_async`] 注册 [`AsyncListener`]：事件进入有界队列，由后台任务处理。
 //! - **线程安全**：监听器表用 `RwLock<HashMap>` 保护；派发时先克隆出监听器 `Arc` 列表、
 //!   **释放锁后再回调**，从而监听器内部可安全地再次订阅/派发（避免重入死锁）。
+//! - **订阅句柄**：每次订阅返回 [`SubscriptionHandle`]，drop 即取消订阅；需常驻的监听器调用
+//!   [`detach`](SubscriptionHandle::detach)。
+//! - **优先级 / 一次性 / 全事件**：[`ListenerOptions`] 指定优先级（大者先执行）与一次性监听，
+//!   [`EventBus::once`] 为一次性订阅的快捷方式；[`EventBus::on_any`] 接收所有事件（`&dyn Any` + 类型名），
+//!   适合通用审计。
 //! - **零开销快路径**：无任何监听器时，[`EventBus::dispatch`] / [`dispatch_if`](EventBus::dispatch_if)
 //!   经一个 `AtomicUsize` 原子读即返回，不取锁、不构造事件。
 //!
 //! # 示例
 //!
 //! ```ignore
-//! use hirust_mapper::runtime::{EventBus, Event, Subscriber};
+//! use hirust_mapper::runtime::{EventBus, Event, ListenerOptions, Subscriber};
 //! use hirust_mapper::runtime::AfterSqlEvent;
 //!
 //! #[derive(Debug)]
@@ -27,26 +32,37 @@
 //!
 //! let bus = EventBus::new();
 //! // 1) 闭包订阅单个事件
-//! bus.on(|e: &LoginEvent| println!("{} 登录", e.user));
-//! // 2) 订阅器批量订阅
+//! bus.on(|e: &LoginEvent| println!("{} 登录", e.user)).detach();
+//! // 2) 高优先级、一次性监听；句柄 drop 即取消
+//! let handle = bus.on_with(ListenerOptions::new().with_priority(10).with_once(true), |e: &LoginEvent| {
+//!     println!("首次登录：{}", e.user)
+//! });
+//! // 3) 全事件审计
+//! bus.on_any(|event, type_name| println!("[AUDIT] {type_name}")).detach();
+//! // 4) 订阅器批量订阅
 //! bus.add_subscriber(&AuditSubscriber);
-//! // 3) 派发
+//! // 5) 派发
 //! bus.dispatch(&LoginEvent { user: "张三".into() });
 //! # struct AuditSubscriber;
 //! # impl Subscriber for AuditSubscriber {
 //! #     fn subscribe(&self, bus: &EventBus) {
-//! #         bus.on(|e: &LoginEvent| ());
+//! #         bus.on(|e: &LoginEvent| ()).detach();
 //! #     }
 //! # }
 //! ```
 
 use std::any::{Any, TypeId};
+use std::cmp::Reverse;
 use std::collections::HashMap;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, Weak};
 
+pub mod async_listener;
 pub mod lifecycle;
 
+pub use async_listener::{AsyncDispatchStats, AsyncListener, AsyncListenerOptions, OverflowPolicy};
+use async_listener::{AsyncCounters, Enqueue};
+
 /// 事件标记 trait。事件类型须 `Send + Sync + 'static`（用于类型擦除与 `TypeId`）。
 pub trait Event: Send + Sync + 'static {}
 
@@ -68,23 +84,52 @@ where
 
 /// 事件订阅器：批量注册多个事件监听器（对应 ThinkPHP 的「事件订阅」）。
 ///
-/// 实现者在 [`subscribe`](Subscriber::subscribe) 中将（自身的）多个处理逻辑绑定到不同事件。
+/// 实现者在 [`subscribe`](Subscriber::subscribe) 中将（自身的）多个处理逻辑绑定到不同事件；
+/// 监听器需随总线常驻时对返回的 [`SubscriptionHandle`] 调用 [`detach`](SubscriptionHandle::detach)。
 pub trait Subscriber: Send + Sync {
     /// 将本订阅器关心的所有事件监听器注册到 `bus`。
     fn subscribe(&self, bus: &EventBus);
 }
 
+/// 订阅选项：优先级与一次性监听（见 [`EventBus::subscribe_with`]）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListenerOptions {
+    /// 优先级：大者先执行，相同优先级按注册顺序（默认 0）
+    pub priority: i32,
+    /// 一次性监听：首次收到事件后自动取消订阅
+    pub once: bool,
+}
+
+impl ListenerOptions {
+    /// 默认选项：优先级 0，常规监听
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置优先级（大者先执行）
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// 设置是否为一次性监听
+    pub fn with_once(mut self, once: bool) -> Self {
+        self.once = once;
+        self
+    }
+}
+
 // ─── 内部：类型擦除的监听器适配器 ───────────────────────────────────
 
 /// 类型擦除后的监听器：把 `&dyn Any` 内部 downcast 回具体事件类型再交给真实监听器。
 trait ErasedListener: Send + Sync {
-    fn handle_any(&self, event: &dyn Any);
+    fn handle_any(&self, event: &dyn Any, type_name: &'static str);
 }
 
 struct Erased<E: Event>(Arc<dyn Listener<E>>);
 
 impl<E: Event> ErasedListener for Erased<E> {
-    fn handle_any(&self, event: &dyn Any) {
+    fn handle_any(&self, event: &dyn Any, _type_name: &'static str) {
         if let Some(e) = event.downcast_ref::<E>() {
             // 仅当事件类型匹配本监听器的 E 时才回调（同一 TypeId 槽下类型恒匹配，此处为保险）
             (self.0).handle(e);
@@ -92,23 +137,193 @@ impl<E: Event> ErasedListener for Erased<E> {
     }
 }
 
+/// 全事件监听器（[`EventBus::on_any`]）：收到任意事件与其类型名
+struct CatchAll<F>(F);
+
+impl<F> ErasedListener for CatchAll<F>
+where
+    F: Fn(&dyn Any, &'static str) + Send + Sync,
+{
+    fn handle_any(&self, event: &dyn Any, type_name: &'static str) {
+        (self.0)(event, type_name)
+    }
+}
+
+/// 一条订阅：监听器 + 排序与一次性信息
+struct Entry {
+    id: u64,
+    /// 所属事件类型；`None` 为全事件监听器
+    key: Option<TypeId>,
+    priority: i32,
+    /// 一次性监听的「已触发」标记（常规监听为 `None`）
+    fired: Option<AtomicBool>,
+    listener: Box<dyn ErasedListener>,
+}
+
+impl Entry {
+    /// 派发顺序：优先级降序，同优先级按注册顺序（id 递增）
+    fn runs_before(&self, other: &Entry) -> bool {
+        (Reverse(self.priority), self.id) < (Reverse(other.priority), other.id)
+    }
+}
+
+/// 某事件类型的监听器快照（按派发顺序排列的不可变切片，派发时整体 `Arc` 克隆）
+type ListenerSlice = Arc<[Arc<Entry>]>;
+
+/// 监听器表（事件总线与订阅句柄共享）
+struct Registry {
+    /// 事件类型(TypeId) → 该类型的监听器（不可变切片，`Arc` 共享）；`any` 为全事件监听器。
+    /// 存 `Arc<[...]>` 而非 `Vec`：派发时只需克隆 `Arc`（1 次原子自增、零分配），
+    /// 订阅 / 取消订阅时重建切片（罕见路径）。派发读锁释放后再回调，监听器内可安全重入。
+    tables: RwLock<Tables>,
+    /// 所有监听器总数（含全事件监听器）；用于无监听器时的免锁原子快路径
+    total: AtomicUsize,
+    next_id: AtomicU64,
+    /// 异步监听器的入队 / 处理 / 丢弃计数
+    async_counters: Arc<AsyncCounters>,
+}
+
+struct Tables {
+    typed: HashMap<TypeId, ListenerSlice>,
+    any: ListenerSlice,
+}
+
+impl Tables {
+    fn slot(&mut self, key: Option<TypeId>) -> Option<&mut ListenerSlice> {
+        match key {
+            Some(key) => self.typed.get_mut(&key),
+            None => Some(&mut self.any),
+        }
+    }
+}
+
+impl Registry {
+    fn insert(&self, key: Option<TypeId>, options: ListenerOptions, listener: Box<dyn ErasedListener>) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let entry = Arc::new(Entry {
+            id,
+            key,
+            priority: options.priority,
+            fired: options.once.then(|| AtomicBool::new(false)),
+            listener,
+        });
+        {
+            let mut tables = self.tables.write().expect("EventBus 锁中毒");
+            let slot = match key {
+                Some(key) => tables.typed.entry(key).or_insert_with(|| Arc::from(Vec::new())),
+                None => &mut tables.any,
+            };
+            // 重建切片（订阅是罕见路径，重建成本可接受；换取派发的零分配）
+            let at = slot.partition_point(|e| e.runs_before(&entry));
+            let mut v = Vec::with_capacity(slot.len() + 1);
+            v.extend(slot[..at].iter().cloned());
+            v.push(entry);
+            v.extend(slot[at..].iter().cloned());
+            *slot = Arc::from(v);
+        }
+        self.total.fetch_add(1, Ordering::Relaxed);
+        id
+    }
+
+    /// 移除订阅；返回是否仍在表中
+    fn remove(&self, key: Option<TypeId>, id: u64) -> bool {
+        let mut tables = self.tables.write().expect("EventBus 锁中毒");
+        let Some(slot) = tables.slot(key) else {
+            return false;
+        };
+        if !slot.iter().any(|e| e.id == id) {
+            return false;
+        }
+        let rest: Vec<_> = slot.iter().filter(|e| e.id != id).cloned().collect();
+        match key {
+            Some(key) if rest.is_empty() => {
+                tables.typed.remove(&key);
+            }
+            _ => *slot = Arc::from(rest),
+        }
+        self.total.fetch_sub(1, Ordering::Relaxed);
+        true
+    }
+
+    fn contains(&self, key: Option<TypeId>, id: u64) -> bool {
+        let tables = self.tables.read().expect("EventBus 锁中毒");
+        let slot = match key {
+            Some(key) => tables.typed.get(&key),
+            None => Some(&tables.any),
+        };
+        slot.is_some_and(|slot| slot.iter().any(|e| e.id == id))
+    }
+}
+
+/// 订阅句柄：drop 或调用 [`unsubscribe`](Self::unsubscribe) 时取消订阅；
+/// 需要监听器随总线常驻时调用 [`detach`](Self::detach)。
+///
+/// 总线已销毁时取消订阅为空操作。取消时正在进行的派发仍可能回调该监听器一次（派发使用快照）。
+#[must_use = "丢弃 SubscriptionHandle 会立即取消订阅；需常驻时调用 `detach()`"]
+pub struct SubscriptionHandle {
+    registry: Weak<Registry>,
+    key: Option<TypeId>,
+    id: u64,
+    /// drop 时是否取消订阅（`detach` 后为 false）
+    armed: bool,
+}
+
+impl SubscriptionHandle {
+    /// 取消订阅；返回监听器此前是否仍在订阅中（一次性监听器触发后为 `false`）
+    pub fn unsubscribe(mut self) -> bool {
+        self.armed = false;
+        self.registry.upgrade().is_some_and(|registry| registry.remove(self.key, self.id))
+    }
+
+    /// 放弃句柄，监听器随总线常驻（一次性监听器仍在首次触发后移除）
+    pub fn detach(mut self) {
+        self.armed = false;
+    }
+
+    /// 监听器是否仍在订阅中
+    pub fn is_subscribed(&self) -> bool {
+        self.registry.upgrade().is_some_and(|registry| registry.contains(self.key, self.id))
+    }
+}
+
+impl Drop for SubscriptionHandle {
+    fn drop(&mut self) {
+        if self.armed
+            && let Some(registry) = self.registry.upgrade()
+        {
+            registry.remove(self.key, self.id);
+        }
+    }
+}
+
+impl std::fmt::Debug for SubscriptionHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubscriptionHandle")
+            .field("id", &self.id)
+            .field("catch_all", &self.key.is_none())
+            .field("detached", &!self.armed)
+            .finish()
+    }
+}
+
 /// 事件分发器（事件总线）：线程安全，按事件类型路由到监听器。
 ///
-/// 一个 `EventBus` 可被多处共享（`Arc<EventBus>`），监听器在其生命周期内常驻。
+/// 一个 `EventBus` 可被多处共享（`Arc<EventBus>`）。每次订阅返回 [`SubscriptionHandle`]，
+/// 监听器存活到句柄 drop（或 [`detach`](SubscriptionHandle::detach) 后随总线常驻）。
+/// 派发顺序：优先级大者先执行，同优先级按注册顺序；[`on_any`](Self::on_any) 全事件监听器按相同规则与类型监听器交错执行。
 pub struct EventBus {
-    /// 事件类型(TypeId) → 该类型的监听器列表（不可变切片，`Arc` 共享）
-    /// 存 `Arc<[...]>` 而非 `Vec`：派发时只需克隆 `Arc`（1 次原子自增、零分配），
-    /// 订阅时重建切片（罕见路径）。派发读锁释放后再回调，监听器内可安全重入。
-    listeners: RwLock<HashMap<TypeId, Arc<[Arc<dyn ErasedListener>]>>>,
-    /// 所有事件类型的监听器总数；用于无监听器时的锁原子快路径
-    total: AtomicUsize,
+    registry: Arc<Registry>,
 }
 
 impl Default for EventBus {
     fn default() -> Self {
         Self {
-            listeners: RwLock::new(HashMap::new()),
-            total: AtomicUsize::new(0),
+            registry: Arc::new(Registry {
+                tables: RwLock::new(Tables { typed: HashMap::new(), any: Arc::from(Vec::new()) }),
+                total: AtomicUsize::new(0),
+                next_id: AtomicU64::new(0),
+                async_counters: Arc::default(),
+            }),
         }
     }
 }
@@ -119,34 +334,96 @@ impl EventBus {
         Self::default()
     }
 
-    /// 订阅：注册一个 `Listener<E>`（trait 对象形式）。
-    pub fn subscribe<E: Event>(&self, listener: Arc<dyn Listener<E>>) {
-        let key = TypeId::of::<E>();
-        let erased: Arc<dyn ErasedListener> = Arc::new(Erased(listener));
-        {
-            let mut map = self.listeners.write().expect("EventBus 锁中毒");
-            // 重建切片（订阅是罕见路径，重建成本可接受；换取派发的零分配）
-            let new: Arc<[Arc<dyn ErasedListener>]> = match map.get(&key) {
-                Some(existing) => {
-                    let mut v = Vec::with_capacity(existing.len() + 1);
-                    v.extend(existing.iter().cloned());
-                    v.push(erased);
-                    Arc::from(v)
-                }
-                None => Arc::from(vec![erased]),
-            };
-            map.insert(key, new);
-        }
-        self.total.fetch_add(1, Ordering::Relaxed);
+    /// 订阅：注册一个 `Listener<E>`（trait 对象形式，优先级 0）。
+    pub fn subscribe<E: Event>(&self, listener: Arc<dyn Listener<E>>) -> SubscriptionHandle {
+        self.subscribe_with(listener, ListenerOptions::default())
+    }
+
+    /// 按选项（优先级 / 一次性）订阅 `Listener<E>`
+    pub fn subscribe_with<E: Event>(&self, listener: Arc<dyn Listener<E>>, options: ListenerOptions) -> SubscriptionHandle {
+        self.register(Some(TypeId::of::<E>()), options, Box::new(Erased(listener)))
     }
 
     /// 便捷订阅：注册一个闭包监听器 `Fn(&E)`。
-    pub fn on<E: Event, F>(&self, handler: F)
+    pub fn on<E: Event, F>(&self, handler: F) -> SubscriptionHandle
+    where
+        F: Fn(&E) + Send + Sync + 'static,
+    {
+        self.on_with(ListenerOptions::default(), handler)
+    }
+
+    /// 按选项订阅闭包监听器
+    pub fn on_with<E: Event, F>(&self, options: ListenerOptions, handler: F) -> SubscriptionHandle
     where
         F: Fn(&E) + Send + Sync + 'static,
     {
         let listener: Arc<dyn Listener<E>> = Arc::new(handler);
-        self.subscribe(listener);
+        self.subscribe_with(listener, options)
+    }
+
+    /// 一次性订阅：首次收到 `E` 后自动取消（并发派发时也只回调一次）
+    pub fn once<E: Event, F>(&self, handler: F) -> SubscriptionHandle
+    where
+        F: Fn(&E) + Send + Sync + 'static,
+    {
+        self.on_with(ListenerOptions::new().with_once(true), handler)
+    }
+
+    /// 全事件订阅：收到每个派发的事件（`&dyn Any`，可 `downcast_ref`）及其类型名，用于通用审计等场景。
+    ///
+    /// 存在全事件监听器时，任何事件类型的 [`has_listeners`](Self::has_listeners) 均为 `true`，
+    /// [`dispatch_if`](Self::dispatch_if) 总会构造事件。
+    pub fn on_any<F>(&self, handler: F) -> SubscriptionHandle
+    where
+        F: Fn(&dyn Any, &'static str) + Send + Sync + 'static,
+    {
+        self.on_any_with(ListenerOptions::default(), handler)
+    }
+
+    /// 按选项全事件订阅
+    pub fn on_any_with<F>(&self, options: ListenerOptions, handler: F) -> SubscriptionHandle
+    where
+        F: Fn(&dyn Any, &'static str) + Send + Sync + 'static,
+    {
+        self.register(None, options, Box::new(CatchAll(handler)))
+    }
+
+    /// 异步订阅：事件克隆后放入有界队列，由后台 tokio 任务调用 `handler(event).await`（见 [`async_listener`]）。
+    ///
+    /// 须在 tokio 运行时内调用（用于启动后台任务）。
+    pub fn on_async<E, F, Fut>(&self, handler: F) -> SubscriptionHandle
+    where
+        E: Event + Clone,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        self.on_async_with(AsyncListenerOptions::default(), handler)
+    }
+
+    /// 按选项（队列容量 / 满队列策略）异步订阅闭包监听器
+    pub fn on_async_with<E, F, Fut>(&self, options: AsyncListenerOptions, handler: F) -> SubscriptionHandle
+    where
+        E: Event + Clone,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = ()> + Send + 'static,
+    {
+        let listener: Arc<dyn AsyncListener<E>> = Arc::new(handler);
+        self.subscribe_async(listener, options)
+    }
+
+    /// 异步订阅 `AsyncListener<E>`（trait 对象形式）
+    pub fn subscribe_async<E: Event + Clone>(
+        &self,
+        listener: Arc<dyn AsyncListener<E>>,
+        options: AsyncListenerOptions,
+    ) -> SubscriptionHandle {
+        let enqueue = Enqueue::spawn(listener, options, Arc::clone(&self.registry.async_counters));
+        self.subscribe(Arc::new(enqueue))
+    }
+
+    /// 全部异步监听器的入队 / 处理 / 丢弃计数
+    pub fn async_stats(&self) -> AsyncDispatchStats {
+        self.registry.async_counters.snapshot()
     }
 
     /// 批量订阅：通过 [`Subscriber`] 注册其全部监听器。
@@ -154,14 +431,18 @@ impl EventBus {
         subscriber.subscribe(self);
     }
 
-    /// 派发事件：按注册顺序同步调用 `E` 的所有监听器。无监听器时零开销（一次原子读）。
+    fn register(&self, key: Option<TypeId>, options: ListenerOptions, listener: Box<dyn ErasedListener>) -> SubscriptionHandle {
+        let id = self.registry.insert(key, options, listener);
+        SubscriptionHandle { registry: Arc::downgrade(&self.registry), key, id, armed: true }
+    }
+
+    /// 派发事件：按优先级（同优先级按注册顺序）同步调用 `E` 的监听器与全事件监听器。
+    /// 无监听器时零开销（一次原子读）。
     pub fn dispatch<E: Event>(&self, event: &E) {
-        let Some(listeners) = self.snapshot::<E>() else {
+        let Some((typed, any)) = self.snapshot::<E>() else {
             return;
         };
-        for l in listeners.iter() {
-            l.handle_any(event);
-        }
+        self.deliver(typed.as_deref().unwrap_or_default(), &any, event, std::any::type_name::<E>());
     }
 
     /// 惰性派发：仅当存在 `E` 的监听器时才调用 `build` 构造事件并派发。
@@ -170,55 +451,69 @@ impl EventBus {
     where
         F: FnOnce() -> E,
     {
-        let Some(listeners) = self.snapshot::<E>() else {
+        let Some((typed, any)) = self.snapshot::<E>() else {
             return;
         };
         let event = build();
-        for l in listeners.iter() {
-            l.handle_any(&event);
+        self.deliver(typed.as_deref().unwrap_or_default(), &any, &event, std::any::type_name::<E>());
+    }
+
+    /// 按派发顺序归并类型监听器与全事件监听器并回调；一次性监听器回调前先移除
+    fn deliver(&self, typed: &[Arc<Entry>], any: &[Arc<Entry>], event: &dyn Any, type_name: &'static str) {
+        let (mut t, mut a) = (typed.iter().peekable(), any.iter().peekable());
+        loop {
+            let entry = match (t.peek(), a.peek()) {
+                (Some(x), Some(y)) if y.runs_before(x) => a.next(),
+                (Some(_), _) => t.next(),
+                (None, Some(_)) => a.next(),
+                (None, None) => break,
+            };
+            let Some(entry) = entry else { break };
+            if let Some(fired) = &entry.fired {
+                if fired.swap(true, Ordering::AcqRel) {
+                    continue;
+                }
+                self.registry.remove(entry.key, entry.id);
+            }
+            entry.listener.handle_any(event, type_name);
         }
     }
 
-    /// 是否存在 `E` 的监听器
+    /// 是否存在会收到 `E` 的监听器（含全事件监听器）
     pub fn has_listeners<E: Event>(&self) -> bool {
-        if self.total.load(Ordering::Relaxed) == 0 {
-            return false;
-        }
-        let map = self.listeners.read().expect("EventBus 锁中毒");
-        map.get(&TypeId::of::<E>())
-            .map(|v| !v.is_empty())
-            .unwrap_or(false)
+        self.snapshot::<E>().is_some()
     }
 
-    /// `E` 的监听器数量
+    /// 会收到 `E` 的监听器数量（含全事件监听器）
     pub fn listener_count<E: Event>(&self) -> usize {
-        let map = self.listeners.read().expect("EventBus 锁中毒");
-        map.get(&TypeId::of::<E>()).map(|v| v.len()).unwrap_or(0)
+        let tables = self.registry.tables.read().expect("EventBus 锁中毒");
+        tables.typed.get(&TypeId::of::<E>()).map(|v| v.len()).unwrap_or(0) + tables.any.len()
     }
 
-    /// 所有事件类型的监听器总数
+    /// 所有监听器总数（含全事件监听器）
     pub fn total_listeners(&self) -> usize {
-        self.total.load(Ordering::Relaxed)
+        self.registry.total.load(Ordering::Relaxed)
     }
 
-    /// 取 `E` 监听器切片的 `Arc` 快照（读锁内一次 `Arc::clone`，无分配；锁随后释放）。
-    /// 无监听器返回 `None`（不创建任何 `Arc`，快路径零分配）。
-    fn snapshot<E: Event>(&self) -> Option<Arc<[Arc<dyn ErasedListener>]>> {
-        if self.total.load(Ordering::Relaxed) == 0 {
+    /// 取 `E` 监听器切片与全事件监听器切片的 `Arc` 快照（读锁内 `Arc::clone`，无分配；锁随后释放）。
+    /// 均无监听器返回 `None`（快路径零分配）。
+    fn snapshot<E: Event>(&self) -> Option<(Option<ListenerSlice>, ListenerSlice)> {
+        if self.registry.total.load(Ordering::Relaxed) == 0 {
             return None;
         }
-        let map = self.listeners.read().expect("EventBus 锁中毒");
-        match map.get(&TypeId::of::<E>()) {
-            Some(v) if !v.is_empty() => Some(Arc::clone(v)),
-            _ => None,
+        let tables = self.registry.tables.read().expect("EventBus 锁中毒");
+        let typed = tables.typed.get(&TypeId::of::<E>()).filter(|v| !v.is_empty()).map(Arc::clone);
+        if typed.is_none() && tables.any.is_empty() {
+            return None;
         }
+        Some((typed, Arc::clone(&tables.any)))
     }
 }
 
 impl std::fmt::Debug for EventBus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("EventBus")
-            .field("total_listeners", &self.total.load(Ordering::Relaxed))
+            .field("total_listeners", &self.total_listeners())
             .finish()
     }
 }
@@ -246,7 +541,7 @@ mod tests {
         bus.on(move |e: &LoginEvent| {
             assert_eq!(e.user, "张三");
             c.fetch_add(1, Ordering::Relaxed);
-        });
+        }).detach();
         assert_eq!(bus.listener_count::<LoginEvent>(), 1);
         bus.dispatch(&LoginEvent { user: "张三".into() });
         assert_eq!(counter.load(Ordering::Relaxed), 1);
@@ -260,7 +555,7 @@ mod tests {
         let s = Arc::clone(&seen);
         bus.on(move |_: &LoginEvent| {
             s.fetch_add(1, Ordering::Relaxed);
-        });
+        }).detach();
         bus.dispatch(&LogoutEvent);
         bus.dispatch(&LoginEvent { user: "x".into() });
         assert_eq!(seen.load(Ordering::Relaxed), 1, "只应被 LoginEvent 触发一次");
@@ -271,11 +566,11 @@ mod tests {
         let bus = EventBus::new();
         let order = Arc::new(std::sync::Mutex::new(Vec::new()));
         let o1 = Arc::clone(&order);
-        bus.on(move |_: &LoginEvent| o1.lock().unwrap().push(1));
+        bus.on(move |_: &LoginEvent| o1.lock().unwrap().push(1)).detach();
         let o2 = Arc::clone(&order);
-        bus.on(move |_: &LoginEvent| o2.lock().unwrap().push(2));
+        bus.on(move |_: &LoginEvent| o2.lock().unwrap().push(2)).detach();
         let o3 = Arc::clone(&order);
-        bus.on(move |_: &LoginEvent| o3.lock().unwrap().push(3));
+        bus.on(move |_: &LoginEvent| o3.lock().unwrap().push(3)).detach();
 
         bus.dispatch(&LoginEvent { user: "a".into() });
         assert_eq!(*order.lock().unwrap(), vec![1, 2, 3]);
@@ -296,7 +591,7 @@ mod tests {
         let b2 = Arc::clone(&built);
         bus.on(move |_: &LoginEvent| {
             b2.fetch_add(10, Ordering::Relaxed);
-        });
+        }).detach();
         bus.dispatch_if(|| {
             built.fetch_add(1, Ordering::Relaxed);
             LoginEvent { user: "y".into() }
@@ -315,11 +610,11 @@ mod tests {
                 let l = Arc::clone(&self.logins);
                 bus.on(move |_: &LoginEvent| {
                     l.fetch_add(1, Ordering::Relaxed);
-                });
+                }).detach();
                 let lo = Arc::clone(&self.logouts);
                 bus.on(move |_: &LogoutEvent| {
                     lo.fetch_add(1, Ordering::Relaxed);
-                });
+                }).detach();
             }
         }
 
@@ -349,8 +644,8 @@ mod tests {
         bus.on(move |_: &LoginEvent| {
             a.fetch_add(1, Ordering::Relaxed);
             // 在回调中新增一个监听器：重建切片，不影响当前派发（用的是旧快照）
-            bus2.on(|_: &LoginEvent| {});
-        });
+            bus2.on(|_: &LoginEvent| {}).detach();
+        }).detach();
         bus.dispatch(&LoginEvent { user: "x".into() });
         assert_eq!(added.load(Ordering::Relaxed), 1);
         assert_eq!(bus.listener_count::<LoginEvent>(), 2, "回调中应已新增一个监听器");
@@ -359,7 +654,7 @@ mod tests {
         let s = Arc::clone(&second);
         bus.on(move |_: &LoginEvent| {
             s.fetch_add(1, Ordering::Relaxed);
-        });
+        }).detach();
         // 现有 3 个；dispatch 一次
         bus.dispatch(&LoginEvent { user: "y".into() });
         assert_eq!(second.load(Ordering::Relaxed), 1, "新监听器应被后续派发触发");
@@ -374,4 +669,95 @@ mod tests {
         bus.dispatch(&LoginEvent { user: "x".into() });
         bus.dispatch_if(|| LoginEvent { user: "y".into() });
     }
+
+    #[test]
+    fn test_handle_unsubscribes_on_drop_or_explicitly() {
+        let bus = EventBus::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = Arc::clone(&count);
+        let handle = bus.on(move |_: &LoginEvent| {
+            c.fetch_add(1, Ordering::Relaxed);
+        });
+        assert!(handle.is_subscribed());
+        bus.dispatch(&LoginEvent { user: "a".into() });
+        drop(handle);
+        assert_eq!(bus.total_listeners(), 0);
+        assert!(!bus.has_listeners::<LoginEvent>());
+        bus.dispatch(&LoginEvent { user: "b".into() });
+        assert_eq!(count.load(Ordering::Relaxed), 1, "drop 后不再收到事件");
+
+        let handle = bus.on(|_: &LoginEvent| {});
+        assert!(handle.unsubscribe());
+        assert_eq!(bus.total_listeners(), 0);
+
+        // detach 后常驻；总线销毁后句柄 drop 为空操作
+        let bus = EventBus::new();
+        bus.on(|_: &LoginEvent| {}).detach();
+        assert_eq!(bus.total_listeners(), 1);
+        let handle = bus.on(|_: &LogoutEvent| {});
+        drop(bus);
+        assert!(!handle.is_subscribed());
+    }
+
+    #[test]
+    fn test_priority_ordering() {
+        let bus = EventBus::new();
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        for (name, priority) in [("a", 0), ("b", 10), ("c", -5), ("d", 10), ("e", 0)] {
+            let o = Arc::clone(&order);
+            bus.on_with(ListenerOptions::new().with_priority(priority), move |_: &LoginEvent| {
+                o.lock().unwrap().push(name)
+            })
+            .detach();
+        }
+        bus.dispatch(&LoginEvent { user: "x".into() });
+        // 大者先执行，同优先级按注册顺序
+        assert_eq!(*order.lock().unwrap(), vec!["b", "d", "a", "e", "c"]);
+    }
+
+    #[test]
+    fn test_once_listener_fires_once() {
+        let bus = EventBus::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = Arc::clone(&count);
+        let handle = bus.once(move |_: &LoginEvent| {
+            c.fetch_add(1, Ordering::Relaxed);
+        });
+        bus.dispatch(&LoginEvent { user: "a".into() });
+        bus.dispatch(&LoginEvent { user: "b".into() });
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert!(!handle.is_subscribed(), "触发后自动取消");
+        assert!(!handle.unsubscribe());
+        assert_eq!(bus.total_listeners(), 0);
+    }
+
+    #[test]
+    fn test_on_any_receives_every_event() {
+        let bus = EventBus::new();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let s = Arc::clone(&seen);
+        let handle = bus.on_any_with(ListenerOptions::new().with_priority(1), move |event, type_name| {
+            let user = event.downcast_ref::<LoginEvent>().map(|e| e.user.clone());
+            s.lock().unwrap().push((type_name.rsplit("::").next().unwrap().to_string(), user));
+        });
+        let s = Arc::clone(&seen);
+        bus.on(move |e: &LoginEvent| s.lock().unwrap().push(("typed".to_string(), Some(e.user.clone()))))
+            .detach();
+
+        // 全事件监听器让任何事件类型都视为有监听器
+        assert!(bus.has_listeners::<LogoutEvent>());
+        assert_eq!(bus.listener_count::<LoginEvent>(), 2);
+        bus.dispatch(&LoginEvent { user: "张三".into() });
+        bus.dispatch_if(|| LogoutEvent);
+        assert_eq!(
+            *seen.lock().unwrap(),
+            vec![
+                ("LoginEvent".to_string(), Some("张三".to_string())),
+                ("typed".to_string(), Some("张三".to_string())),
+                ("LogoutEvent".to_string(), None),
+            ]
+        );
+        drop(handle);
+        assert!(!bus.has_listeners::<LogoutEvent>());
+    }
 }
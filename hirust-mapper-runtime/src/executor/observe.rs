@@ -0,0 +1,138 @@
+//! SQL 执行观测：各执行点共用的「执行前事件 → span / 计时 → 结果记录 → 日志 → 慢查询 → 执行后事件」流程
+//!
+//! 日志与事件中的参数经 [`Redactor`] 打码。
+
+use std::time::{Duration, Instant};
+
+use hirust_mapper_core::BoundSql;
+
+use crate::event::lifecycle::{AfterSqlEvent, BeforeSqlEvent, SlowQueryEvent, SqlOutcome, StatementContext};
+use crate::event::EventBus;
+use crate::explain::{QueryPlan, SlowQueryExplainer};
+use crate::redact::Redactor;
+use crate::sql_log::{self, ExecutionLog, SqlLogConfig};
+use crate::telemetry::Span;
+
+/// 一次执行的观测配置
+#[derive(Clone, Copy)]
+pub(crate) struct Observer<'a> {
+    pub(crate) sql_log: &'a SqlLogConfig,
+    pub(crate) event_bus: &'a EventBus,
+    pub(crate) explainer: Option<&'a SlowQueryExplainer>,
+    pub(crate) redactor: &'a Redactor,
+}
+
+/// 进行中的一次执行：span 与起始时间（无需计时时为 `None`）
+#[derive(Debug)]
+pub(crate) struct Observation {
+    pub(crate) span: Span,
+    start: Option<Instant>,
+}
+
+impl Observation {
+    fn elapsed(&self) -> Duration {
+        self.start.map(|s| s.elapsed()).unwrap_or_default()
+    }
+}
+
+impl Observer<'_> {
+    /// 派发 [`BeforeSqlEvent`] 并开启 span（父 span 为当前上下文）
+    ///
+    /// 仅当 SQL 日志开启、有 [`AfterSqlEvent`] / [`SlowQueryEvent`] 监听器、配置了执行计划捕获
+    /// 或 span 被订阅时才计时（全关时零开销）。
+    pub(crate) fn begin(&self, statement: &StatementContext, bound: &BoundSql) -> Observation {
+        let bus = self.event_bus;
+        bus.dispatch_if(|| BeforeSqlEvent {
+            raw_sql: bound.sql.clone(),
+            params: self.redactor.redact_params(bound),
+            kind: statement.kind,
+            statement: statement.clone(),
+        });
+        let span = Span::sql(statement);
+        let watch_slow = self.sql_log.slow_threshold_ms > 0
+            && (self.explainer.is_some() || bus.has_listeners::<SlowQueryEvent>());
+        let need_timing =
+            self.sql_log.enabled || bus.has_listeners::<AfterSqlEvent>() || watch_slow || span.is_enabled();
+        Observation { span, start: need_timing.then(Instant::now) }
+    }
+
+    /// 执行结束：记录 span、慢查询执行计划、SQL 日志，派发 [`SlowQueryEvent`] 与 [`AfterSqlEvent`]
+    ///
+    /// 成功的慢查询在有消费方（SQL 日志开启或有 [`SlowQueryEvent`] 监听器）时捕获执行计划。
+    pub(crate) async fn finish(
+        &self,
+        observation: &Observation,
+        statement: &StatementContext,
+        bound: &BoundSql,
+        outcome: impl Fn() -> SqlOutcome,
+    ) {
+        let elapsed = observation.elapsed();
+        observation.span.record_outcome(elapsed, &outcome);
+        let plan = match self.explainer {
+            Some(explainer)
+                if self.sql_log.is_slow(elapsed)
+                    && (self.sql_log.enabled || self.event_bus.has_listeners::<SlowQueryEvent>())
+                    && outcome().is_ok() =>
+            {
+                explainer.capture(bound).await
+            }
+            _ => None,
+        };
+        self.report(statement, bound, elapsed, plan, outcome);
+    }
+
+    /// 同 [`finish`](Self::finish)，但不捕获执行计划（用于无法等待的流式查询）
+    pub(crate) fn finish_sync(
+        &self,
+        observation: &Observation,
+        statement: &StatementContext,
+        bound: &BoundSql,
+        outcome: impl Fn() -> SqlOutcome,
+    ) {
+        let elapsed = observation.elapsed();
+        observation.span.record_outcome(elapsed, &outcome);
+        self.report(statement, bound, elapsed, None, outcome);
+    }
+
+    fn report(
+        &self,
+        statement: &StatementContext,
+        bound: &BoundSql,
+        elapsed: Duration,
+        plan: Option<QueryPlan>,
+        outcome: impl Fn() -> SqlOutcome,
+    ) {
+        if self.sql_log.should_log(elapsed) {
+            let result = outcome();
+            if let Some(level) = self.sql_log.decide(statement, elapsed, !result.is_ok()) {
+                let redacted = self.redactor.redact(bound);
+                let record = ExecutionLog {
+                    statement,
+                    bound: &redacted,
+                    elapsed,
+                    outcome: Some(&result),
+                    plan: plan.as_ref(),
+                };
+                sql_log::write_execution(self.sql_log, level, &record);
+            }
+        }
+        if self.sql_log.is_slow(elapsed) {
+            self.event_bus.dispatch_if(|| SlowQueryEvent {
+                raw_sql: bound.sql.clone(),
+                params: self.redactor.redact_params(bound),
+                statement: statement.clone(),
+                elapsed,
+                threshold: Duration::from_millis(self.sql_log.slow_threshold_ms),
+                plan,
+            });
+        }
+        self.event_bus.dispatch_if(|| AfterSqlEvent {
+            raw_sql: bound.sql.clone(),
+            params: self.redactor.redact_params(bound),
+            kind: statement.kind,
+            statement: statement.clone(),
+            elapsed,
+            outcome: outcome(),
+        });
+    }
+}
@@ -3,24 +3,75 @@
 //! 串联 [`ParameterHandler`]（绑定）→ sqlx 执行 → [`ResultSetHandler`]（映射）。
 //! 所有方法以泛型 `E: sqlx::Executor` 接收执行目标，因此同一套逻辑可作用于
 //! 连接池（`&AnyPool`）或事务连接（`&mut AnyConnection`）。
+//!
+//! `*_within` / `*_with` 变体额外接收 [`StatementContext`]（所属语句与会话），随生命周期事件与
+//! SQL 日志输出；其余方法使用未命名上下文（查询为 `Select`，写语句为 `Other`）。
 
+use std::borrow::Cow;
+use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
-use std::time::Instant;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
 
+use futures_util::stream::BoxStream;
 use futures_util::{Stream, StreamExt};
 use hirust_mapper_core::BoundSql;
 use serde::de::DeserializeOwned;
 use sqlx::any::{AnyQueryResult, AnyRow};
 use sqlx::Executor;
 
-use crate::error::Result;
-use crate::event::lifecycle::{classify_sql, AfterSqlEvent, BeforeSqlEvent, SqlKind, SqlOutcome};
+use crate::error::{MapperRuntimeError, Result};
+use crate::event::lifecycle::{SqlKind, SqlOutcome, StatementContext};
 use crate::event::EventBus;
+use crate::executor::observe::{Observation, Observer};
+use crate::explain::SlowQueryExplainer;
+use crate::redact::Redactor;
 use crate::handler::parameter::ParameterHandler;
 use crate::handler::result_set::ResultSetHandler;
 use crate::sql_log::SqlLogConfig;
-use crate::type_handler::TypeHandlerRegistry;
+use crate::type_handler::{BinaryEncoding, TypeHandlerRegistry};
+
+/// 语句执行时限：超过 `duration` 未完成时放弃等待并返回 [`MapperRuntimeError::Timeout`]。
+///
+/// 超时后连接上可能仍有未完成的语句，调用方须丢弃该连接（不归还连接池），
+/// [`SqlSession`](crate::SqlSession) 已按此处理。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatementTimeout {
+    pub namespace: String,
+    pub id: String,
+    pub duration: Duration,
+}
+
+impl StatementTimeout {
+    pub fn new(namespace: impl Into<String>, id: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            namespace: namespace.into(),
+            id: id.into(),
+            duration,
+        }
+    }
+
+    fn to_error(&self) -> MapperRuntimeError {
+        MapperRuntimeError::Timeout {
+            namespace: self.namespace.clone(),
+            id: self.id.clone(),
+        }
+    }
+}
+
+/// 在可选时限内等待 `fut`（无时限时直接等待）
+pub async fn with_timeout<F, T>(timeout: Option<&StatementTimeout>, fut: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    match timeout {
+        Some(t) => tokio::time::timeout(t.duration, fut)
+            .await
+            .unwrap_or_else(|_| Err(t.to_error())),
+        None => fut.await,
+    }
+}
 
 /// 基础执行器
 ///
@@ -29,6 +80,12 @@ pub struct SimpleExecutor {
     type_handler_registry: Arc<TypeHandlerRegistry>,
     sql_log: Arc<SqlLogConfig>,
     event_bus: Arc<EventBus>,
+    /// Blob 列的结果表示
+    binary_encoding: BinaryEncoding,
+    /// 慢查询执行计划捕获（`[settings] slow_query_explain`）
+    slow_query_explainer: Option<Arc<SlowQueryExplainer>>,
+    /// 日志与事件中的敏感参数打码
+    redactor: Arc<Redactor>,
 }
 
 impl SimpleExecutor {
@@ -38,6 +95,9 @@ impl SimpleExecutor {
             type_handler_registry,
             sql_log: Arc::new(SqlLogConfig::default()),
             event_bus: Arc::new(EventBus::new()),
+            binary_encoding: BinaryEncoding::default(),
+            slow_query_explainer: None,
+            redactor: Arc::new(Redactor::default()),
         }
     }
 
@@ -58,11 +118,40 @@ impl SimpleExecutor {
         self
     }
 
+    /// 设置 Blob 列的结果表示（`[settings] binary_encoding`）
+    pub fn with_binary_encoding(mut self, binary_encoding: BinaryEncoding) -> Self {
+        self.binary_encoding = binary_encoding;
+        self
+    }
+
+    /// 设置慢查询执行计划捕获器：达到 `sql_log.slow_threshold_ms` 的语句附带执行计划
+    /// 写入 SQL 日志与 [`SlowQueryEvent`](crate::SlowQueryEvent)
+    pub fn with_slow_query_explainer(mut self, explainer: Arc<SlowQueryExplainer>) -> Self {
+        self.slow_query_explainer = Some(explainer);
+        self
+    }
+
+    /// 设置敏感参数打码器（默认仅打码 `#{.., redact=true}` 声明的参数）
+    pub fn with_redactor(mut self, redactor: Arc<Redactor>) -> Self {
+        self.redactor = redactor;
+        self
+    }
+
     /// 事件总线引用
     pub fn event_bus(&self) -> &EventBus {
         &self.event_bus
     }
 
+    /// 本执行器的观测配置（SQL 日志、事件总线、慢查询执行计划、参数打码）
+    pub(crate) fn observer(&self) -> Observer<'_> {
+        Observer {
+            sql_log: &self.sql_log,
+            event_bus: &self.event_bus,
+            explainer: self.slow_query_explainer.as_deref(),
+            redactor: &self.redactor,
+        }
+    }
+
     /// 类型处理器注册表
     pub fn type_handler_registry(&self) -> &TypeHandlerRegistry {
         &self.type_handler_registry
@@ -73,32 +162,34 @@ impl SimpleExecutor {
     where
         E: Executor<'q, Database = sqlx::Any>,
     {
-        let args = ParameterHandler::bind_arguments(bound)?;
-        let bus = &self.event_bus;
-        bus.dispatch_if(|| BeforeSqlEvent {
-            raw_sql: bound.sql.clone(),
-            params: bound.parameters.clone(),
-            kind: SqlKind::Select,
-        });
-        // 仅当 SQL 日志开启 或 有 AfterSqlEvent 监听器时才计时（全关时零开销）
-        let need_timing = self.sql_log.enabled || bus.has_listeners::<AfterSqlEvent>();
-        let start = need_timing.then(Instant::now);
-        let result = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args)
-            .fetch_all(executor)
+        self.query_rows_within(bound, executor, &StatementContext::unnamed(SqlKind::Select), None)
             .await
-            .map_err(crate::error::MapperRuntimeError::from);
-        let elapsed = start.map(|s| s.elapsed()).unwrap_or_default();
-        crate::sql_log::log_execution(&self.sql_log, bound, elapsed);
-        bus.dispatch_if(|| AfterSqlEvent {
-            raw_sql: bound.sql.clone(),
-            params: bound.parameters.clone(),
-            kind: SqlKind::Select,
-            elapsed,
-            outcome: match &result {
-                Ok(rows) => SqlOutcome::Fetched(rows.len()),
-                Err(e) => SqlOutcome::Failed(e.to_string()),
-            },
-        });
+    }
+
+    /// 在时限内执行 `statement` 的查询，返回原始行（超时返回 [`MapperRuntimeError::Timeout`]）
+    pub async fn query_rows_within<'q, E>(
+        &self,
+        bound: &'q BoundSql,
+        executor: E,
+        statement: &StatementContext,
+        timeout: Option<&StatementTimeout>,
+    ) -> Result<Vec<AnyRow>>
+    where
+        E: Executor<'q, Database = sqlx::Any>,
+    {
+        let args = ParameterHandler::bind_arguments_with(bound, &self.type_handler_registry)?;
+        let observer = self.observer();
+        let observation = observer.begin(statement, bound);
+        let fetch = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args).fetch_all(executor);
+        let result = observation
+            .span
+            .instrument(with_timeout(timeout, async { fetch.await.map_err(MapperRuntimeError::from) }))
+            .await;
+        let outcome = || match &result {
+            Ok(rows) => SqlOutcome::Fetched(rows.len()),
+            Err(e) => SqlOutcome::Failed(e.to_string()),
+        };
+        observer.finish(&observation, statement, bound, outcome).await;
         result
     }
 
@@ -114,6 +205,9 @@ impl SimpleExecutor {
     ///     // ...
     /// }
     /// ```
+    ///
+    /// 创建时派发 [`BeforeSqlEvent`](crate::BeforeSqlEvent)；流结束（耗尽、出错或被提前丢弃）时记录 SQL 日志并派发
+    /// [`AfterSqlEvent`](crate::AfterSqlEvent)，其耗时为创建到结束的总时长（含调用方逐行处理的时间）。
     pub fn query_rows_stream<'q, E>(
         &self,
         bound: &'q BoundSql,
@@ -122,14 +216,62 @@ impl SimpleExecutor {
     where
         E: Executor<'q, Database = sqlx::Any> + Send + 'q,
     {
-        let args = match ParameterHandler::bind_arguments(bound) {
+        self.query_rows_stream_with(bound, executor, StatementContext::unnamed(SqlKind::Select))
+    }
+
+    /// 流式查询 `statement`（事件与日志附带语句上下文，其余同 [`query_rows_stream`](Self::query_rows_stream)）
+    pub fn query_rows_stream_with<'q, E>(
+        &self,
+        bound: &'q BoundSql,
+        executor: E,
+        statement: StatementContext,
+    ) -> Pin<Box<dyn Stream<Item = Result<AnyRow>> + Send + 'q>>
+    where
+        E: Executor<'q, Database = sqlx::Any> + Send + 'q,
+    {
+        self.observed_rows_stream(Cow::Borrowed(bound), executor, statement)
+    }
+
+    /// 流式查询（持有 `bound`）：流只借用 `executor`，可从构建 [`BoundSql`] 的函数中直接返回。
+    /// 事件与日志同 [`query_rows_stream_with`](Self::query_rows_stream_with)。
+    pub fn query_rows_stream_owned<'e, E>(
+        &self,
+        bound: BoundSql,
+        executor: E,
+        statement: StatementContext,
+    ) -> Pin<Box<dyn Stream<Item = Result<AnyRow>> + Send + 'e>>
+    where
+        E: Executor<'e, Database = sqlx::Any> + Send + 'e,
+    {
+        self.observed_rows_stream(Cow::Owned(bound), executor, statement)
+    }
+
+    fn observed_rows_stream<'q, E>(
+        &self,
+        bound: Cow<'q, BoundSql>,
+        executor: E,
+        statement: StatementContext,
+    ) -> Pin<Box<dyn Stream<Item = Result<AnyRow>> + Send + 'q>>
+    where
+        E: Executor<'q, Database = sqlx::Any> + Send + 'q,
+    {
+        let args = match ParameterHandler::bind_arguments_with(&bound, &self.type_handler_registry) {
             Ok(a) => a,
             Err(e) => return Box::pin(futures_util::stream::once(async move { Err(e) })),
         };
-        let s = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args)
-            .fetch(executor)
-            .map(|res| res.map_err(crate::error::MapperRuntimeError::from));
-        Box::pin(s)
+        let observation = self.observer().begin(&statement, &bound);
+        let inner = sqlx::query_with(sqlx::AssertSqlSafe(bound.sql.clone()), args).fetch(executor);
+        Box::pin(ObservedRows {
+            inner,
+            bound,
+            statement,
+            observation,
+            sql_log: Arc::clone(&self.sql_log),
+            event_bus: Arc::clone(&self.event_bus),
+            redactor: Arc::clone(&self.redactor),
+            fetched: 0,
+            finished: false,
+        })
     }
 
     /// 流式查询并逐行映射为 `T`（[`query`](Self::query) 的流式版本）
@@ -143,7 +285,8 @@ impl SimpleExecutor {
         T: DeserializeOwned + Send + 'q,
     {
         let row_stream = self.query_rows_stream(bound, executor);
-        Box::pin(row_stream.map(|r| r.and_then(|row| ResultSetHandler::map_row::<T>(&row))))
+        let binary_encoding = self.binary_encoding;
+        Box::pin(row_stream.map(move |r| r.and_then(|row| ResultSetHandler::map_row_with::<T>(&row, binary_encoding))))
     }
 
     /// 执行查询，将每行映射为 `T`
@@ -154,7 +297,7 @@ impl SimpleExecutor {
     {
         let rows = self.query_rows(bound, executor).await?;
         // map_rows 是无状态的关联函数（列类型按 AnyTypeInfoKind 静态分派）
-        ResultSetHandler::map_rows(rows)
+        ResultSetHandler::map_rows_with(rows, self.binary_encoding)
     }
 
     /// 执行单行查询，返回映射后的 `Option<T>`（0 或 1 行；多于 1 行报错）
@@ -170,8 +313,8 @@ impl SimpleExecutor {
         let rows = self.query_rows(bound, executor).await?;
         match rows.len() {
             0 => Ok(None),
-            1 => Ok(Some(ResultSetHandler::map_row(&rows[0])?)),
-            n => Err(crate::error::MapperRuntimeError::TooManyRows { actual: n }),
+            1 => Ok(Some(ResultSetHandler::map_row_with(&rows[0], self.binary_encoding)?)),
+            n => Err(MapperRuntimeError::TooManyRows { actual: n }),
         }
     }
 
@@ -180,41 +323,111 @@ impl SimpleExecutor {
     where
         E: Executor<'q, Database = sqlx::Any>,
     {
-        let args = ParameterHandler::bind_arguments(bound)?;
-        let kind = classify_sql(&bound.sql);
-        let bus = &self.event_bus;
-        bus.dispatch_if(|| BeforeSqlEvent {
-            raw_sql: bound.sql.clone(),
-            params: bound.parameters.clone(),
-            kind,
-        });
-        // 仅当 SQL 日志开启 或 有 AfterSqlEvent 监听器时才计时（全关时零开销）
-        let need_timing = self.sql_log.enabled || bus.has_listeners::<AfterSqlEvent>();
-        let start = need_timing.then(Instant::now);
-        let result = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args)
-            .execute(executor)
+        self.execute_within(bound, executor, &StatementContext::unnamed(SqlKind::Other), None)
             .await
-            .map_err(crate::error::MapperRuntimeError::from);
-        let elapsed = start.map(|s| s.elapsed()).unwrap_or_default();
-        crate::sql_log::log_execution(&self.sql_log, bound, elapsed);
-        bus.dispatch_if(|| AfterSqlEvent {
-            raw_sql: bound.sql.clone(),
-            params: bound.parameters.clone(),
-            kind,
-            elapsed,
-            outcome: match &result {
-                Ok(r) => SqlOutcome::Affected(r.rows_affected()),
-                Err(e) => SqlOutcome::Failed(e.to_string()),
-            },
-        });
+    }
+
+    /// 在时限内执行 `statement` 的更新（超时返回 [`MapperRuntimeError::Timeout`]）
+    pub async fn execute_within<'q, E>(
+        &self,
+        bound: &'q BoundSql,
+        executor: E,
+        statement: &StatementContext,
+        timeout: Option<&StatementTimeout>,
+    ) -> Result<AnyQueryResult>
+    where
+        E: Executor<'q, Database = sqlx::Any>,
+    {
+        let args = ParameterHandler::bind_arguments_with(bound, &self.type_handler_registry)?;
+        let observer = self.observer();
+        let observation = observer.begin(statement, bound);
+        let exec = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args).execute(executor);
+        let result = observation
+            .span
+            .instrument(with_timeout(timeout, async { exec.await.map_err(MapperRuntimeError::from) }))
+            .await;
+        let outcome = || match &result {
+            Ok(r) => SqlOutcome::Affected(r.rows_affected()),
+            Err(e) => SqlOutcome::Failed(e.to_string()),
+        };
+        observer.finish(&observation, statement, bound, outcome).await;
         result
     }
 }
 
+/// 行流观察器：包装 sqlx 行流，在流结束时记录 SQL 日志并派发 [`AfterSqlEvent`](crate::AfterSqlEvent)
+///
+/// 正常耗尽为 `Fetched(行数)`，出错为 `Failed`；调用方提前丢弃流时按已拉取行数记为 `Fetched`。
+/// 流结束于同步的 `poll_next` / `drop` 中，慢查询不捕获执行计划（[`SlowQueryEvent`](crate::SlowQueryEvent) 照常派发）。
+struct ObservedRows<'q> {
+    inner: BoxStream<'q, sqlx::Result<AnyRow>>,
+    bound: Cow<'q, BoundSql>,
+    statement: StatementContext,
+    /// 执行 span（每次 poll 时进入）与起始时间
+    observation: Observation,
+    sql_log: Arc<SqlLogConfig>,
+    event_bus: Arc<EventBus>,
+    redactor: Arc<Redactor>,
+    fetched: usize,
+    finished: bool,
+}
+
+impl ObservedRows<'_> {
+    fn finish(&mut self, outcome: impl Fn() -> SqlOutcome) {
+        self.finished = true;
+        let observer = Observer {
+            sql_log: &self.sql_log,
+            event_bus: &self.event_bus,
+            explainer: None,
+            redactor: &self.redactor,
+        };
+        observer.finish_sync(&self.observation, &self.statement, &self.bound, outcome);
+    }
+}
+
+impl Stream for ObservedRows<'_> {
+    type Item = Result<AnyRow>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+        let this = &mut *self;
+        match ready!(this.observation.span.in_scope(|| this.inner.poll_next_unpin(cx))) {
+            Some(Ok(row)) => {
+                self.fetched += 1;
+                Poll::Ready(Some(Ok(row)))
+            }
+            Some(Err(e)) => {
+                let e = MapperRuntimeError::from(e);
+                let message = e.to_string();
+                self.finish(|| SqlOutcome::Failed(message.clone()));
+                Poll::Ready(Some(Err(e)))
+            }
+            None => {
+                let fetched = self.fetched;
+                self.finish(|| SqlOutcome::Fetched(fetched));
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+impl Drop for ObservedRows<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let fetched = self.fetched;
+            self.finish(|| SqlOutcome::Fetched(fetched));
+        }
+    }
+}
+
 /// 独立辅助函数：执行绑定并返回受影响行数（无需 SimpleExecutor 实例）。
 ///
 /// `sql_log` 控制 SQL 执行日志；`event_bus` 用于派发执行前/后生命周期事件。
-/// 传入 [`SqlLogConfig::default`]（关闭）则不记录，传入空 [`EventBus`] 则不派发。
+/// 传入 [`SqlLogConfig::default`]（关闭）则不记录，传入空 [`EventBus`] 则不派发。慢查询不捕获执行计划，
+/// 参数仅按 `#{.., redact=true}` 打码。
+/// 不属于 Mapper 语句，事件与日志使用未命名上下文（[`SqlKind::Other`]）。
 pub async fn execute_rows_affected<'q, E>(
     bound: &'q BoundSql,
     executor: E,
@@ -225,31 +438,19 @@ where
     E: Executor<'q, Database = sqlx::Any>,
 {
     let args = ParameterHandler::bind_arguments(bound)?;
-    let kind = classify_sql(&bound.sql);
-    event_bus.dispatch_if(|| BeforeSqlEvent {
-        raw_sql: bound.sql.clone(),
-        params: bound.parameters.clone(),
-        kind,
-    });
-    // 仅当 SQL 日志开启 或 有 AfterSqlEvent 监听器时才计时（全关时零开销）
-    let need_timing = sql_log.enabled || event_bus.has_listeners::<AfterSqlEvent>();
-    let start = need_timing.then(Instant::now);
-    let result = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args)
-        .execute(executor)
+    let statement = StatementContext::unnamed(SqlKind::Other);
+    let redactor = Redactor::default();
+    let observer = Observer { sql_log, event_bus, explainer: None, redactor: &redactor };
+    let observation = observer.begin(&statement, bound);
+    let result = observation
+        .span
+        .instrument(sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args).execute(executor))
         .await;
-    let elapsed = start.map(|s| s.elapsed()).unwrap_or_default();
-    crate::sql_log::log_execution(sql_log, bound, elapsed);
-    let outcome = match &result {
+    let outcome = || match &result {
         Ok(r) => SqlOutcome::Affected(r.rows_affected()),
         Err(e) => SqlOutcome::Failed(e.to_string()),
     };
-    event_bus.dispatch_if(|| AfterSqlEvent {
-        raw_sql: bound.sql.clone(),
-        params: bound.parameters.clone(),
-        kind,
-        elapsed,
-        outcome,
-    });
+    observer.finish_sync(&observation, &statement, bound, outcome);
     let result = result?;
     Ok(result.rows_affected())
 }
@@ -0,0 +1,213 @@
+//! 执行计划（EXPLAIN）捕获
+//!
+//! - [`QueryPlan`]：按方言 `EXPLAIN` / `EXPLAIN QUERY PLAN` 取回的执行计划行
+//! - [`explain_bound`]：在指定执行目标上对 [`BoundSql`] 取执行计划（绑定相同参数）
+//! - [`SlowQueryExplainer`]：慢查询自动捕获。在**独立的池连接**上执行（仅取空闲连接，
+//!   不等待），并按最小间隔限流，避免慢查询高发时 EXPLAIN 本身加重数据库负担
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use hirust_mapper_core::BoundSql;
+use serde_json::Value;
+use sqlx::AnyPool;
+use sqlx::Executor;
+
+use crate::dialect::Dialect;
+use crate::error::{MapperRuntimeError, Result};
+use crate::handler::parameter::ParameterHandler;
+use crate::handler::result_set::ResultSetHandler;
+use crate::type_handler::{BinaryEncoding, TypeHandlerRegistry};
+
+/// 执行计划
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    /// 实际执行的 EXPLAIN 语句
+    pub sql: String,
+    /// 计划行（列名 → 值，列随方言而异）
+    pub rows: Vec<Value>,
+}
+
+impl QueryPlan {
+    /// 每行一条的可读文本：sqlite 取 `detail` 列，单列结果（postgres `QUERY PLAN`）取其值，
+    /// 其余按 `列=值` 以逗号连接
+    pub fn lines(&self) -> Vec<String> {
+        self.rows.iter().map(plan_line).collect()
+    }
+}
+
+/// 多行计划以换行连接
+impl std::fmt::Display for QueryPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.lines().join("\n"))
+    }
+}
+
+fn plan_line(row: &Value) -> String {
+    let text = |v: &Value| match v {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let Value::Object(columns) = row else {
+        return text(row);
+    };
+    if let Some(detail) = columns.get("detail") {
+        return text(detail);
+    }
+    if columns.len() == 1 {
+        return columns.values().next().map(text).unwrap_or_default();
+    }
+    columns
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, text(v)))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 在 `executor` 上对 `bound` 执行方言的 EXPLAIN 语句（参数按原样绑定）
+pub async fn explain_bound<'q, E>(
+    dialect: Dialect,
+    bound: &BoundSql,
+    executor: E,
+    type_handler_registry: &TypeHandlerRegistry,
+) -> Result<QueryPlan>
+where
+    E: Executor<'q, Database = sqlx::Any>,
+{
+    let args = ParameterHandler::bind_arguments_with(bound, type_handler_registry)?;
+    let sql = dialect.explain_sql(&bound.sql);
+    let rows = sqlx::query_with(sqlx::AssertSqlSafe(sql.clone()), args)
+        .fetch_all(executor)
+        .await
+        .map_err(MapperRuntimeError::from)?;
+    let rows = rows
+        .iter()
+        .map(|row| ResultSetHandler::map_row_with::<Value>(row, BinaryEncoding::default()))
+        .collect::<Result<_>>()?;
+    Ok(QueryPlan { sql, rows })
+}
+
+/// 慢查询执行计划捕获器（由工厂按 `[settings] slow_query_explain` 创建，所有 session 共享）
+pub struct SlowQueryExplainer {
+    pool: AnyPool,
+    dialect: Dialect,
+    type_handler_registry: Arc<TypeHandlerRegistry>,
+    /// 两次捕获的最小间隔
+    min_interval: Duration,
+    /// 上次捕获时间
+    last_capture: Mutex<Option<Instant>>,
+}
+
+impl std::fmt::Debug for SlowQueryExplainer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SlowQueryExplainer")
+            .field("dialect", &self.dialect)
+            .field("min_interval", &self.min_interval)
+            .finish()
+    }
+}
+
+impl SlowQueryExplainer {
+    /// 默认最小捕获间隔
+    pub const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// 在 `pool` 上按 `dialect` 捕获执行计划
+    pub fn new(pool: AnyPool, dialect: Dialect, type_handler_registry: Arc<TypeHandlerRegistry>) -> Self {
+        Self {
+            pool,
+            dialect,
+            type_handler_registry,
+            min_interval: Self::DEFAULT_MIN_INTERVAL,
+            last_capture: Mutex::new(None),
+        }
+    }
+
+    /// 设置两次捕获的最小间隔（`0` 表示不限流）
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// 捕获 `bound` 的执行计划
+    ///
+    /// 距上次捕获不足最小间隔、连接池无空闲连接或 EXPLAIN 失败时返回 `None`（失败经运行时诊断消息告警），
+    /// 从不阻塞等待连接，也不占用慢查询所在的连接。
+    pub async fn capture(&self, bound: &BoundSql) -> Option<QueryPlan> {
+        // 先取连接再占用名额：无空闲连接时不消耗限流间隔
+        let mut conn = self.pool.try_acquire()?;
+        if !self.try_reserve() {
+            return None;
+        }
+        match explain_bound(self.dialect, bound, &mut *conn, &self.type_handler_registry).await {
+            Ok(plan) => Some(plan),
+            Err(e) => {
+                crate::telemetry::warn(&format!("慢查询执行计划捕获失败: {}", e));
+                None
+            }
+        }
+    }
+
+    /// 限流：距上次捕获已满最小间隔时占用本次名额
+    fn try_reserve(&self) -> bool {
+        let mut last = self.last_capture.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        if last.is_some_and(|t| now.duration_since(t) < self.min_interval) {
+            return false;
+        }
+        *last = Some(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_plan_lines() {
+        let plan = QueryPlan {
+            sql: "EXPLAIN QUERY PLAN SELECT 1".into(),
+            rows: vec![
+                json!({"id": 2, "parent": 0, "notused": 0, "detail": "SCAN users"}),
+                json!({"QUERY PLAN": "Seq Scan on users"}),
+                json!({"id": 1, "type": "ALL"}),
+            ],
+        };
+        assert_eq!(plan.lines(), ["SCAN users", "Seq Scan on users", "id=1, type=ALL"]);
+        assert_eq!(plan.to_string(), "SCAN users\nSeq Scan on users\nid=1, type=ALL");
+    }
+
+    #[tokio::test]
+    async fn test_capture_is_rate_limited() {
+        sqlx::any::install_default_drivers();
+        let pool = AnyPool::connect("sqlite::memory:").await.unwrap();
+        let explainer = SlowQueryExplainer::new(pool, Dialect::Sqlite, Arc::new(TypeHandlerRegistry::with_defaults()))
+            .with_min_interval(Duration::from_secs(60));
+        let bound = BoundSql { sql: "SELECT ? AS x".into(), parameters: vec![json!(1)], ..Default::default() };
+        let plan = explainer.capture(&bound).await.expect("首次捕获");
+        assert_eq!(plan.sql, "EXPLAIN QUERY PLAN SELECT ? AS x");
+        assert!(explainer.capture(&bound).await.is_none(), "间隔内不再捕获");
+
+        let unlimited = SlowQueryExplainer { min_interval: Duration::ZERO, ..explainer };
+        assert!(unlimited.try_reserve() && unlimited.try_reserve(), "间隔为 0 时不限流");
+    }
+
+    #[tokio::test]
+    async fn test_capture_without_idle_connection_keeps_slot() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        let explainer = SlowQueryExplainer::new(pool.clone(), Dialect::Sqlite, Arc::new(TypeHandlerRegistry::with_defaults()))
+            .with_min_interval(Duration::from_secs(60));
+        let bound = BoundSql { sql: "SELECT 1".into(), ..Default::default() };
+
+        let busy = pool.acquire().await.unwrap();
+        assert!(explainer.capture(&bound).await.is_none(), "无空闲连接时跳过");
+        drop(busy);
+        // 连接在后台归还连接池
+        while pool.num_idle() == 0 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(explainer.capture(&bound).await.is_some(), "跳过的捕获不占用限流名额");
+    }
+}
@@ -7,4 +7,4 @@ pub mod parameter;
 pub mod result_set;
 
 pub use parameter::{bind_value, ParameterHandler};
-pub use result_set::ResultSetHandler;
+pub use result_set::{key_rows_by, DuplicateKeyPolicy, OutOfOrderPolicy, ResultMapGrouper, ResultSetHandler};
@@ -2,14 +2,16 @@
 //!
 //! [`ParameterHandler`] 负责将 [`BoundSql`] 的参数列表（`Vec<serde_json::Value>`）
 //! 绑定到 sqlx 查询。由于参数中间表示统一为 `serde_json::Value`，绑定按 Value 的
-//! 变体分派，将每种 JSON 类型映射到 sqlx::Any 兼容的原语类型。
+//! 变体分派，将每种 JSON 类型映射到 sqlx::Any 兼容的原语类型；占位符声明了
+//! `typeHandler` / `rustType` / `jdbcType` 时改由 [`TypeHandlerRegistry`] 中的处理器绑定。
 
-use hirust_mapper_core::BoundSql;
+use hirust_mapper_core::{BoundSql, ParameterMapping};
 use serde_json::Value;
 use sqlx::any::AnyArguments;
 use sqlx::Arguments;
 
 use crate::error::{MapperRuntimeError, Result};
+use crate::type_handler::{TypeHandler, TypeHandlerRegistry};
 
 /// JSON Value → sqlx::Any 参数绑定的映射
 ///
@@ -17,8 +19,8 @@ use crate::error::{MapperRuntimeError, Result};
 /// |-------------------|-------------------|
 /// | `Null`             | `Option::<i64>::None` |
 /// | `Bool(b)`          | `bool`            |
-/// | `Number` (整数)    | `i64`             |
-/// | `Number` (浮点)    | `f64`             |
+/// | `Number` (整数)    | `i64`；超出 i64 的 u64 为十进制文本 |
+/// | `Number` (浮点)    | `f64`；启用 `arbitrary_precision` 且无法被 f64 精确表示时为十进制文本 |
 /// | `String(s)`        | `String`          |
 /// | `Array` / `Object` | 序列化为 JSON 字符串 |
 pub fn bind_value(arguments: &mut AnyArguments, value: &Value) -> Result<()> {
@@ -28,6 +30,10 @@ pub fn bind_value(arguments: &mut AnyArguments, value: &Value) -> Result<()> {
         Value::Number(n) => {
             if let Some(i) = n.as_i64() {
                 arguments.add(i)
+            } else if n.is_u64() || !is_exact_f64(n) {
+                // 大整数 / 高精度小数以十进制文本绑定，避免经浮点丢失精度；
+                // sqlite / mysql 按列类型隐式转换，postgres 须在 SQL 中显式转换（如 `CAST(#{x} AS NUMERIC)`）
+                arguments.add(n.to_string())
             } else if let Some(f) = n.as_f64() {
                 arguments.add(f)
             } else {
@@ -46,6 +52,44 @@ pub fn bind_value(arguments: &mut AnyArguments, value: &Value) -> Result<()> {
     })
 }
 
+/// 数字能否被 f64 精确表示：任意精度模式下原始十进制文本与其 f64 最短表示的**数值**须相等
+///（按数值而非文本比较：`1.50`、`1e2` 可精确表示，`0.30000000000000001` 不能）
+#[cfg(feature = "arbitrary_precision")]
+fn is_exact_f64(n: &serde_json::Number) -> bool {
+    let text = n.to_string();
+    text.parse::<f64>().is_ok_and(|f| {
+        f.is_finite() && decimal_parts(&text).is_some_and(|parts| decimal_parts(&format!("{:e}", f)) == Some(parts))
+    })
+}
+
+/// 十进制数字文本的规范形式：(负号, 去掉首尾 0 的有效数字, 十进制指数)；零统一为 `(false, "", 0)`
+#[cfg(feature = "arbitrary_precision")]
+fn decimal_parts(text: &str) -> Option<(bool, String, i64)> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (mantissa, exponent) = match text.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().ok()?),
+        None => (text, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = format!("{}{}", int, frac);
+    let digits = digits.trim_start_matches('0');
+    let significant = digits.trim_end_matches('0');
+    if significant.is_empty() {
+        return Some((false, String::new(), 0));
+    }
+    let exponent = exponent - frac.len() as i64 + (digits.len() - significant.len()) as i64;
+    Some((negative, significant.to_string(), exponent))
+}
+
+/// 数字能否被 f64 精确表示（非任意精度模式下浮点数字本身即 f64）
+#[cfg(not(feature = "arbitrary_precision"))]
+fn is_exact_f64(_n: &serde_json::Number) -> bool {
+    true
+}
+
 /// 参数处理器：将 [`BoundSql`] 绑定为可执行的 sqlx 查询
 pub struct ParameterHandler;
 
@@ -66,6 +110,45 @@ impl ParameterHandler {
         Ok(arguments)
     }
 
+    /// 按参数映射绑定：声明了类型的参数经注册表中的处理器绑定，其余按 Value 变体绑定
+    ///
+    /// 查找优先级：`typeHandler`（按名称，未注册报错）> `rustType` > `jdbcType`；
+    /// `rustType` / `jdbcType` 无匹配处理器时退回默认绑定。
+    pub fn bind_arguments_with(bound: &BoundSql, registry: &TypeHandlerRegistry) -> Result<AnyArguments> {
+        let mut arguments = AnyArguments::default();
+        arguments.reserve(bound.parameters.len(), 0);
+        for (index, value) in bound.parameters.iter().enumerate() {
+            let handler = match bound.parameter_mapping(index) {
+                Some(mapping) => Self::mapping_handler(mapping, registry)?,
+                None => None,
+            };
+            match handler {
+                Some(handler) => handler.set_parameter(value, &mut arguments)?,
+                None => bind_value(&mut arguments, value)?,
+            }
+        }
+        Ok(arguments)
+    }
+
+    /// 参数映射声明的处理器（未声明类型时为 `None`）
+    fn mapping_handler<'r>(
+        mapping: &ParameterMapping,
+        registry: &'r TypeHandlerRegistry,
+    ) -> Result<Option<&'r std::sync::Arc<dyn TypeHandler>>> {
+        if let Some(name) = &mapping.type_handler {
+            return registry.get(name).map(Some).ok_or_else(|| {
+                MapperRuntimeError::TypeConversion(format!(
+                    "参数 '{}' 声明的类型处理器 '{}' 未注册",
+                    mapping.property, name
+                ))
+            });
+        }
+        if let Some(handler) = mapping.rust_type.as_deref().and_then(|t| registry.for_rust_type(t)) {
+            return Ok(Some(handler));
+        }
+        Ok(mapping.jdbc_type.as_deref().and_then(|t| registry.for_jdbc_type(t)))
+    }
+
     /// 绑定单个 Value 到已有参数缓冲区
     pub fn bind_one(arguments: &mut AnyArguments, value: &Value) -> Result<()> {
         bind_value(arguments, value)
@@ -97,6 +180,7 @@ mod tests {
         BoundSql {
             sql: sql.to_string(),
             parameters: vec![],
+            ..Default::default()
         }
     }
 
@@ -147,12 +231,14 @@ mod tests {
         let bound = BoundSql {
             sql: "SELECT ?, ?, ?".to_string(),
             parameters: vec![Value::Null, Value::Null, Value::Null],
+            ..Default::default()
         };
         assert!(ParameterHandler::validate_placeholder_count(&bound).is_ok());
 
         let bound_mismatch = BoundSql {
             sql: "SELECT ?, ?".to_string(),
             parameters: vec![Value::Null],
+            ..Default::default()
         };
         let result = ParameterHandler::validate_placeholder_count(&bound_mismatch);
         assert!(result.is_err());
@@ -192,4 +278,50 @@ mod tests {
         assert!(row.0.is_none());
         assert_eq!(row.1, "[1,2]"); // Array 序列化为 JSON 字符串
     }
+
+    #[tokio::test]
+    async fn test_bind_large_u64_as_text() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE t3 (big TEXT, small INTEGER)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut bound = bound_for("INSERT INTO t3 (big, small) VALUES (?, ?)");
+        bound.parameters = vec![Value::from(u64::MAX), Value::from(5u64)];
+        let args = ParameterHandler::bind_arguments(&bound).unwrap();
+        sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // 超出 i64 的 u64 以十进制文本绑定（不经 f64 舍入），可容纳的值仍按整数绑定
+        let row: (String, i64) = sqlx::query_as("SELECT big, small FROM t3")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(row.0, u64::MAX.to_string());
+        assert_eq!(row.1, 5);
+    }
+
+    #[cfg(feature = "arbitrary_precision")]
+    #[test]
+    fn test_is_exact_f64_compares_values() {
+        use std::str::FromStr;
+        let exact = |text: &str| is_exact_f64(&serde_json::Number::from_str(text).unwrap());
+        // 文本不同但数值可由 f64 精确往返
+        for text in ["1.50", "1e2", "2.5E-3", "-0.0", "0.1", "-12.340"] {
+            assert!(exact(text), "{}", text);
+        }
+        // 位数超出 f64 精度：按十进制文本绑定
+        for text in ["12345678901234567.89", "0.30000000000000001", "1e400"] {
+            assert!(!exact(text), "{}", text);
+        }
+    }
 }
@@ -12,11 +12,29 @@
 //! | `SmallInt`/`Integer`/`BigInt` | `Number`(i64) |
 //! | `Real`/`Double`  | `Number`(f64)     |
 //! | `Text`           | `String`          |
-//! | `Blob`           | `String`（UTF-8 lossy）|
+//! | `Blob`           | base64 `String` 或字节数组（[`BinaryEncoding`]，默认 base64）|
+//!
+//! ResultMap 中声明了 `typeHandler` / `rustType` / `jdbcType` 的列改由注册表中对应的
+//! [`TypeHandler::get_result`] 解码（优先级同此顺序）；`typeHandler` 指向未注册的处理器时报错，
+//! 无法识别的 `rustType` / `jdbcType` 回退为上表的按值分派。
+//!
+//! sqlx Any 驱动只暴露值的种类而不暴露列的声明类型，未声明类型的 DECIMAL / NUMERIC 列无法识别：
+//! sqlite 的 NUMERIC 亲和列按实际存储（REAL → f64，可能已丢失精度）解码，mysql 的 DECIMAL 与 postgres 的
+//! NUMERIC 列不被 Any 驱动支持。需要精确小数时在 SQL 中转为文本（如 `CAST(amount AS CHAR)` / `amount::text`），
+//! 并在 ResultMap 中声明 `rustType="decimal"`（`rust_decimal` feature）；`arbitrary_precision` 本身不改变列的解码。
+//!
+//! 无 ResultMap 的查询若 `resultType` 为已注册的 [`MapperModel`](crate::MapperModel)，
+//! 则按模型的列注解改名（列名 → 字段名），声明了 `type_handler` 的列同样经处理器解码。
 
-use std::collections::HashMap;
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::Arc;
 
-use hirust_mapper_core::{NestedMapping, ResultMap};
+use hirust_mapper_core::{NestedMapping, ResultColumn, ResultMap};
 use serde::de::DeserializeOwned;
 use serde_json::{Number, Value};
 use sqlx::any::{AnyRow, AnyTypeInfoKind};
@@ -25,19 +43,37 @@ use sqlx::Row;
 use sqlx::ValueRef;
 
 use crate::error::{MapperRuntimeError, Result};
-use crate::type_handler::TypeHandlerRegistry;
+use crate::registry::{ModelRegistry, TypeAliasRegistry};
+use crate::type_handler::{BinaryEncoding, TypeHandler, TypeHandlerRegistry};
 
 /// 结果集处理器：AnyRow → serde_json::Value → T
+///
+/// 无 ResultMap 的映射（[`map_row`](Self::map_row) 等）为关联函数，按值类型静态分派；
+/// ResultMap 映射为实例方法，按列声明经类型处理器注册表解码。
+#[derive(Clone)]
 pub struct ResultSetHandler {
-    /// 类型处理器注册表（用于自定义类型解码，可选）
-    type_handlers: TypeHandlerRegistry,
+    /// 类型处理器注册表（ResultMap 列按 typeHandler / rustType / jdbcType 查找）
+    type_handlers: Arc<TypeHandlerRegistry>,
+    /// 类型别名（解析 rustType 短名）
+    type_aliases: Arc<TypeAliasRegistry>,
+    /// 已注册模型的列注解（按 resultType 查找）
+    models: Arc<ModelRegistry>,
+    /// Blob 列的结果表示
+    binary_encoding: BinaryEncoding,
 }
 
 impl Default for ResultSetHandler {
     fn default() -> Self {
-        Self {
-            type_handlers: TypeHandlerRegistry::with_defaults(),
-        }
+        Self::with_registry(Arc::new(TypeHandlerRegistry::with_defaults()))
+    }
+}
+
+impl std::fmt::Debug for ResultSetHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResultSetHandler")
+            .field("type_handlers", &self.type_handlers)
+            .field("binary_encoding", &self.binary_encoding)
+            .finish()
     }
 }
 
@@ -49,15 +85,77 @@ impl ResultSetHandler {
 
     /// 使用指定的类型处理器注册表
     pub fn with_handlers(type_handlers: TypeHandlerRegistry) -> Self {
-        Self { type_handlers }
+        Self::with_registry(Arc::new(type_handlers))
     }
 
-    /// 将一行的指定列解码为 `serde_json::Value`
+    /// 共享已有的类型处理器注册表（session 使用工厂的注册表）
+    pub fn with_registry(type_handlers: Arc<TypeHandlerRegistry>) -> Self {
+        Self {
+            type_handlers,
+            type_aliases: Arc::new(TypeAliasRegistry::new()),
+            models: Arc::new(ModelRegistry::new()),
+            binary_encoding: BinaryEncoding::default(),
+        }
+    }
+
+    /// 设置类型别名表（`rustType` 先经别名解析再查找处理器）
+    pub fn with_type_aliases(mut self, type_aliases: Arc<TypeAliasRegistry>) -> Self {
+        self.type_aliases = type_aliases;
+        self
+    }
+
+    /// 设置模型注册表（`resultType` 为已注册模型时按其列注解映射）
+    pub fn with_models(mut self, models: Arc<ModelRegistry>) -> Self {
+        self.models = models;
+        self
+    }
+
+    /// 设置 Blob 列的结果表示（`[settings] binary_encoding`）
+    pub fn with_binary_encoding(mut self, binary_encoding: BinaryEncoding) -> Self {
+        self.binary_encoding = binary_encoding;
+        self
+    }
+
+    /// Blob 列的结果表示
+    pub fn binary_encoding(&self) -> BinaryEncoding {
+        self.binary_encoding
+    }
+
+    /// ResultMap 列声明对应的类型处理器（未声明或无法识别时为 `None`）
+    ///
+    /// 优先级：`typeHandler` > `rustType` > `jdbcType`。`typeHandler` 未注册时报错。
+    pub fn column_handler(&self, column: &ResultColumn) -> Result<Option<&Arc<dyn TypeHandler>>> {
+        if let Some(name) = &column.type_handler {
+            return match self.type_handlers.get(name) {
+                Some(handler) => Ok(Some(handler)),
+                None => Err(MapperRuntimeError::TypeConversion(format!(
+                    "列 '{}' 声明的类型处理器 '{}' 未注册", column.column, name
+                ))),
+            };
+        }
+        if let Some(rust_type) = &column.rust_type {
+            let resolved = self.type_aliases.resolve(rust_type);
+            if let Some(handler) = self.type_handlers.for_rust_type(&resolved) {
+                return Ok(Some(handler));
+            }
+        }
+        Ok(column
+            .jdbc_type
+            .as_deref()
+            .and_then(|jdbc| self.type_handlers.for_jdbc_type(jdbc)))
+    }
+
+    /// 将一行的指定列解码为 `serde_json::Value`（Blob 按默认的 base64 表示）
+    pub fn column_to_value(row: &AnyRow, index: usize) -> Result<Value> {
+        Self::column_to_value_with(row, index, BinaryEncoding::default())
+    }
+
+    /// 将一行的指定列解码为 `serde_json::Value`，Blob 按 `binary_encoding` 表示
     ///
     /// 按**实际值的类型种类**（而非列声明类型）分派——通过 [`AnyRow::try_get_raw`]
     /// 读取原始值的 `type_info`，可正确处理计算列（如 `count(*)`，声明类型为 NULL
     /// 但实际值是整数）。
-    pub fn column_to_value(row: &AnyRow, index: usize, _rust_type: Option<&str>) -> Result<Value> {
+    pub fn column_to_value_with(row: &AnyRow, index: usize, binary_encoding: BinaryEncoding) -> Result<Value> {
         let columns = row.columns();
         if index >= columns.len() {
             return Err(MapperRuntimeError::TypeConversion(format!(
@@ -101,19 +199,22 @@ impl ResultSetHandler {
             }
             AnyTypeInfoKind::Blob => {
                 let v: Option<Vec<u8>> = row.try_get(index).map_err(decode_err("blob", index))?;
-                Ok(v
-                    .map(|b| Value::String(String::from_utf8_lossy(&b).into_owned()))
-                    .unwrap_or(Value::Null))
+                Ok(v.map(|b| binary_encoding.encode(&b)).unwrap_or(Value::Null))
             }
         }
     }
 
     /// 将整行映射为 `serde_json::Value::Object`（列名 → 值）
     pub fn row_to_value(row: &AnyRow) -> Result<Value> {
+        Self::row_to_value_with(row, BinaryEncoding::default())
+    }
+
+    /// 将整行映射为 `serde_json::Value::Object`，Blob 按 `binary_encoding` 表示
+    pub fn row_to_value_with(row: &AnyRow, binary_encoding: BinaryEncoding) -> Result<Value> {
         let mut obj = serde_json::Map::with_capacity(row.columns().len());
         for (idx, col) in row.columns().iter().enumerate() {
             let name = col.name().to_string();
-            let val = Self::column_to_value(row, idx, None)?;
+            let val = Self::column_to_value_with(row, idx, binary_encoding)?;
             obj.insert(name, val);
         }
         Ok(Value::Object(obj))
@@ -121,7 +222,12 @@ impl ResultSetHandler {
 
     /// 将一行反序列化为目标类型 `T`
     pub fn map_row<T: DeserializeOwned>(row: &AnyRow) -> Result<T> {
-        let value = Self::row_to_value(row)?;
+        Self::map_row_with(row, BinaryEncoding::default())
+    }
+
+    /// 将一行反序列化为目标类型 `T`，Blob 按 `binary_encoding` 表示
+    pub fn map_row_with<T: DeserializeOwned>(row: &AnyRow, binary_encoding: BinaryEncoding) -> Result<T> {
+        let value = Self::row_to_value_with(row, binary_encoding)?;
         // 直接 move value（成功路径零克隆）；错误信息不附带完整行数据以避免每行克隆
         serde_json::from_value::<T>(value).map_err(|e| {
             MapperRuntimeError::TypeConversion(format!("反序列化行失败: {}", e))
@@ -130,18 +236,120 @@ impl ResultSetHandler {
 
     /// 将多行反序列化为 `Vec<T>`
     pub fn map_rows<T: DeserializeOwned>(rows: Vec<AnyRow>) -> Result<Vec<T>> {
+        Self::map_rows_with(rows, BinaryEncoding::default())
+    }
+
+    /// 将多行反序列化为 `Vec<T>`，Blob 按 `binary_encoding` 表示
+    pub fn map_rows_with<T: DeserializeOwned>(rows: Vec<AnyRow>, binary_encoding: BinaryEncoding) -> Result<Vec<T>> {
         rows.iter()
-            .map(Self::map_row)
+            .map(|row| Self::map_row_with(row, binary_encoding))
             .collect()
     }
 
+    /// 无 ResultMap 时按 `resultType` 将一行反序列化为 `T`
+    ///
+    /// `resultType` 为标量类型（`long` / `string` / `java.lang.Integer` / `i64` 等）时取首列
+    /// （见 [`row_to_positional`](Self::row_to_positional)）；为已注册模型时按其列注解映射；
+    /// 否则同 [`map_row_with`](Self::map_row_with)。
+    pub fn map_row_as<T: DeserializeOwned>(&self, row: &AnyRow, result_type: Option<&str>) -> Result<T> {
+        let value = if self.scalar_result_type(result_type).is_some() {
+            self.row_to_positional(row, result_type)?
+        } else if let Some(columns) = self.model_columns(result_type) {
+            self.row_to_model_value(row, columns)?
+        } else {
+            return Self::map_row_with(row, self.binary_encoding);
+        };
+        serde_json::from_value::<T>(value).map_err(|e| {
+            MapperRuntimeError::TypeConversion(format!("反序列化行失败: {}", e))
+        })
+    }
+
+    /// 按列位置映射一行：单列为标量值，多列为数组（可反序列化为元组 `(i64, String)`）
+    ///
+    /// `resultType` 为标量类型时只取首列，并经该类型的处理器解码（如 `boolean` 将 0/1 解为布尔）。
+    pub fn row_to_positional(&self, row: &AnyRow, result_type: Option<&str>) -> Result<Value> {
+        if let Some(scalar) = self.scalar_result_type(result_type) {
+            let Some(column) = row.columns().first() else {
+                return Ok(Value::Null);
+            };
+            return match self.type_handlers.for_rust_type(&scalar) {
+                Some(handler) => handler.get_result(row, column.name()),
+                None => Self::column_to_value_with(row, 0, self.binary_encoding),
+            };
+        }
+        match row.columns().len() {
+            1 => Self::column_to_value_with(row, 0, self.binary_encoding),
+            n => (0..n)
+                .map(|idx| Self::column_to_value_with(row, idx, self.binary_encoding))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+        }
+    }
+
+    /// 按列位置将一行反序列化为标量或元组（见 [`row_to_positional`](Self::row_to_positional)）
+    pub fn map_row_positional<T: DeserializeOwned>(&self, row: &AnyRow, result_type: Option<&str>) -> Result<T> {
+        let value = self.row_to_positional(row, result_type)?;
+        serde_json::from_value::<T>(value).map_err(|e| {
+            MapperRuntimeError::TypeConversion(format!("反序列化标量失败: {}", e))
+        })
+    }
+
+    /// `resultType`（先经类型别名解析）为标量类型时返回其规范名（小写，如 `_long` / `java.lang.Long` → `long`）
+    fn scalar_result_type(&self, result_type: Option<&str>) -> Option<String> {
+        let resolved = self.type_aliases.resolve(result_type?);
+        let base = resolved.rsplit(['.', ':']).next()?.trim().trim_start_matches('_');
+        let name = base.to_ascii_lowercase();
+        matches!(
+            name.as_str(),
+            "long" | "int" | "integer" | "short" | "byte" | "double" | "float" | "boolean" | "string"
+                | "decimal" | "bigdecimal" | "bool" | "str" | "i8" | "i16" | "i32" | "i64" | "u8"
+                | "u16" | "u32" | "u64" | "usize" | "f32" | "f64"
+        )
+        .then_some(name)
+    }
+
+    /// 无 ResultMap 时按 `resultType` 将多行反序列化为 `Vec<T>`（见 [`map_row_as`](Self::map_row_as)）
+    pub fn map_rows_as<T: DeserializeOwned>(&self, rows: Vec<AnyRow>, result_type: Option<&str>) -> Result<Vec<T>> {
+        rows.iter().map(|row| self.map_row_as(row, result_type)).collect()
+    }
+
+    /// `resultType`（先经类型别名解析）对应的模型列注解
+    fn model_columns(&self, result_type: Option<&str>) -> Option<&[ResultColumn]> {
+        if self.models.is_empty() {
+            return None;
+        }
+        let resolved = self.type_aliases.resolve(result_type?);
+        self.models.get(&resolved)
+    }
+
+    /// 整行 → 对象：模型声明的列以字段名为键并按处理器解码，其余列按值分派
+    fn row_to_model_value(&self, row: &AnyRow, columns: &[ResultColumn]) -> Result<Value> {
+        let mut obj = serde_json::Map::with_capacity(row.columns().len());
+        for (idx, col) in row.columns().iter().enumerate() {
+            let name = col.name();
+            match columns.iter().find(|c| c.column == name) {
+                Some(column) => {
+                    let value = match self.column_handler(column)? {
+                        Some(handler) => handler.get_result(row, name)?,
+                        None => Self::column_to_value_with(row, idx, self.binary_encoding)?,
+                    };
+                    obj.insert(column.property.clone(), value);
+                }
+                None => {
+                    obj.insert(name.to_string(), Self::column_to_value_with(row, idx, self.binary_encoding)?);
+                }
+            }
+        }
+        Ok(Value::Object(obj))
+    }
+
     /// 按列名 → 值的方式映射（用于列名与字段名不一致的场景）
     ///
     /// 返回 `HashMap<列名, Value>`，调用方可自行构造 Value::Object 再反序列化。
     pub fn row_to_map(row: &AnyRow) -> Result<HashMap<String, Value>> {
         let mut map = HashMap::with_capacity(row.columns().len());
         for (idx, col) in row.columns().iter().enumerate() {
-            let val = Self::column_to_value(row, idx, None)?;
+            let val = Self::column_to_value(row, idx)?;
             map.insert(col.name().to_string(), val);
         }
         Ok(map)
@@ -154,31 +362,37 @@ impl ResultSetHandler {
 
     // ─── ResultMap 嵌套映射（P8）──────────────────────────────────
 
-    /// 按列名读取单格值（列不存在返回 Null）
+    /// 按 ResultMap 列声明读取单格值（列不存在返回 Null）
     ///
-    /// 通过预构建的 `col_index`（列名→列序号）做 O(1) 查找，避免每格线性扫描。
-    fn column_value_by_name(
+    /// 通过预构建的 `col_index`（列名→列序号）做 O(1) 查找，避免每格线性扫描；
+    /// 声明了类型的列经对应 [`TypeHandler`] 解码。
+    fn column_value<K: ColumnKey>(
+        &self,
         row: &AnyRow,
-        name: &str,
-        col_index: &HashMap<&str, usize>,
+        column: &ResultColumn,
+        col_index: &HashMap<K, usize>,
     ) -> Result<Value> {
-        match col_index.get(name) {
-            Some(&idx) => Self::column_to_value(row, idx, None),
-            None => Ok(Value::Null),
+        let Some(&idx) = col_index.get(column.column.as_str()) else {
+            return Ok(Value::Null);
+        };
+        match self.column_handler(column)? {
+            Some(handler) => handler.get_result(row, &column.column),
+            None => Self::column_to_value_with(row, idx, self.binary_encoding),
         }
     }
 
     /// 构建嵌套对象（association / collection 子项共用）。
     /// 若所有结果列为 null，返回 `Value::Null`（表示无关联对象）。
-    fn build_nested_object(
+    fn build_nested_object<K: ColumnKey>(
+        &self,
         row: &AnyRow,
         mapping: &NestedMapping,
-        col_index: &HashMap<&str, usize>,
+        col_index: &HashMap<K, usize>,
     ) -> Result<Value> {
         let mut obj = serde_json::Map::with_capacity(mapping.result_columns.len());
         let mut any_non_null = false;
         for col in &mapping.result_columns {
-            let v = Self::column_value_by_name(row, &col.column, col_index)?;
+            let v = self.column_value(row, col, col_index)?;
             if !v.is_null() {
                 any_non_null = true;
             }
@@ -195,10 +409,11 @@ impl ResultSetHandler {
     ///
     /// `id_values` 为调用方已解码的 `<id>` 列值（按 result_columns 中 is_id 出现顺序），
     /// 传入后避免重复解码；为空或耗尽时回退为按列名解码（供单行路径复用）。
-    fn build_parent_object(
+    fn build_parent_object<K: ColumnKey>(
+        &self,
         row: &AnyRow,
         result_map: &ResultMap,
-        col_index: &HashMap<&str, usize>,
+        col_index: &HashMap<K, usize>,
         id_values: Vec<Value>,
     ) -> Result<Value> {
         let mut obj = serde_json::Map::with_capacity(
@@ -213,23 +428,23 @@ impl ResultSetHandler {
             let v = if col.is_id {
                 match id_iter.next() {
                     Some(v) => v,
-                    None => Self::column_value_by_name(row, &col.column, col_index)?,
+                    None => self.column_value(row, col, col_index)?,
                 }
             } else {
-                Self::column_value_by_name(row, &col.column, col_index)?
+                self.column_value(row, col, col_index)?
             };
             obj.insert(col.property.clone(), v);
         }
 
         // association（一对一）：列为空则 Null
         for assoc in &result_map.associations {
-            let nested = Self::build_nested_object(row, assoc, col_index)?;
+            let nested = self.build_nested_object(row, assoc, col_index)?;
             obj.insert(assoc.property.clone(), nested);
         }
 
         // collection（一对多）：首行放入数组，后续行追加
         for coll in &result_map.collections {
-            let child = Self::build_nested_object(row, coll, col_index)?;
+            let child = self.build_nested_object(row, coll, col_index)?;
             if child.is_null() {
                 obj.insert(coll.property.clone(), Value::Array(Vec::new()));
             } else {
@@ -255,6 +470,7 @@ impl ResultSetHandler {
     /// - association：从扁平 join 行的列构建嵌套对象（列为空 → null）
     /// - collection：按父 id 分组，每行贡献一个子项
     pub fn map_rows_with_result_map<T: DeserializeOwned>(
+        &self,
         rows: Vec<AnyRow>,
         result_map: &ResultMap,
     ) -> Result<Vec<T>> {
@@ -266,11 +482,10 @@ impl ResultSetHandler {
         // 索引的 &str 键借用 rows[0] 的列名，rows 在整个函数期内存活，借用有效。
         let col_index = Self::col_index_of(&rows[0]);
 
-        let id_cols: Vec<&str> = result_map
+        let id_cols: Vec<&ResultColumn> = result_map
             .result_columns
             .iter()
             .filter(|c| c.is_id)
-            .map(|c| c.column.as_str())
             .collect();
 
         // 上界 = 行数（每行至多产生一个父对象）
@@ -278,59 +493,93 @@ impl ResultSetHandler {
         let mut key_index: HashMap<String, usize> = HashMap::with_capacity(rows.len());
 
         for (row_idx, row) in rows.iter().enumerate() {
-            // id 列每行只解码一次：分组键与父对象构建共用，避免双重解码
-            let id_values: Vec<Value> = id_cols
-                .iter()
-                .map(|c| Self::column_value_by_name(row, c, &col_index))
-                .collect::<Result<_>>()?;
-            let key = if id_cols.is_empty() {
-                format!("__row_{}", row_idx)
-            } else if id_values.len() == 1 {
-                id_values[0].to_string() // 常见单 id 列：免 Vec/join
-            } else {
-                // 单元分隔符拼接，避免值内逗号冲突
-                id_values
-                    .iter()
-                    .map(|v| v.to_string())
-                    .collect::<Vec<_>>()
-                    .join("\u{1F}")
-            };
-
+            let (key, id_values) = self.group_key(row, &id_cols, &col_index, row_idx)?;
             if let Some(&idx) = key_index.get(&key) {
                 // 已存在父：仅追加 collection 子项
-                for coll in &result_map.collections {
-                    let child = Self::build_nested_object(row, coll, &col_index)?;
-                    if child.is_null() {
-                        continue;
-                    }
-                    if let Some(arr) = parents[idx]
-                        .as_object_mut()
-                        .and_then(|o| o.get_mut(&coll.property))
-                        .and_then(|v| v.as_array_mut())
-                    {
-                        arr.push(child);
-                    }
-                }
+                self.append_collection_children(&mut parents[idx], row, result_map, &col_index)?;
             } else {
                 key_index.insert(key, parents.len());
-                parents.push(Self::build_parent_object(row, result_map, &col_index, id_values)?);
+                parents.push(self.build_parent_object(row, result_map, &col_index, id_values)?);
             }
         }
 
-        parents
-            .into_iter()
-            .map(|v| {
-                serde_json::from_value::<T>(v).map_err(|e| {
-                    MapperRuntimeError::TypeConversion(format!("ResultMap 反序列化失败: {}", e))
-                })
-            })
-            .collect()
+        parents.into_iter().map(from_parent_value).collect()
+    }
+
+    /// 分组键与已解码的 `<id>` 列值
+    ///
+    /// id 列每行只解码一次：分组键与父对象构建共用，避免双重解码；无 `<id>` 时每行自成一组。
+    fn group_key<K: ColumnKey>(
+        &self,
+        row: &AnyRow,
+        id_cols: &[&ResultColumn],
+        col_index: &HashMap<K, usize>,
+        row_idx: usize,
+    ) -> Result<(String, Vec<Value>)> {
+        let id_values: Vec<Value> = id_cols
+            .iter()
+            .map(|c| self.column_value(row, c, col_index))
+            .collect::<Result<_>>()?;
+        let key = if id_cols.is_empty() {
+            format!("__row_{}", row_idx)
+        } else if id_values.len() == 1 {
+            id_values[0].to_string() // 常见单 id 列：免 Vec/join
+        } else {
+            // 单元分隔符拼接，避免值内逗号冲突
+            id_values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join("\u{1F}")
+        };
+        Ok((key, id_values))
+    }
+
+    /// 同一父对象的后续行：仅向各 collection 追加子项（子项列全空则跳过）
+    fn append_collection_children<K: ColumnKey>(
+        &self,
+        parent: &mut Value,
+        row: &AnyRow,
+        result_map: &ResultMap,
+        col_index: &HashMap<K, usize>,
+    ) -> Result<()> {
+        for coll in &result_map.collections {
+            let child = self.build_nested_object(row, coll, col_index)?;
+            if child.is_null() {
+                continue;
+            }
+            if let Some(arr) = parent
+                .as_object_mut()
+                .and_then(|o| o.get_mut(&coll.property))
+                .and_then(|v| v.as_array_mut())
+            {
+                arr.push(child);
+            }
+        }
+        Ok(())
+    }
+
+    /// 创建流式分组器（结果须按父对象 `<id>` 有序，见 [`ResultMapGrouper`]）
+    pub fn grouper<'a, T: DeserializeOwned>(&'a self, result_map: &'a ResultMap) -> ResultMapGrouper<'a, T> {
+        ResultMapGrouper {
+            handler: self,
+            result_map,
+            id_cols: result_map.result_columns.iter().filter(|c| c.is_id).collect(),
+            col_index: None,
+            current: None,
+            current_ids: Vec::new(),
+            direction: None,
+            finished_keys: HashSet::new(),
+            rows: 0,
+            _target: PhantomData,
+        }
     }
 
     /// 使用 ResultMap 映射单行（`select_one` 路径；多于一行报错）
     ///
     /// 单行无需分组：直接构建父对象（跳过分组键/索引搭建成本）。
     pub fn map_row_with_result_map<T: DeserializeOwned>(
+        &self,
         rows: Vec<AnyRow>,
         result_map: &ResultMap,
     ) -> Result<Option<T>> {
@@ -339,17 +588,188 @@ impl ResultSetHandler {
             1 => {
                 let row = &rows[0];
                 let col_index = Self::col_index_of(row);
-                let value = Self::build_parent_object(row, result_map, &col_index, Vec::new())?;
-                let t = serde_json::from_value::<T>(value).map_err(|e| {
-                    MapperRuntimeError::TypeConversion(format!("ResultMap 反序列化失败: {}", e))
-                })?;
-                Ok(Some(t))
+                let value = self.build_parent_object(row, result_map, &col_index, Vec::new())?;
+                from_parent_value(value).map(Some)
             }
             n => Err(MapperRuntimeError::TooManyRows { actual: n }),
         }
     }
 }
 
+/// [`select_map`](crate::SqlSession::select_map) 遇到重复键时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DuplicateKeyPolicy {
+    /// 返回 [`MapperRuntimeError::DuplicateMapKey`]（默认）
+    #[default]
+    Error,
+    /// 保留先出现的行
+    FirstWins,
+    /// 后出现的行覆盖先出现的行（MyBatis `@MapKey` 的行为）
+    LastWins,
+}
+
+/// 按属性为已映射的行建立 `HashMap`（MyBatis `@MapKey`）
+///
+/// `key_property` 为映射后的属性名（ResultMap 的 `property`，无 ResultMap 时为列名），
+/// 支持以 `.` 访问嵌套对象（如 `department.id`）；缺失的属性按 `null` 处理。
+pub fn key_rows_by<K, V>(
+    rows: Vec<Value>,
+    key_property: &str,
+    policy: DuplicateKeyPolicy,
+) -> Result<HashMap<K, V>>
+where
+    K: DeserializeOwned + Eq + Hash,
+    V: DeserializeOwned,
+{
+    let mut map = HashMap::with_capacity(rows.len());
+    for row in rows {
+        let key_value = key_property
+            .split('.')
+            .try_fold(&row, |v, segment| v.get(segment))
+            .cloned()
+            .unwrap_or(Value::Null);
+        let key: K = serde_json::from_value(key_value.clone()).map_err(|e| {
+            MapperRuntimeError::TypeConversion(format!("map key 属性 '{}' 转换失败: {}", key_property, e))
+        })?;
+        match map.entry(key) {
+            Entry::Vacant(slot) => {
+                slot.insert(from_parent_value(row)?);
+            }
+            Entry::Occupied(mut slot) => match policy {
+                DuplicateKeyPolicy::Error => {
+                    return Err(MapperRuntimeError::DuplicateMapKey {
+                        property: key_property.to_string(),
+                        key: key_value.to_string(),
+                    });
+                }
+                DuplicateKeyPolicy::FirstWins => {}
+                DuplicateKeyPolicy::LastWins => {
+                    slot.insert(from_parent_value(row)?);
+                }
+            },
+        }
+    }
+    Ok(map)
+}
+
+/// 流式分组遇到乱序 `<id>` 时的处理方式（[`SqlSession::select_stream_grouped`](crate::SqlSession::select_stream_grouped)）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfOrderPolicy {
+    /// 流式分组，检测到乱序 id 时返回 [`MapperRuntimeError::UnorderedGroup`]（默认）
+    #[default]
+    Error,
+    /// 不要求有序：缓冲全部行后整体分组再逐个产出（内存随结果集增长，等价于 `select_list`）
+    Buffer,
+}
+
+/// 流式 ResultMap 分组器：按父对象 `<id>` 的变化切分，逐个产出完整的父对象
+///
+/// 要求结果集按父 id 排序（如 `ORDER BY u.id`，多个 `<id>` 列须同向排序）。每推入一行，若其 id 与当前分组不同，
+/// 则返回已完成的上一个父对象；id 不符合排序时返回 [`MapperRuntimeError::UnorderedGroup`]。
+///
+/// 乱序检测：数值 id 与上一个分组的 id 逐列比较，排序方向由首次切换分组确定，之后任何逆向（或相等）的切换即报错，
+/// 内存与结果集大小无关；非数值 id（如字符串，其顺序取决于数据库排序规则，无法在内存中比较）
+/// 退回记录已产出分组的 id 键，内存随父对象数增长。
+pub struct ResultMapGrouper<'a, T> {
+    handler: &'a ResultSetHandler,
+    result_map: &'a ResultMap,
+    id_cols: Vec<&'a ResultColumn>,
+    /// 列名→列序号（首行建立；流中各行列序一致）
+    col_index: Option<HashMap<String, usize>>,
+    /// 当前分组：(id 键, 父对象)
+    current: Option<(String, Value)>,
+    /// 当前分组的 `<id>` 值（与下一个分组比较排序方向）
+    current_ids: Vec<Value>,
+    /// 按 id 数值确定的排序方向（首次切换分组时确定）
+    direction: Option<Ordering>,
+    /// 非数值 id 时已产出分组的 id 键
+    finished_keys: HashSet<String>,
+    rows: usize,
+    _target: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> ResultMapGrouper<'_, T> {
+    /// 推入一行；父 id 变化时返回上一个已完成的父对象
+    pub fn push(&mut self, row: &AnyRow) -> Result<Option<T>> {
+        let col_index = self.col_index.get_or_insert_with(|| {
+            row.columns()
+                .iter()
+                .enumerate()
+                .map(|(i, c)| (c.name().to_string(), i))
+                .collect()
+        });
+        let row_idx = self.rows;
+        self.rows += 1;
+        let (key, id_values) = self.handler.group_key(row, &self.id_cols, col_index, row_idx)?;
+
+        if let Some((current_key, parent)) = self.current.as_mut()
+            && *current_key == key
+        {
+            self.handler
+                .append_collection_children(parent, row, self.result_map, col_index)?;
+            return Ok(None);
+        }
+        // 无 <id> 时每行自成一组，无需检测；数值 id 比较排序方向，否则查已产出的键
+        if !self.id_cols.is_empty() {
+            if let Some((done_key, _)) = &self.current {
+                let out_of_order = match compare_ids(&self.current_ids, &id_values) {
+                    Some(Ordering::Equal) => true,
+                    Some(step) => *self.direction.get_or_insert(step) != step,
+                    None => {
+                        self.finished_keys.insert(done_key.clone());
+                        self.finished_keys.contains(&key)
+                    }
+                };
+                if out_of_order {
+                    return Err(MapperRuntimeError::UnorderedGroup {
+                        result_map: self.result_map.id.clone(),
+                        key,
+                    });
+                }
+            }
+            self.current_ids = id_values.clone();
+        }
+        let parent = self
+            .handler
+            .build_parent_object(row, self.result_map, col_index, id_values)?;
+        match self.current.replace((key, parent)) {
+            Some((_, done)) => from_parent_value(done).map(Some),
+            None => Ok(None),
+        }
+    }
+
+
+    /// 结束流：返回最后一个父对象（无行时为 `None`）
+    pub fn finish(self) -> Result<Option<T>> {
+        self.current.map(|(_, parent)| from_parent_value(parent)).transpose()
+    }
+}
+
+/// 按数值逐列比较两组 `<id>` 值；含非数值（字符串、null 等）时返回 `None`
+fn compare_ids(a: &[Value], b: &[Value]) -> Option<Ordering> {
+    for (x, y) in a.iter().zip(b) {
+        let (x, y) = (x.as_number()?, y.as_number()?);
+        let ord = match (x.as_i64(), y.as_i64()) {
+            (Some(x), Some(y)) => x.cmp(&y),
+            _ => x.as_f64()?.partial_cmp(&y.as_f64()?)?,
+        };
+        if ord != Ordering::Equal {
+            return Some(ord);
+        }
+    }
+    Some(Ordering::Equal)
+}
+
+/// 列名索引的键：借用首行列名（`&str`）或自有（`String`，流式分组器跨行持有）
+trait ColumnKey: Borrow<str> + Hash + Eq {}
+
+impl<K: Borrow<str> + Hash + Eq> ColumnKey for K {}
+
+fn from_parent_value<T: DeserializeOwned>(value: Value) -> Result<T> {
+    serde_json::from_value::<T>(value)
+        .map_err(|e| MapperRuntimeError::TypeConversion(format!("ResultMap 反序列化失败: {}", e)))
+}
+
 fn decode_err(typ: &str, idx: usize) -> impl Fn(sqlx::Error) -> MapperRuntimeError + '_ {
     move |e: sqlx::Error| {
         MapperRuntimeError::TypeConversion(format!("解码列 {} 为 {} 失败: {}", idx, typ, e))
@@ -489,6 +909,7 @@ mod tests {
         let bound = BoundSql {
             sql: "SELECT id, name, score, active FROM users WHERE id = ?".to_string(),
             parameters: vec![Value::Number(2.into())],
+            ..Default::default()
         };
 
         let args = ParameterHandler::bind_arguments(&bound).unwrap();
@@ -501,4 +922,103 @@ mod tests {
         assert_eq!(user.id, 2);
         assert_eq!(user.name, "李四");
     }
+    #[tokio::test]
+    async fn test_grouper_detects_unordered_ids() {
+        let pool = setup_pool().await;
+        let result_map = ResultMap {
+            id: "userMap".into(),
+            result_columns: vec![
+                ResultColumn { property: "id".into(), column: "id".into(), is_id: true, ..Default::default() },
+                ResultColumn { property: "name".into(), column: "name".into(), ..Default::default() },
+            ],
+            ..Default::default()
+        };
+        let handler = ResultSetHandler::new();
+        sqlx::query("INSERT INTO users VALUES (3, '王五', 70.0, 1)").execute(&pool).await.unwrap();
+
+        // 有序：id 变化时产出上一个父对象，finish 产出最后一个
+        let rows = sqlx::query("SELECT id, name FROM users ORDER BY id").fetch_all(&pool).await.unwrap();
+        let mut grouper = handler.grouper::<Value>(&result_map);
+        assert_eq!(grouper.push(&rows[0]).unwrap(), None);
+        assert_eq!(grouper.push(&rows[0]).unwrap(), None);
+        assert_eq!(grouper.push(&rows[1]).unwrap().unwrap()["id"], 1);
+        assert_eq!(grouper.finish().unwrap().unwrap()["id"], 2);
+
+        // 刚结束的 id 紧接着再次出现
+        let mut grouper = handler.grouper::<Value>(&result_map);
+        for row in [&rows[0], &rows[1]] {
+            grouper.push(row).unwrap();
+        }
+        let err = grouper.push(&rows[0]).unwrap_err();
+        assert!(matches!(err, MapperRuntimeError::UnorderedGroup { ref result_map, .. } if result_map == "userMap"), "{}", err);
+
+        // 隔了其他分组再出现的 id：与已确定的升序方向相反
+        let mut grouper = handler.grouper::<Value>(&result_map);
+        assert_eq!(grouper.push(&rows[0]).unwrap(), None);
+        assert_eq!(grouper.push(&rows[1]).unwrap().unwrap()["id"], 1);
+        assert_eq!(grouper.push(&rows[2]).unwrap().unwrap()["id"], 2);
+        let err = grouper.push(&rows[0]).unwrap_err();
+        assert!(matches!(err, MapperRuntimeError::UnorderedGroup { ref key, .. } if key == "1"), "{}", err);
+
+        // 降序同样可流式分组，方向由首次切换确定；之后的逆向切换（未重复的 id）也视为乱序
+        let mut grouper = handler.grouper::<Value>(&result_map);
+        for row in [&rows[2], &rows[1]] {
+            grouper.push(row).unwrap();
+        }
+        assert_eq!(grouper.push(&rows[0]).unwrap().unwrap()["id"], 2);
+        assert!(grouper.push(&rows[1]).is_err());
+
+        // 字符串 id（排序规则未知）按已产出的键检测
+        let name_map = ResultMap {
+            id: "nameMap".into(),
+            result_columns: vec![ResultColumn { property: "name".into(), column: "name".into(), is_id: true, ..Default::default() }],
+            ..Default::default()
+        };
+        let mut grouper = handler.grouper::<Value>(&name_map);
+        for row in [&rows[0], &rows[1], &rows[2]] {
+            grouper.push(row).unwrap();
+        }
+        assert!(matches!(grouper.push(&rows[0]), Err(MapperRuntimeError::UnorderedGroup { .. })));
+    }
+
+    #[test]
+    fn test_key_rows_by_policies() {
+        let rows = || {
+            vec![
+                serde_json::json!({"id": 1, "dept": {"code": "a"}, "name": "张三"}),
+                serde_json::json!({"id": 2, "dept": {"code": "a"}, "name": "李四"}),
+            ]
+        };
+        let by_id: HashMap<i64, Value> = key_rows_by(rows(), "id", DuplicateKeyPolicy::Error).unwrap();
+        assert_eq!(by_id[&2]["name"], "李四");
+
+        let err = key_rows_by::<String, Value>(rows(), "dept.code", DuplicateKeyPolicy::Error).unwrap_err();
+        assert!(matches!(err, MapperRuntimeError::DuplicateMapKey { ref key, .. } if key == "\"a\""), "{}", err);
+        let first: HashMap<String, Value> = key_rows_by(rows(), "dept.code", DuplicateKeyPolicy::FirstWins).unwrap();
+        assert_eq!(first["a"]["name"], "张三");
+        let last: HashMap<String, Value> = key_rows_by(rows(), "dept.code", DuplicateKeyPolicy::LastWins).unwrap();
+        assert_eq!(last["a"]["name"], "李四");
+
+        // 缺失的键属性按 null 处理，无法转换为 i64
+        assert!(key_rows_by::<i64, Value>(rows(), "missing", DuplicateKeyPolicy::Error).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_positional_and_scalar_result_type() {
+        let pool = setup_pool().await;
+        let handler = ResultSetHandler::new();
+        let row = sqlx::query("SELECT COUNT(*) FROM users").fetch_one(&pool).await.unwrap();
+        assert_eq!(handler.map_row_positional::<i64>(&row, None).unwrap(), 2);
+
+        let row = sqlx::query("SELECT id, name FROM users WHERE id = 1").fetch_one(&pool).await.unwrap();
+        let pair: (i64, String) = handler.map_row_positional(&row, None).unwrap();
+        assert_eq!(pair, (1, "张三".to_string()));
+
+        // 标量 resultType：取首列并经处理器解码
+        let row = sqlx::query("SELECT active, name FROM users WHERE id = 1").fetch_one(&pool).await.unwrap();
+        for result_type in ["boolean", "_boolean", "java.lang.Boolean", "bool"] {
+            assert!(handler.map_row_as::<bool>(&row, Some(result_type)).unwrap(), "{}", result_type);
+        }
+        assert!(handler.map_row_as::<bool>(&row, Some("User")).is_err());
+    }
 }
@@ -150,11 +150,10 @@ impl Drop for MapperWatcher {
 fn reload_mapper(registry: &MapperRegistry, path: &Path) {
     match registry.register_from_file(path) {
         Ok(namespace) => {
-            // 热重载成功（生产环境可接入 tracing/log）
-            log_info(&format!("热重载成功: {} ({})", namespace, path.display()));
+            crate::telemetry::info(&format!("热重载成功: {} ({})", namespace, path.display()));
         }
         Err(e) => {
-            log_warn(&format!("热重载失败 {}: {}", path.display(), e));
+            crate::telemetry::warn(&format!("热重载失败 {}: {}", path.display(), e));
         }
     }
 }
@@ -163,14 +162,6 @@ fn is_xml(path: &Path) -> bool {
     path.extension().and_then(|e| e.to_str()).map(|s| s.eq_ignore_ascii_case("xml")).unwrap_or(false)
 }
 
-/// 轻量日志：当前用 eprintln（避免引入 log 依赖；P10 可替换为 tracing）
-fn log_info(msg: &str) {
-    eprintln!("[hirust-mapper] {}", msg);
-}
-fn log_warn(msg: &str) {
-    eprintln!("[hirust-mapper][WARN] {}", msg);
-}
-
 /// 从 mapper glob 模式列表推导出需监视的目录
 ///
 /// 规则：取每个模式中第一个通配符之前的静态前缀作为路径，相对 `base_dir` 解析；
@@ -6,32 +6,60 @@
 
 pub mod bound_sql;
 pub mod config;
+pub mod dialect;
 pub mod environment;
 pub mod error;
 pub mod event;
 pub mod executor;
+pub mod explain;
 pub mod handler;
 pub mod hot_reload;
+pub mod metrics;
+pub mod page;
+pub mod redact;
 pub mod registry;
+pub mod retry;
 pub mod session;
+pub mod sql_comment;
 pub mod sql_log;
 pub mod session_factory;
+pub mod telemetry;
+pub mod transaction;
 pub mod type_handler;
 
 pub use bound_sql::BoundSql;
 pub use config::*;
+pub use dialect::{BeginStatements, Dialect};
 pub use environment::*;
 pub use error::*;
-pub use event::{Event, EventBus, Listener, Subscriber};
-pub use event::lifecycle::{AfterSqlEvent, BeforeSqlEvent, SqlKind, SqlOutcome};
-pub use executor::SimpleExecutor;
-pub use handler::{ParameterHandler, ResultSetHandler};
+pub use event::{
+    AsyncDispatchStats, AsyncListener, AsyncListenerOptions, Event, EventBus, Listener, ListenerOptions,
+    OverflowPolicy, Subscriber, SubscriptionHandle,
+};
+pub use event::lifecycle::{
+    AfterSqlEvent, BeforeSqlEvent, PoolAcquireEvent, RetryEvent, RetryScope, RollbackReason, SlowQueryEvent,
+    SqlKind, SqlOutcome, StatementContext, TransactionBeginEvent, TransactionCommitEvent,
+    TransactionOutcomeUnknownEvent, TransactionRollbackEvent,
+};
+pub use executor::{SimpleExecutor, StatementTimeout};
+pub use explain::{QueryPlan, SlowQueryExplainer};
+pub use handler::{
+    DuplicateKeyPolicy, OutOfOrderPolicy, ParameterHandler, ResultMapGrouper, ResultSetHandler,
+};
 pub use hot_reload::MapperWatcher;
+pub use metrics::{HistogramSnapshot, MetricsRegistry, MetricsSnapshot, StatementMetrics};
+pub use page::{Page, PageRequest};
+pub use redact::{RedactionPolicy, Redactor};
 pub use registry::*;
+pub use retry::{RetryErrorClass, RetryPolicy};
 pub use session::{MapperProxy, SqlSession};
-pub use sql_log::SqlLogConfig;
-pub use session_factory::SqlSessionFactory;
+pub use sql_comment::{SqlCommentConfig, SqlCommenter, TraceContext, TraceContextProvider};
+pub use sql_log::{SqlLogConfig, SqlLogFormat, SqlLogLevel};
+pub use session_factory::{SqlSessionFactory, SqlSessionFactoryBuilder};
+pub use transaction::{
+    IsolationLevel, Propagation, ScopedSession, TransactionOptions, TxSession,
+};
 pub use type_handler::{
-    BoolHandler, F64Handler, I32Handler, I64Handler, StringHandler, TypeHandler,
-    TypeHandlerRegistry,
+    BinaryEncoding, BoolHandler, BytesHandler, EnumOrdinalHandler, EnumStringHandler, F64Handler,
+    I32Handler, I64Handler, MapperEnum, StringHandler, TypeHandler, TypeHandlerRegistry, U64Handler,
 };
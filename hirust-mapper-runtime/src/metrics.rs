@@ -0,0 +1,359 @@
+//! 语句级指标
+//!
+//! [`MetricsRegistry`] 是 [`EventBus`] 上的 [`Subscriber`]：按「命名空间 + 语句 id」聚合
+//! [`AfterSqlEvent`]（调用次数、错误次数、取回 / 影响行数、耗时直方图）与
+//! [`PoolAcquireEvent`]（连接池等待时间直方图）。
+//!
+//! 开启 `[settings] metrics = true` 后工厂自动注册，经
+//! [`SqlSessionFactory::metrics_snapshot`](crate::SqlSessionFactory::metrics_snapshot) 取快照，
+//! [`MetricsSnapshot::to_prometheus`] 渲染为 Prometheus 文本格式：
+//!
+//! ```text
+//! # HELP hirust_mapper_statement_calls_total SQL 语句执行次数
+//! # TYPE hirust_mapper_statement_calls_total counter
+//! hirust_mapper_statement_calls_total{namespace="app.UserDao",statement="findById"} 42
+//! ```
+//!
+//! 未命名的 SQL（经 `SimpleExecutor` 直接执行）聚合在空命名空间与空语句 id 下。
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::event::lifecycle::{AfterSqlEvent, PoolAcquireEvent, SqlOutcome, StatementContext};
+use crate::event::{EventBus, Subscriber};
+
+/// 直方图桶上界（秒），另有隐含的 `+Inf` 桶
+pub const DEFAULT_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// 单个直方图（各桶为非累计计数，末位为超出全部上界的观测）
+#[derive(Debug, Clone)]
+struct Histogram {
+    counts: Vec<u64>,
+    sum: Duration,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self { counts: vec![0; DEFAULT_BUCKETS.len() + 1], sum: Duration::ZERO }
+    }
+
+    fn observe(&mut self, value: Duration) {
+        let secs = value.as_secs_f64();
+        let index = DEFAULT_BUCKETS.iter().position(|&bound| secs <= bound).unwrap_or(DEFAULT_BUCKETS.len());
+        self.counts[index] += 1;
+        self.sum += value;
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        let cumulative = self
+            .counts
+            .iter()
+            .scan(0, |acc, n| {
+                *acc += n;
+                Some(*acc)
+            })
+            .collect();
+        HistogramSnapshot { bounds: DEFAULT_BUCKETS, cumulative, sum: self.sum }
+    }
+}
+
+/// 单条语句的累计值
+#[derive(Debug, Clone)]
+struct StatementStats {
+    calls: u64,
+    errors: u64,
+    rows_fetched: u64,
+    rows_affected: u64,
+    latency: Histogram,
+    pool_wait: Histogram,
+}
+
+impl StatementStats {
+    fn new() -> Self {
+        Self {
+            calls: 0,
+            errors: 0,
+            rows_fetched: 0,
+            rows_affected: 0,
+            latency: Histogram::new(),
+            pool_wait: Histogram::new(),
+        }
+    }
+}
+
+type StatementKey = (String, String);
+
+/// Prometheus 计数器描述：名称、HELP 文本与取值函数
+type CounterFamily = (&'static str, &'static str, fn(&StatementMetrics) -> u64);
+
+/// Prometheus 直方图描述：名称、HELP 文本与取值函数
+type HistogramFamily = (&'static str, &'static str, fn(&StatementMetrics) -> &HistogramSnapshot);
+
+/// 语句级指标注册表（线程安全，克隆共享同一份数据）
+///
+/// 作为 [`Subscriber`] 注册到事件总线后开始采集：
+///
+/// ```ignore
+/// let metrics = MetricsRegistry::new();
+/// factory.event_bus().add_subscriber(&metrics);
+/// // ...
+/// let body = metrics.snapshot().to_prometheus();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MetricsRegistry {
+    stats: Arc<Mutex<HashMap<StatementKey, StatementStats>>>,
+}
+
+impl MetricsRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 记录一次执行
+    pub fn record_execution(&self, statement: &StatementContext, elapsed: Duration, outcome: &SqlOutcome) {
+        self.with_stats(statement, |stats| {
+            stats.calls += 1;
+            stats.latency.observe(elapsed);
+            match outcome {
+                SqlOutcome::Fetched(n) => stats.rows_fetched += *n as u64,
+                SqlOutcome::Affected(n) => stats.rows_affected += n,
+                SqlOutcome::Failed(_) => stats.errors += 1,
+            }
+        });
+    }
+
+    /// 记录一次连接池等待
+    pub fn record_pool_wait(&self, statement: &StatementContext, wait: Duration) {
+        self.with_stats(statement, |stats| stats.pool_wait.observe(wait));
+    }
+
+    /// 当前快照（按命名空间、语句 id 排序）
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        let stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let mut statements: Vec<StatementMetrics> = stats
+            .iter()
+            .map(|((namespace, statement_id), s)| StatementMetrics {
+                namespace: namespace.clone(),
+                statement_id: statement_id.clone(),
+                calls: s.calls,
+                errors: s.errors,
+                rows_fetched: s.rows_fetched,
+                rows_affected: s.rows_affected,
+                latency: s.latency.snapshot(),
+                pool_wait: s.pool_wait.snapshot(),
+            })
+            .collect();
+        statements.sort_by(|a, b| (&a.namespace, &a.statement_id).cmp(&(&b.namespace, &b.statement_id)));
+        MetricsSnapshot { statements }
+    }
+
+    /// 清空全部累计值
+    pub fn reset(&self) {
+        self.stats.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+
+    fn with_stats(&self, statement: &StatementContext, f: impl FnOnce(&mut StatementStats)) {
+        let mut stats = self.stats.lock().unwrap_or_else(|e| e.into_inner());
+        let key = (statement.namespace.clone(), statement.statement_id.clone());
+        f(stats.entry(key).or_insert_with(StatementStats::new));
+    }
+}
+
+impl Subscriber for MetricsRegistry {
+    fn subscribe(&self, bus: &EventBus) {
+        let metrics = self.clone();
+        bus.on(move |e: &AfterSqlEvent| metrics.record_execution(&e.statement, e.elapsed, &e.outcome)).detach();
+        let metrics = self.clone();
+        bus.on(move |e: &PoolAcquireEvent| metrics.record_pool_wait(&e.statement, e.wait)).detach();
+    }
+}
+
+/// 直方图快照
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramSnapshot {
+    /// 桶上界（秒）
+    pub bounds: &'static [f64],
+    /// 累计计数：第 i 项为 ≤ `bounds[i]` 的观测数，末项（`+Inf`）为总数
+    pub cumulative: Vec<u64>,
+    /// 观测值之和
+    pub sum: Duration,
+}
+
+impl HistogramSnapshot {
+    /// 观测总数
+    pub fn count(&self) -> u64 {
+        self.cumulative.last().copied().unwrap_or(0)
+    }
+
+    /// 平均值（无观测时为 0）
+    pub fn mean(&self) -> Duration {
+        match self.count() {
+            0 => Duration::ZERO,
+            n => Duration::from_secs_f64(self.sum.as_secs_f64() / n as f64),
+        }
+    }
+}
+
+/// 单条语句的指标快照
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementMetrics {
+    pub namespace: String,
+    pub statement_id: String,
+    /// 执行次数
+    pub calls: u64,
+    /// 失败次数
+    pub errors: u64,
+    /// SELECT 取回的总行数
+    pub rows_fetched: u64,
+    /// INSERT/UPDATE/DELETE 影响的总行数
+    pub rows_affected: u64,
+    /// 执行耗时
+    pub latency: HistogramSnapshot,
+    /// 连接池等待时间（仅事务外语句）
+    pub pool_wait: HistogramSnapshot,
+}
+
+/// 指标快照
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// 各语句指标（按命名空间、语句 id 排序）
+    pub statements: Vec<StatementMetrics>,
+}
+
+impl MetricsSnapshot {
+    /// 查找指定语句的指标
+    pub fn get(&self, namespace: &str, statement_id: &str) -> Option<&StatementMetrics> {
+        self.statements
+            .iter()
+            .find(|s| s.namespace == namespace && s.statement_id == statement_id)
+    }
+
+    /// 渲染为 Prometheus 文本格式（`text/plain; version=0.0.4`）
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::new();
+        let counters: [CounterFamily; 4] = [
+            ("hirust_mapper_statement_calls_total", "SQL 语句执行次数", |s| s.calls),
+            ("hirust_mapper_statement_errors_total", "SQL 语句执行失败次数", |s| s.errors),
+            ("hirust_mapper_statement_rows_fetched_total", "SELECT 取回的总行数", |s| s.rows_fetched),
+            ("hirust_mapper_statement_rows_affected_total", "写语句影响的总行数", |s| s.rows_affected),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} counter", name, help, name);
+            for s in &self.statements {
+                let _ = writeln!(out, "{}{{{}}} {}", name, labels(s), value(s));
+            }
+        }
+        let histograms: [HistogramFamily; 2] = [
+            ("hirust_mapper_statement_duration_seconds", "SQL 语句执行耗时（秒）", |s| &s.latency),
+            ("hirust_mapper_pool_wait_seconds", "连接池等待时间（秒）", |s| &s.pool_wait),
+        ];
+        for (name, help, histogram) in histograms {
+            let _ = writeln!(out, "# HELP {} {}\n# TYPE {} histogram", name, help, name);
+            for s in &self.statements {
+                let h = histogram(s);
+                let labels = labels(s);
+                for (bound, count) in h.bounds.iter().zip(&h.cumulative) {
+                    let _ = writeln!(out, "{}_bucket{{{},le=\"{}\"}} {}", name, labels, bound, count);
+                }
+                let _ = writeln!(out, "{}_bucket{{{},le=\"+Inf\"}} {}", name, labels, h.count());
+                let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, h.sum.as_secs_f64());
+                let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, h.count());
+            }
+        }
+        out
+    }
+}
+
+fn labels(s: &StatementMetrics) -> String {
+    format!(
+        "namespace=\"{}\",statement=\"{}\"",
+        escape_label(&s.namespace),
+        escape_label(&s.statement_id)
+    )
+}
+
+/// 标签值转义：`\` → `\\`，`"` → `\"`，换行 → `\n`
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::lifecycle::SqlKind;
+
+    fn find_by_id() -> StatementContext {
+        StatementContext::new("app.UserDao", "findById", SqlKind::Select)
+    }
+
+    #[test]
+    fn test_records_events_from_bus() {
+        let bus = EventBus::new();
+        let metrics = MetricsRegistry::new();
+        bus.add_subscriber(&metrics);
+        let after = |elapsed_ms: u64, outcome: SqlOutcome| AfterSqlEvent {
+            raw_sql: "SELECT 1".into(),
+            params: vec![],
+            kind: SqlKind::Select,
+            statement: find_by_id(),
+            elapsed: Duration::from_millis(elapsed_ms),
+            outcome,
+        };
+        bus.dispatch(&after(3, SqlOutcome::Fetched(2)));
+        bus.dispatch(&after(30, SqlOutcome::Failed("boom".into())));
+        bus.dispatch(&PoolAcquireEvent { statement: find_by_id(), wait: Duration::from_millis(20) });
+
+        let snapshot = metrics.snapshot();
+        let s = snapshot.get("app.UserDao", "findById").unwrap();
+        assert_eq!((s.calls, s.errors, s.rows_fetched, s.rows_affected), (2, 1, 2, 0));
+        assert_eq!(s.latency.count(), 2);
+        assert_eq!(s.latency.sum, Duration::from_millis(33));
+        // 3ms 落入 ≤0.005 桶，30ms 落入 ≤0.05 桶
+        assert_eq!(&s.latency.cumulative[..5], &[0, 1, 1, 1, 2]);
+        assert_eq!(s.pool_wait.count(), 1);
+        assert_eq!(s.pool_wait.mean(), Duration::from_millis(20));
+
+        metrics.reset();
+        assert!(metrics.snapshot().statements.is_empty());
+    }
+
+    #[test]
+    fn test_histogram_overflow_bucket() {
+        let mut h = Histogram::new();
+        h.observe(Duration::from_secs(60));
+        let snapshot = h.snapshot();
+        assert_eq!(snapshot.cumulative[DEFAULT_BUCKETS.len() - 1], 0);
+        assert_eq!(snapshot.count(), 1);
+    }
+
+    #[test]
+    fn test_prometheus_text() {
+        let metrics = MetricsRegistry::new();
+        metrics.record_execution(&find_by_id(), Duration::from_millis(2), &SqlOutcome::Fetched(1));
+        let odd = StatementContext::new("a\"b", "x\\y", SqlKind::Update);
+        metrics.record_execution(&odd, Duration::from_millis(2), &SqlOutcome::Affected(4));
+        let text = metrics.snapshot().to_prometheus();
+
+        assert!(text.contains("# TYPE hirust_mapper_statement_calls_total counter\n"));
+        assert!(text.contains("hirust_mapper_statement_calls_total{namespace=\"app.UserDao\",statement=\"findById\"} 1\n"));
+        assert!(text.contains("hirust_mapper_statement_rows_affected_total{namespace=\"a\\\"b\",statement=\"x\\\\y\"} 4\n"));
+        assert!(text.contains("# TYPE hirust_mapper_statement_duration_seconds histogram\n"));
+        assert!(text.contains(
+            "hirust_mapper_statement_duration_seconds_bucket{namespace=\"app.UserDao\",statement=\"findById\",le=\"0.001\"} 0\n"
+        ));
+        assert!(text.contains(
+            "hirust_mapper_statement_duration_seconds_bucket{namespace=\"app.UserDao\",statement=\"findById\",le=\"0.005\"} 1\n"
+        ));
+        assert!(text.contains(
+            "hirust_mapper_statement_duration_seconds_count{namespace=\"app.UserDao\",statement=\"findById\"} 1\n"
+        ));
+        assert!(text.contains(
+            "hirust_mapper_pool_wait_seconds_bucket{namespace=\"app.UserDao\",statement=\"findById\",le=\"+Inf\"} 0\n"
+        ));
+    }
+}
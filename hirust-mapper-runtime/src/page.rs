@@ -0,0 +1,203 @@
+//! 分页查询
+//!
+//! [`PageRequest`] 描述请求的页码与页大小，[`Page`] 为一页结果（数据 + 总行数）。
+//!
+//! [`SqlSession::select_page`](crate::SqlSession::select_page) 的改写规则：
+//! - 总数：默认将原查询包装为 `SELECT COUNT(*) FROM (<原 SQL>) hirust_page_count`；
+//!   若 Mapper 中存在 `<select id="{id}_count">` 语句，则改用该语句（可写更高效的计数 SQL）。
+//! - 数据：在原 SQL 之后按 [`Dialect`] 追加 `LIMIT/OFFSET` 子句，分页参数以 `?` 绑定追加到参数列表末尾。
+
+use hirust_mapper_core::{BoundSql, ParameterMapping};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::dialect::Dialect;
+
+/// 自定义计数语句的 id 后缀：`<select id="list_user_count">` 对应 `list_user`
+pub const COUNT_STATEMENT_SUFFIX: &str = "_count";
+
+/// 分页请求（页码从 1 开始）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PageRequest {
+    /// 页码（从 1 开始；传 0 按 1 处理）
+    pub page: u64,
+    /// 每页行数
+    pub size: u64,
+}
+
+impl PageRequest {
+    /// 创建分页请求（`page < 1` 时按第 1 页处理）
+    pub fn new(page: u64, size: u64) -> Self {
+        Self {
+            page: page.max(1),
+            size,
+        }
+    }
+
+    /// 跳过的行数：`(page - 1) * size`
+    pub fn offset(&self) -> u64 {
+        self.page.max(1).saturating_sub(1).saturating_mul(self.size)
+    }
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self { page: 1, size: 10 }
+    }
+}
+
+/// 一页查询结果
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// 当前页数据
+    pub items: Vec<T>,
+    /// 满足条件的总行数
+    pub total: u64,
+    /// 当前页码（从 1 开始）
+    pub page: u64,
+    /// 每页行数
+    pub size: u64,
+}
+
+impl<T> Page<T> {
+    /// 由分页请求、数据与总数构造
+    pub fn new(request: PageRequest, items: Vec<T>, total: u64) -> Self {
+        Self {
+            items,
+            total,
+            page: request.page.max(1),
+            size: request.size,
+        }
+    }
+
+    /// 总页数（`size == 0` 时为 0）
+    pub fn total_pages(&self) -> u64 {
+        if self.size == 0 {
+            0
+        } else {
+            self.total.div_ceil(self.size)
+        }
+    }
+
+    /// 是否存在下一页
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages()
+    }
+
+    /// 转换每条数据，保留分页信息
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            page: self.page,
+            size: self.size,
+        }
+    }
+}
+
+/// 去除 SQL 末尾的空白与分号（包装为子查询 / 追加子句前需要）
+fn trim_statement_end(sql: &str) -> &str {
+    sql.trim_end().trim_end_matches(';').trim_end()
+}
+
+/// 将查询包装为计数查询（参数列表不变）
+pub fn count_bound_sql(bound: &BoundSql) -> BoundSql {
+    BoundSql {
+        sql: format!(
+            "SELECT COUNT(*) FROM ({}) hirust_page_count",
+            trim_statement_end(&bound.sql)
+        ),
+        parameters: bound.parameters.clone(),
+        parameter_mappings: bound.parameter_mappings.clone(),
+    }
+}
+
+/// 在查询末尾追加方言对应的分页子句，分页参数追加到参数列表末尾
+pub fn limit_bound_sql(bound: BoundSql, dialect: Dialect, request: PageRequest) -> BoundSql {
+    let (clause, offset_first) = dialect.limit_offset_clause();
+    let mut parameters = bound.parameters;
+    let mut parameter_mappings = bound.parameter_mappings;
+    if !parameter_mappings.is_empty() {
+        // 分页参数无类型声明，保持映射与参数一一对应
+        parameter_mappings.extend([ParameterMapping::default(), ParameterMapping::default()]);
+    }
+    let (size, offset) = (Value::from(request.size), Value::from(request.offset()));
+    if offset_first {
+        parameters.extend([offset, size]);
+    } else {
+        parameters.extend([size, offset]);
+    }
+    BoundSql {
+        sql: format!("{} {}", trim_statement_end(&bound.sql), clause),
+        parameters,
+        parameter_mappings,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bound(sql: &str, params: Vec<Value>) -> BoundSql {
+        BoundSql {
+            sql: sql.to_string(),
+            parameters: params,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_page_request_offset() {
+        assert_eq!(PageRequest::new(1, 20).offset(), 0);
+        assert_eq!(PageRequest::new(3, 20).offset(), 40);
+        // 页码 0 按第 1 页处理
+        assert_eq!(PageRequest::new(0, 20).page, 1);
+        assert_eq!(PageRequest { page: 0, size: 20 }.offset(), 0);
+    }
+
+    #[test]
+    fn test_page_total_pages() {
+        let page = Page::new(PageRequest::new(2, 10), vec![1, 2, 3], 23);
+        assert_eq!(page.total_pages(), 3);
+        assert!(page.has_next());
+        let last = Page::new(PageRequest::new(3, 10), vec![1], 23);
+        assert!(!last.has_next());
+        let empty: Page<i32> = Page::new(PageRequest::new(1, 0), vec![], 5);
+        assert_eq!(empty.total_pages(), 0);
+    }
+
+    #[test]
+    fn test_page_map() {
+        let page = Page::new(PageRequest::new(1, 2), vec![1, 2], 5).map(|n| n * 10);
+        assert_eq!(page.items, vec![10, 20]);
+        assert_eq!(page.total, 5);
+    }
+
+    #[test]
+    fn test_count_bound_sql() {
+        let b = bound("SELECT * FROM t WHERE a = ? ;\n", vec![json!(1)]);
+        let c = count_bound_sql(&b);
+        assert_eq!(
+            c.sql,
+            "SELECT COUNT(*) FROM (SELECT * FROM t WHERE a = ?) hirust_page_count"
+        );
+        assert_eq!(c.parameters, vec![json!(1)]);
+    }
+
+    #[test]
+    fn test_limit_bound_sql_by_dialect() {
+        let req = PageRequest::new(3, 10);
+        let b = limit_bound_sql(
+            bound("SELECT * FROM t WHERE a = ?", vec![json!("x")]),
+            Dialect::Sqlite,
+            req,
+        );
+        assert_eq!(b.sql, "SELECT * FROM t WHERE a = ? LIMIT ? OFFSET ?");
+        assert_eq!(b.parameters, vec![json!("x"), json!(10), json!(20)]);
+
+        let b = limit_bound_sql(bound("SELECT * FROM t;", vec![]), Dialect::MySql, req);
+        assert_eq!(b.sql, "SELECT * FROM t LIMIT ?, ?");
+        assert_eq!(b.parameters, vec![json!(20), json!(10)]);
+    }
+}
@@ -0,0 +1,228 @@
+//! 敏感参数打码
+//!
+//! SQL 日志（参数内联进 `?`）与 SQL 事件（`params`）默认输出参数原值。以下任一来源标记的参数在输出前
+//! 替换为掩码——只影响日志与事件，实际绑定到数据库的仍是原值：
+//!
+//! - XML 占位符选项：`#{password, redact=true}`
+//! - 配置 `[settings.redaction] patterns`：参数名模式，大小写不敏感，`*` 匹配任意字符，
+//!   按属性路径末段匹配（`user.password`、`items[0].id_card` 分别按 `password`、`id_card` 匹配）
+//! - 已注册 [`MapperModel`](crate::MapperModel) 的 `#[mapper(redact)]` 字段名（按名称精确匹配，作用于所有语句）
+//!
+//! 掩码由 `[settings.redaction] mask` 配置，默认 [`DEFAULT_MASK`]。
+
+use std::borrow::Cow;
+
+use hirust_mapper_core::{BoundSql, ParameterMapping};
+use serde_json::Value;
+
+/// 默认掩码
+pub const DEFAULT_MASK: &str = "******";
+
+/// 打码规则（对应 `[settings.redaction]`）
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct RedactionPolicy {
+    /// 敏感参数名模式（如 `password`、`*token*`、`id_card`）
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// 替换敏感参数值的掩码
+    #[serde(default = "default_mask")]
+    pub mask: String,
+}
+
+fn default_mask() -> String {
+    DEFAULT_MASK.to_string()
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self { patterns: Vec::new(), mask: default_mask() }
+    }
+}
+
+impl RedactionPolicy {
+    /// 无名称模式、默认掩码（仅 XML `redact=true` 与模型字段生效）
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置敏感参数名模式
+    pub fn with_patterns<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.patterns = patterns.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// 设置掩码
+    pub fn with_mask(mut self, mask: impl Into<String>) -> Self {
+        self.mask = mask.into();
+        self
+    }
+}
+
+/// 打码器：判定敏感参数并生成打码后的参数列表（工厂按配置与已注册模型创建，所有 session 共享）
+#[derive(Debug, Clone)]
+pub struct Redactor {
+    /// 名称模式（含模型字段名）
+    patterns: Vec<String>,
+    mask: Cow<'static, str>,
+}
+
+impl Default for Redactor {
+    /// 无名称模式、默认掩码：只有 `redact=true` 的占位符被打码
+    fn default() -> Self {
+        Self { patterns: Vec::new(), mask: Cow::Borrowed(DEFAULT_MASK) }
+    }
+}
+
+impl Redactor {
+    /// 按打码规则创建
+    pub fn new(policy: &RedactionPolicy) -> Self {
+        Self {
+            patterns: policy.patterns.iter().map(|p| p.trim().to_string()).filter(|p| !p.is_empty()).collect(),
+            mask: Cow::Owned(policy.mask.clone()),
+        }
+    }
+
+    /// 追加按名称精确匹配的敏感参数名（如模型的 `#[mapper(redact)]` 字段）
+    pub fn with_names<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        // 字段名不含 `*`，作为模式即为精确匹配
+        self.patterns.extend(names.into_iter().map(|n| n.as_ref().to_string()));
+        self
+    }
+
+    /// 掩码
+    pub fn mask(&self) -> &str {
+        &self.mask
+    }
+
+    /// 属性路径是否匹配敏感参数名模式
+    pub fn is_sensitive_name(&self, property: &str) -> bool {
+        if self.patterns.is_empty() {
+            return false;
+        }
+        let name = last_segment(property);
+        self.patterns.iter().any(|p| wildcard_match(p.as_bytes(), name.as_bytes()))
+    }
+
+    /// 参数是否需打码（占位符声明 `redact=true` 或名称匹配）
+    pub fn is_sensitive(&self, mapping: &ParameterMapping) -> bool {
+        mapping.redact || self.is_sensitive_name(&mapping.property)
+    }
+
+    /// 打码后的参数列表（与 `bound.parameters` 一一对应；无参数映射的参数原样保留）
+    pub fn redact_params(&self, bound: &BoundSql) -> Vec<Value> {
+        bound
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(i, value)| match bound.parameter_mapping(i) {
+                Some(mapping) if self.is_sensitive(mapping) => Value::String(self.mask.to_string()),
+                _ => value.clone(),
+            })
+            .collect()
+    }
+
+    /// 打码后的 [`BoundSql`]（无敏感参数时借用原值，不克隆）
+    pub fn redact<'a>(&self, bound: &'a BoundSql) -> Cow<'a, BoundSql> {
+        if !bound.parameter_mappings.iter().any(|m| self.is_sensitive(m)) {
+            return Cow::Borrowed(bound);
+        }
+        Cow::Owned(BoundSql {
+            sql: bound.sql.clone(),
+            parameters: self.redact_params(bound),
+            parameter_mappings: bound.parameter_mappings.clone(),
+        })
+    }
+}
+
+/// 属性路径末段，去掉下标（`user.password` → `password`，`items[0].id_card` → `id_card`，`ids[1]` → `ids`）
+fn last_segment(property: &str) -> &str {
+    let last = property.rsplit('.').next().unwrap_or(property).trim();
+    last.split('[').next().unwrap_or(last)
+}
+
+/// 大小写不敏感（ASCII）的通配匹配，`*` 匹配任意长度字符
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // 最近一个 `*` 的位置与其当前匹配到的文本位置（回溯点）
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if c.eq_ignore_ascii_case(&text[t]) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn mapping(property: &str, redact: bool) -> ParameterMapping {
+        ParameterMapping { property: property.into(), redact, ..Default::default() }
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match(b"password", b"Password"));
+        assert!(wildcard_match(b"*token*", b"accessTokenValue"));
+        assert!(wildcard_match(b"*_card", b"id_card"));
+        assert!(wildcard_match(b"*", b""));
+        assert!(!wildcard_match(b"password", b"password_hint"));
+        assert!(!wildcard_match(b"*_card", b"card"));
+    }
+
+    #[test]
+    fn test_sensitive_by_flag_pattern_and_name() {
+        let redactor = Redactor::new(&RedactionPolicy::new().with_patterns(["*token*", " id_card "]))
+            .with_names(["password"]);
+        assert!(redactor.is_sensitive(&mapping("remark", true)));
+        assert!(redactor.is_sensitive(&mapping("user.password", false)));
+        assert!(redactor.is_sensitive(&mapping("items[0].id_card", false)));
+        assert!(redactor.is_sensitive(&mapping("refreshToken", false)));
+        assert!(!redactor.is_sensitive(&mapping("name", false)));
+        assert!(!Redactor::default().is_sensitive(&mapping("password", false)));
+    }
+
+    #[test]
+    fn test_redact_bound() {
+        let redactor = Redactor::new(&RedactionPolicy::new().with_patterns(["password"]).with_mask("<hidden>"));
+        let bound = BoundSql {
+            sql: "UPDATE users SET password = ?, pin = ? WHERE id = ?".into(),
+            parameters: vec![json!("s3cret"), json!(1234), json!(7)],
+            parameter_mappings: vec![mapping("password", false), mapping("pin", true), mapping("id", false)],
+        };
+        let redacted = redactor.redact(&bound);
+        assert_eq!(redacted.parameters, vec![json!("<hidden>"), json!("<hidden>"), json!(7)]);
+        assert_eq!(redacted.sql, bound.sql);
+
+        let plain = BoundSql { parameter_mappings: vec![mapping("id", false)], parameters: vec![json!(1)], ..bound };
+        assert!(matches!(redactor.redact(&plain), Cow::Borrowed(_)));
+        // 手工构造、无参数映射的 BoundSql 原样保留
+        let unmapped = BoundSql { parameter_mappings: vec![], ..plain };
+        assert_eq!(redactor.redact_params(&unmapped), vec![json!(1)]);
+    }
+}
@@ -1,10 +1,10 @@
-//! Mapper 注册表与类型别名注册表
+//! Mapper 注册表、类型别名注册表与模型注册表
 //!
 //! 线程安全地持有所有已解析的 `Mapper` 实例，支持热重载时的并发读写。
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::{Arc, RwLock};
-use hirust_mapper_core::{Mapper, MapperError, MyBatisXmlParser};
+use hirust_mapper_core::{Mapper, MapperError, MyBatisXmlParser, ResultColumn};
 use crate::error::MapperRuntimeError;
 
 /// 线程安全的 Mapper 注册表
@@ -131,6 +131,83 @@ impl TypeAliasRegistry {
     }
 }
 
+/// 带列映射注解的模型（通常经 `#[derive(MapperModel)]` 生成）
+///
+/// 经 [`SqlSessionFactoryBuilder::model`](crate::SqlSessionFactoryBuilder::model) 注册后，
+/// `resultType` 为该类型的查询（无 ResultMap）按注解改名列、经类型处理器解码列。
+pub trait MapperModel {
+    /// 类型名（与 XML `resultType` 匹配，亦可经类型别名或全限定路径引用）
+    const TYPE_NAME: &'static str;
+
+    /// 字段名 → 列名
+    fn column_mappings() -> &'static [(&'static str, &'static str)];
+
+    /// 声明了 type_handler 的字段 → 处理器类型名（Rust 类型或注册名）
+    fn type_handlers() -> &'static [(&'static str, &'static str)];
+
+    /// 敏感字段名：同名参数在 SQL 日志与事件中打码（见 [`Redactor`](crate::Redactor)）
+    fn redacted_fields() -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// 模型注册表：类型名 → 列声明（字段、列名、处理器），及全部模型的敏感字段名
+#[derive(Debug, Default, Clone)]
+pub struct ModelRegistry {
+    models: HashMap<String, Vec<ResultColumn>>,
+    redacted_fields: BTreeSet<String>,
+}
+
+impl ModelRegistry {
+    /// 创建空注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册模型的列声明（同名后注册者覆盖）
+    pub fn register<M: MapperModel>(&mut self) {
+        let handlers: HashMap<&str, &str> = M::type_handlers().iter().copied().collect();
+        let columns = M::column_mappings()
+            .iter()
+            .map(|(field, column)| ResultColumn {
+                property: field.to_string(),
+                column: column.to_string(),
+                rust_type: handlers.get(field).map(|h| h.to_string()),
+                ..Default::default()
+            })
+            .collect();
+        self.models.insert(M::TYPE_NAME.to_string(), columns);
+        self.redacted_fields.extend(M::redacted_fields().iter().map(|f| f.to_string()));
+    }
+
+    /// 按 `resultType` 查找列声明：先精确匹配，再按路径末段（`myapp::User` / `com.x.User` → `User`）
+    pub fn get(&self, result_type: &str) -> Option<&[ResultColumn]> {
+        let result_type = result_type.trim();
+        self.models
+            .get(result_type)
+            .or_else(|| {
+                let short = result_type.rsplit("::").next().unwrap_or(result_type);
+                self.models.get(short.rsplit('.').next().unwrap_or(short))
+            })
+            .map(Vec::as_slice)
+    }
+
+    /// 已注册模型声明的敏感字段名（去重、有序）
+    pub fn redacted_fields(&self) -> impl Iterator<Item = &str> {
+        self.redacted_fields.iter().map(String::as_str)
+    }
+
+    /// 已注册模型数量
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -190,4 +267,32 @@ mod tests {
         // 清理
         std::fs::remove_dir_all(&temp).ok();
     }
+
+    #[test]
+    fn test_model_registry_lookup() {
+        struct User;
+        impl MapperModel for User {
+            const TYPE_NAME: &'static str = "User";
+            fn column_mappings() -> &'static [(&'static str, &'static str)] {
+                &[("name", "user_name"), ("created", "created_at")]
+            }
+            fn type_handlers() -> &'static [(&'static str, &'static str)] {
+                &[("created", "chrono::DateTime<chrono::Utc>")]
+            }
+            fn redacted_fields() -> &'static [&'static str] {
+                &["password"]
+            }
+        }
+
+        let mut models = ModelRegistry::new();
+        models.register::<User>();
+        let columns = models.get("myapp::model::User").unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].column, "user_name");
+        assert_eq!(columns[0].rust_type, None);
+        assert_eq!(columns[1].rust_type.as_deref(), Some("chrono::DateTime<chrono::Utc>"));
+        assert!(models.get("com.example.User").is_some());
+        assert!(models.get("Order").is_none());
+        assert_eq!(models.redacted_fields().collect::<Vec<_>>(), ["password"]);
+    }
 }
@@ -0,0 +1,273 @@
+//! 瞬时错误重试策略
+//!
+//! 锁竞争类错误（sqlite `SQLITE_BUSY` / `SQLITE_LOCKED`、mysql 死锁 1213、
+//! postgres 死锁 `40P01` 与序列化失败 `40001`）通常在稍后重试即可成功。
+//! [`RetryPolicy`] 描述最大尝试次数、指数退避（可加抖动）与参与重试的错误类别，
+//! 对应配置 `[settings.retry]`，默认不重试（`max_attempts = 1`）。
+//!
+//! 重试作用于两处：
+//! - **事务外的查询**（`select_one` / `select_list` / `select_page`）：幂等，单条语句重试；
+//! - **整个事务单元**（[`SqlSessionFactory::transaction_with_retry`](crate::SqlSessionFactory::transaction_with_retry)）：
+//!   回滚后重新开启事务并再次调用闭包。
+//!
+//! 事务内的单条语句不重试（数据库通常已回滚或中止该事务）。每次重试前派发 [`RetryEvent`]。
+
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+
+use crate::error::MapperRuntimeError;
+use crate::event::lifecycle::{RetryEvent, RetryScope};
+use crate::event::EventBus;
+
+/// 可重试的错误类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryErrorClass {
+    /// 数据库忙 / 被锁（sqlite `SQLITE_BUSY`、`SQLITE_LOCKED`）
+    Busy,
+    /// 死锁（mysql 1213、postgres `40P01`）
+    Deadlock,
+    /// 序列化失败（SQLSTATE `40001`）
+    SerializationFailure,
+}
+
+impl RetryErrorClass {
+    /// 判定错误所属的可重试类别；非数据库错误或其他数据库错误返回 `None`
+    pub fn classify(err: &MapperRuntimeError) -> Option<RetryErrorClass> {
+        let MapperRuntimeError::Database(sqlx::Error::Database(db)) = err else {
+            return None;
+        };
+        #[cfg(feature = "mysql")]
+        if let Some(mysql) = db.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+            // mysql 死锁的 SQLSTATE 同为 40001，按错误号区分
+            if mysql.number() == 1213 {
+                return Some(RetryErrorClass::Deadlock);
+            }
+        }
+        let code = db.code()?;
+        match code.as_ref() {
+            "40P01" => Some(RetryErrorClass::Deadlock),
+            "40001" => Some(RetryErrorClass::SerializationFailure),
+            // sqlite 返回（扩展）结果码，低 8 位为主结果码：5 = BUSY，6 = LOCKED
+            other => match other.parse::<i32>() {
+                Ok(n) if matches!(n & 0xff, 5 | 6) => Some(RetryErrorClass::Busy),
+                _ => None,
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for RetryErrorClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RetryErrorClass::Busy => write!(f, "busy"),
+            RetryErrorClass::Deadlock => write!(f, "deadlock"),
+            RetryErrorClass::SerializationFailure => write!(f, "serialization_failure"),
+        }
+    }
+}
+
+/// 重试策略（对应 `[settings.retry]`）
+///
+/// 第 n 次重试前等待 `initial_backoff_ms * 2^(n-1)`，上限 `max_backoff_ms`；
+/// 开启 `jitter` 时在 `[一半, 全部]` 区间内随机取值，避免竞争者同时重试。
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（含首次），`1` 表示不重试
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// 首次重试前的等待（毫秒）
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+    /// 单次等待上限（毫秒）
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+    /// 是否对等待时间加随机抖动
+    #[serde(default = "default_jitter")]
+    pub jitter: bool,
+    /// 参与重试的错误类别
+    #[serde(default = "default_error_classes")]
+    pub error_classes: Vec<RetryErrorClass>,
+}
+
+fn default_max_attempts() -> u32 { 1 }
+fn default_initial_backoff_ms() -> u64 { 20 }
+fn default_max_backoff_ms() -> u64 { 1000 }
+fn default_jitter() -> bool { true }
+fn default_error_classes() -> Vec<RetryErrorClass> {
+    vec![
+        RetryErrorClass::Busy,
+        RetryErrorClass::Deadlock,
+        RetryErrorClass::SerializationFailure,
+    ]
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            jitter: default_jitter(),
+            error_classes: default_error_classes(),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 指定最大尝试次数（含首次），其余取默认值
+    pub fn new(max_attempts: u32) -> Self {
+        Self { max_attempts, ..Self::default() }
+    }
+
+    /// 设置退避区间（毫秒）
+    pub fn with_backoff_ms(mut self, initial_ms: u64, max_ms: u64) -> Self {
+        self.initial_backoff_ms = initial_ms;
+        self.max_backoff_ms = max_ms;
+        self
+    }
+
+    /// 设置是否加随机抖动
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// 设置参与重试的错误类别
+    pub fn with_error_classes(mut self, classes: Vec<RetryErrorClass>) -> Self {
+        self.error_classes = classes;
+        self
+    }
+
+    /// 是否启用重试
+    pub fn is_enabled(&self) -> bool {
+        self.max_attempts > 1 && !self.error_classes.is_empty()
+    }
+
+    /// 错误在本策略下的可重试类别（不考虑次数）
+    pub fn retryable_class(&self, err: &MapperRuntimeError) -> Option<RetryErrorClass> {
+        RetryErrorClass::classify(err).filter(|c| self.error_classes.contains(c))
+    }
+
+    /// 第 `attempt` 次尝试失败后、下一次尝试前的等待时间
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exp = attempt.saturating_sub(1).min(32);
+        let base = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << exp)
+            .min(self.max_backoff_ms);
+        let ms = if self.jitter && base > 1 {
+            let half = base / 2;
+            half + random_u64() % (base - half + 1)
+        } else {
+            base
+        };
+        Duration::from_millis(ms)
+    }
+}
+
+/// 抖动用随机数（每个 `RandomState` 携带不同的随机键，无需额外依赖）
+fn random_u64() -> u64 {
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(std::time::SystemTime::UNIX_EPOCH.elapsed().unwrap_or_default().as_nanos());
+    hasher.finish()
+}
+
+/// 一次操作的重试状态：记录已尝试次数，判定是否重试并在重试前派发事件、等待退避
+pub(crate) struct Retrier<'a> {
+    policy: &'a RetryPolicy,
+    event_bus: &'a EventBus,
+    attempt: u32,
+}
+
+impl<'a> Retrier<'a> {
+    pub(crate) fn new(policy: &'a RetryPolicy, event_bus: &'a EventBus) -> Self {
+        Self { policy, event_bus, attempt: 1 }
+    }
+
+    /// 本次失败是否应重试（未用尽次数且属于可重试类别）
+    pub(crate) fn should_retry(&self, err: &MapperRuntimeError) -> bool {
+        self.attempt < self.policy.max_attempts && self.policy.retryable_class(err).is_some()
+    }
+
+    /// 派发 [`RetryEvent`]、记录日志并等待退避，随后进入下一次尝试
+    pub(crate) async fn wait(&mut self, err: &MapperRuntimeError, scope: impl FnOnce() -> RetryScope) {
+        let delay = self.policy.backoff(self.attempt);
+        let class = self.policy.retryable_class(err).unwrap_or(RetryErrorClass::Busy);
+        let scope = scope();
+        crate::telemetry::warn(&format!(
+            "{} 第 {}/{} 次尝试失败（{}），{}ms 后重试: {}",
+            scope,
+            self.attempt,
+            self.policy.max_attempts,
+            class,
+            delay.as_millis(),
+            err
+        ));
+        self.event_bus.dispatch_if(|| RetryEvent {
+            scope,
+            attempt: self.attempt,
+            max_attempts: self.policy.max_attempts,
+            delay,
+            class,
+            error: err.to_string(),
+        });
+        tokio::time::sleep(delay).await;
+        self.attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_policy_disabled() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_attempts, 1);
+        assert!(!policy.is_enabled());
+        assert!(RetryPolicy::new(3).is_enabled());
+        assert!(!RetryPolicy::new(3).with_error_classes(vec![]).is_enabled());
+    }
+
+    #[test]
+    fn test_backoff_exponential_and_capped() {
+        let policy = RetryPolicy::new(10).with_backoff_ms(10, 100).with_jitter(false);
+        assert_eq!(policy.backoff(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff(3), Duration::from_millis(40));
+        assert_eq!(policy.backoff(5), Duration::from_millis(100));
+        assert_eq!(policy.backoff(60), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_backoff_jitter_within_bounds() {
+        let policy = RetryPolicy::new(10).with_backoff_ms(40, 1000);
+        for _ in 0..100 {
+            let d = policy.backoff(2).as_millis();
+            assert!((40..=80).contains(&d), "抖动应落在 [40, 80]，实际 {}", d);
+        }
+    }
+
+    #[test]
+    fn test_classify_non_database_errors() {
+        assert_eq!(RetryErrorClass::classify(&MapperRuntimeError::Config("x".into())), None);
+        let timeout = MapperRuntimeError::Timeout { namespace: "a".into(), id: "b".into() };
+        assert_eq!(RetryErrorClass::classify(&timeout), None);
+        let pool = MapperRuntimeError::Database(sqlx::Error::PoolTimedOut);
+        assert_eq!(RetryErrorClass::classify(&pool), None);
+    }
+
+    #[test]
+    fn test_deserialize_policy() {
+        let policy: RetryPolicy = toml::from_str(
+            "max_attempts = 4\ninitial_backoff_ms = 5\nerror_classes = [\"deadlock\"]",
+        )
+        .unwrap();
+        assert_eq!(policy.max_attempts, 4);
+        assert_eq!(policy.initial_backoff_ms, 5);
+        assert_eq!(policy.max_backoff_ms, 1000);
+        assert!(policy.jitter);
+        assert_eq!(policy.error_classes, vec![RetryErrorClass::Deadlock]);
+    }
+}
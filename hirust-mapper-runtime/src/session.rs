@@ -11,10 +11,14 @@
 //! Session 是请求级对象（单线程顺序使用），`&mut self` 是惯用且正确的设计。
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use futures_util::StreamExt;
+use futures_util::{Stream, StreamExt};
 use hirust_mapper_core::{BoundSql, Mapper, ResultMap};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -22,32 +26,238 @@ use serde_json::Value;
 
 use crate::environment::Environment;
 use crate::error::{MapperRuntimeError, Result};
-use crate::event::lifecycle::{AfterSqlEvent, BeforeSqlEvent, SqlKind, SqlOutcome};
+use crate::event::lifecycle::{
+    PoolAcquireEvent, RetryScope, RollbackReason, SqlKind, SqlOutcome, StatementContext, TransactionBeginEvent,
+    TransactionCommitEvent, TransactionOutcomeUnknownEvent, TransactionRollbackEvent,
+};
 use crate::event::EventBus;
-use crate::executor::SimpleExecutor;
-use crate::registry::{MapperRegistry, TypeAliasRegistry};
+use crate::executor::{with_timeout, SimpleExecutor, StatementTimeout};
+use crate::explain::{explain_bound, QueryPlan, SlowQueryExplainer};
+use crate::handler::result_set::{key_rows_by, DuplicateKeyPolicy, OutOfOrderPolicy, ResultSetHandler};
+use crate::page::{self, Page, PageRequest};
+use crate::redact::Redactor;
+use crate::registry::{MapperRegistry, ModelRegistry, TypeAliasRegistry};
+use crate::retry::{Retrier, RetryPolicy};
+use crate::sql_comment::SqlCommenter;
 use crate::sql_log::SqlLogConfig;
-use crate::type_handler::TypeHandlerRegistry;
+use crate::telemetry::Span;
+use crate::transaction::TransactionOptions;
+use crate::type_handler::{BinaryEncoding, TypeHandlerRegistry};
+
+/// 会话 id 分配器（进程内单调递增，用于在事件与日志中区分会话）
+static NEXT_SESSION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 事务结束回调（[`SqlSession::after_commit`] / [`SqlSession::after_rollback`]）
+type TransactionHook = Box<dyn FnOnce() + Send>;
+
+/// 会话事务：直接持有池连接，事务控制语句由本结构执行（不经 `sqlx::Transaction`），
+/// 以便事务连接上的语句超时时将连接标记为关闭后丢弃，而不是回滚后归还连接池（超时的语句可能仍在执行）。
+struct SessionTransaction {
+    conn: sqlx::pool::PoolConnection<sqlx::Any>,
+    /// 事务尚未提交或回滚
+    open: bool,
+}
+
+impl SessionTransaction {
+    /// 在 `conn` 上执行 `begin` 语句开启事务
+    async fn begin(mut conn: sqlx::pool::PoolConnection<sqlx::Any>, begin: &str) -> std::result::Result<Self, sqlx::Error> {
+        sqlx::query(sqlx::AssertSqlSafe(begin)).execute(&mut *conn).await?;
+        Ok(Self { conn, open: true })
+    }
+
+    async fn commit(self) -> std::result::Result<(), sqlx::Error> {
+        self.finish("COMMIT").await
+    }
+
+    async fn rollback(self) -> std::result::Result<(), sqlx::Error> {
+        self.finish("ROLLBACK").await
+    }
+
+    /// 执行结束语句；失败时连接状态未知，关闭而不归还连接池
+    async fn finish(mut self, sql: &'static str) -> std::result::Result<(), sqlx::Error> {
+        let result = sqlx::query(sqlx::AssertSqlSafe(sql)).execute(&mut *self.conn).await;
+        if result.is_ok() {
+            self.open = false;
+        }
+        result.map(|_| ())
+    }
+
+    /// 放弃事务：关闭连接（数据库随之回滚），不归还连接池
+    fn discard(self) {
+        drop(self);
+    }
+}
+
+impl Drop for SessionTransaction {
+    /// 未结束（含被放弃、结束语句失败或被取消）的事务：关闭连接，由数据库回滚
+    fn drop(&mut self) {
+        if self.open {
+            self.conn.close_on_drop();
+        }
+    }
+}
+
+impl std::ops::Deref for SessionTransaction {
+    type Target = sqlx::AnyConnection;
+
+    fn deref(&self) -> &sqlx::AnyConnection {
+        &self.conn
+    }
+}
+
+impl std::ops::DerefMut for SessionTransaction {
+    fn deref_mut(&mut self) -> &mut sqlx::AnyConnection {
+        &mut self.conn
+    }
+}
+
+/// 事务生命周期：派发事务事件、保存并在事务结束时执行回调
+///
+/// 与事务连接分开存放：session 未提交即被 drop 时，由本结构的 `Drop` 派发回滚事件并执行 `after_rollback` 回调
+/// （事务连接随之关闭，由数据库回滚）。
+struct TransactionHooks {
+    session_id: u64,
+    event_bus: Arc<EventBus>,
+    /// 当前事务开启时刻（事务进行中为 `Some`）
+    started: Option<Instant>,
+    /// 各保存点创建时已登记的 `after_commit` 回调数（回滚到保存点时丢弃其后登记的回调）
+    savepoint_marks: Vec<(String, usize)>,
+    /// 提交成功后执行的回调
+    after_commit: Vec<TransactionHook>,
+    /// 回滚后执行的回调
+    after_rollback: Vec<TransactionHook>,
+}
+
+impl TransactionHooks {
+    fn new(session_id: u64, event_bus: Arc<EventBus>) -> Self {
+        Self {
+            session_id,
+            event_bus,
+            started: None,
+            savepoint_marks: Vec::new(),
+            after_commit: Vec::new(),
+            after_rollback: Vec::new(),
+        }
+    }
+
+    /// 事务已开启：派发开启事件
+    fn begun(&mut self, options: TransactionOptions) {
+        self.started = Some(Instant::now());
+        let session_id = self.session_id;
+        self.event_bus.dispatch_if(|| TransactionBeginEvent { session_id, options });
+    }
+
+    /// 事务已提交：派发提交事件，执行 `after_commit` 回调
+    fn committed(&mut self) {
+        let elapsed = self.finish();
+        self.after_rollback.clear();
+        let hooks = std::mem::take(&mut self.after_commit);
+        let session_id = self.session_id;
+        self.event_bus.dispatch_if(|| TransactionCommitEvent { session_id, elapsed });
+        run_hooks(hooks, "after_commit");
+    }
+
+    /// 事务已回滚（或被放弃）：派发回滚事件，执行 `after_rollback` 回调
+    fn rolled_back(&mut self, reason: RollbackReason) {
+        let elapsed = self.finish();
+        self.after_commit.clear();
+        let hooks = std::mem::take(&mut self.after_rollback);
+        let session_id = self.session_id;
+        self.event_bus.dispatch_if(|| TransactionRollbackEvent { session_id, elapsed, reason });
+        run_hooks(hooks, "after_rollback");
+    }
+
+    /// 提交结果未知（连接中断等）：派发结果未知事件，丢弃全部回调
+    fn outcome_unknown(&mut self, error: String) {
+        let elapsed = self.finish();
+        self.after_commit.clear();
+        self.after_rollback.clear();
+        let session_id = self.session_id;
+        self.event_bus.dispatch_if(|| TransactionOutcomeUnknownEvent { session_id, elapsed, error });
+    }
+
+    /// 结束当前事务，返回其持续时间
+    fn finish(&mut self) -> Duration {
+        self.savepoint_marks.clear();
+        self.started.take().map(|t| t.elapsed()).unwrap_or_default()
+    }
+
+    fn savepoint_created(&mut self, name: &str) {
+        self.savepoint_marks.push((name.to_string(), self.after_commit.len()));
+    }
+
+    /// 释放保存点同时释放其后创建的保存点
+    fn savepoint_released(&mut self, name: &str) {
+        if let Some(index) = self.savepoint_marks.iter().position(|(n, _)| n == name) {
+            self.savepoint_marks.truncate(index);
+        }
+    }
+
+    /// 回滚到保存点：丢弃其后登记的 `after_commit` 回调；保存点本身仍有效，其后创建的保存点失效
+    fn savepoint_rolled_back(&mut self, name: &str) {
+        if let Some(index) = self.savepoint_marks.iter().position(|(n, _)| n == name) {
+            self.after_commit.truncate(self.savepoint_marks[index].1);
+            self.savepoint_marks.truncate(index + 1);
+        }
+    }
+}
+
+impl Drop for TransactionHooks {
+    fn drop(&mut self) {
+        if self.started.is_some() {
+            self.rolled_back(RollbackReason::Dropped);
+        }
+    }
+}
+
+/// 提交失败是否表示事务未提交
+///
+/// 数据库返回的错误表示提交被拒绝（未结束的事务连接随之关闭，由数据库回滚）；
+/// 连接中断等其他错误发生时 COMMIT 可能已在数据库生效，结果未知。
+fn commit_rejected(err: &sqlx::Error) -> bool {
+    matches!(err, sqlx::Error::Database(_))
+}
 
 /// SqlSession（请求级）
 pub struct SqlSession {
+    /// 会话 id（见 [`SqlSession::id`]）
+    id: u64,
     environment: Environment,
     /// Mapper 注册表（内部自带 RwLock，无需外层再包一层锁）
     mapper_registry: Arc<MapperRegistry>,
     type_alias_registry: Arc<TypeAliasRegistry>,
     type_handler_registry: Arc<TypeHandlerRegistry>,
-    sql_log: Arc<SqlLogConfig>,
     event_bus: Arc<EventBus>,
     executor: SimpleExecutor,
-    transaction: Option<sqlx::Transaction<'static, sqlx::Any>>,
+    /// ResultMap 映射（共享工厂的类型处理器注册表与类型别名）
+    result_set_handler: ResultSetHandler,
+    transaction: Option<SessionTransaction>,
+    /// 当前事务的 span（`tracing` feature；事务内的 SQL span 挂在其下）
+    transaction_span: Span,
+    /// 事务事件与结束回调
+    hooks: TransactionHooks,
+    /// 已创建的保存点计数（用于生成唯一保存点名）
+    savepoint_seq: usize,
+    /// 当前事务是否只读（只读时拒绝 insert/update/delete）
+    read_only: bool,
+    /// 事务因语句超时被中止（连接已丢弃），须 rollback 后才能继续使用
+    transaction_aborted: bool,
+    /// 语句默认超时（XML `timeout` 属性优先）
+    default_timeout: Option<Duration>,
+    /// 瞬时错误重试策略（仅作用于事务外查询）
+    retry_policy: Arc<RetryPolicy>,
+    /// SQL 来源注释（`[settings.sql_comment]` 开启时）
+    sql_commenter: Option<Arc<SqlCommenter>>,
     closed: bool,
 }
 
 impl std::fmt::Debug for SqlSession {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("SqlSession")
+            .field("id", &self.id)
             .field("driver", &self.environment.driver())
             .field("in_transaction", &self.transaction.is_some())
+            .field("read_only", &self.read_only)
             .field("closed", &self.closed)
             .finish()
     }
@@ -64,21 +274,84 @@ impl SqlSession {
         event_bus: Arc<EventBus>,
     ) -> Self {
         let executor = SimpleExecutor::new(Arc::clone(&type_handler_registry))
-            .with_sql_log(Arc::clone(&sql_log))
+            .with_sql_log(sql_log)
             .with_event_bus(Arc::clone(&event_bus));
+        let result_set_handler = ResultSetHandler::with_registry(Arc::clone(&type_handler_registry))
+            .with_type_aliases(Arc::clone(&type_alias_registry));
+        let id = NEXT_SESSION_ID.fetch_add(1, Ordering::Relaxed);
+        let hooks = TransactionHooks::new(id, Arc::clone(&event_bus));
         Self {
+            id,
             environment,
             mapper_registry,
             type_alias_registry,
             type_handler_registry,
-            sql_log,
             event_bus,
             executor,
+            result_set_handler,
             transaction: None,
+            transaction_span: Span::default(),
+            hooks,
+            savepoint_seq: 0,
+            read_only: false,
+            transaction_aborted: false,
+            default_timeout: None,
+            retry_policy: Arc::new(RetryPolicy::default()),
+            sql_commenter: None,
             closed: false,
         }
     }
 
+    /// 会话 id（进程内唯一），随 [`StatementContext`] 出现在 SQL 事件与日志中
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// 设置语句默认超时（由工厂按 `default_statement_timeout_ms` 设置）
+    pub(crate) fn with_default_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.default_timeout = timeout;
+        self
+    }
+
+    /// 设置 Blob 列的结果表示（由工厂按 `binary_encoding` 设置）
+    pub(crate) fn with_binary_encoding(mut self, binary_encoding: BinaryEncoding) -> Self {
+        self.executor = self.executor.with_binary_encoding(binary_encoding);
+        self.result_set_handler = self.result_set_handler.with_binary_encoding(binary_encoding);
+        self
+    }
+
+    /// 设置模型注册表（由工厂按构建器注册的 `MapperModel` 设置）
+    pub(crate) fn with_models(mut self, models: Arc<ModelRegistry>) -> Self {
+        self.result_set_handler = self.result_set_handler.with_models(models);
+        self
+    }
+
+    /// 设置慢查询执行计划捕获器（由工厂按 `[settings] slow_query_explain` 设置）
+    pub(crate) fn with_slow_query_explainer(mut self, explainer: Option<Arc<SlowQueryExplainer>>) -> Self {
+        if let Some(explainer) = explainer {
+            self.executor = self.executor.with_slow_query_explainer(explainer);
+        }
+        self
+    }
+
+    /// 设置敏感参数打码器（由工厂按 `[settings.redaction]` 与已注册模型设置）
+    pub(crate) fn with_redactor(mut self, redactor: Arc<Redactor>) -> Self {
+        self.executor = self.executor.with_redactor(redactor);
+        self
+    }
+
+    /// 设置 SQL 来源注释生成器（由工厂按 `[settings.sql_comment]` 设置）
+    pub(crate) fn with_sql_commenter(mut self, commenter: Option<Arc<SqlCommenter>>) -> Self {
+        self.sql_commenter = commenter;
+        self
+    }
+
+    /// 设置瞬时错误重试策略（由工厂按 `[settings.retry]` 设置）
+    pub(crate) fn with_retry_policy(mut self, policy: Arc<RetryPolicy>) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
     // ─── 访问器 ────────────────────────────────────────────────────
 
     /// 数据库环境引用
@@ -121,6 +394,22 @@ impl SqlSession {
         self.transaction.is_some()
     }
 
+    /// 是否处于只读事务中
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// 只读事务中拒绝写操作（在生成 SQL、访问数据库之前检查）
+    fn ensure_writable(&self, namespace: &str, statement_id: &str) -> Result<()> {
+        if self.read_only {
+            return Err(MapperRuntimeError::ReadOnlyViolation {
+                namespace: namespace.to_string(),
+                id: statement_id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
     // ─── Mapper 查找与 SQL 生成 ────────────────────────────────────
 
     /// 按 namespace 查找 Mapper（返回廉价的 `Arc<Mapper>`，不深克隆）
@@ -130,7 +419,7 @@ impl SqlSession {
             .ok_or_else(|| MapperRuntimeError::MapperNotFound(namespace.to_string()))
     }
 
-    /// 两阶段绑定：生成 BoundSql（`#{}` → `?`，`${}` → 内联）
+    /// 两阶段绑定：生成 BoundSql（`#{}` → `?`，`${}` → 内联；开启 `[settings.sql_comment]` 时附来源注释）
     pub fn build_bound_sql(
         &self,
         namespace: &str,
@@ -138,9 +427,29 @@ impl SqlSession {
         params: &HashMap<String, Value>,
     ) -> Result<BoundSql> {
         let mapper = self.get_mapper(namespace)?;
-        mapper
+        self.bind_statement(&mapper, namespace, statement_id, params)
+    }
+
+    /// 生成语句的 BoundSql；开启 `[settings.sql_comment]` 时在 SQL 末尾追加来源注释
+    fn bind_statement(
+        &self,
+        mapper: &Mapper,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<BoundSql> {
+        let mut bound = mapper
             .build_bound_sql(statement_id, params)
-            .map_err(MapperRuntimeError::from)
+            .map_err(MapperRuntimeError::from)?;
+        self.tag_sql(&mut bound, namespace, statement_id);
+        Ok(bound)
+    }
+
+    /// 追加来源注释（未开启时不变）
+    fn tag_sql(&self, bound: &mut BoundSql, namespace: &str, statement_id: &str) {
+        if let Some(commenter) = &self.sql_commenter {
+            commenter.apply(&mut bound.sql, namespace, statement_id);
+        }
     }
 
     /// 将任意 `Serialize` 参数转为 `HashMap<String, Value>`
@@ -175,13 +484,154 @@ impl SqlSession {
         }
     }
 
+    /// 查询语句声明的 `resultType`（无 ResultMap 时据此查找已注册模型）
+    fn result_type_of<'a>(mapper: &'a Mapper, statement_id: &str) -> Option<&'a str> {
+        mapper
+            .statements
+            .get(statement_id)
+            .and_then(|stmt| stmt.result_type.as_deref())
+    }
+
+    /// 语句的执行时限：XML `timeout`（秒，`0` 不限时）优先，否则取默认超时
+    fn statement_timeout(
+        &self,
+        mapper: &Mapper,
+        namespace: &str,
+        statement_id: &str,
+    ) -> Option<StatementTimeout> {
+        let duration = match mapper.statements.get(statement_id).and_then(|s| s.timeout) {
+            Some(0) => None,
+            Some(secs) => Some(Duration::from_secs(secs)),
+            None => self.default_timeout,
+        };
+        duration.map(|d| StatementTimeout::new(namespace, statement_id, d))
+    }
+
+    /// 语句上下文：语句类型取自 XML 元素，附带本会话 id 与当前事务状态
+    fn statement_context(&self, mapper: &Mapper, namespace: &str, statement_id: &str) -> StatementContext {
+        let kind = mapper
+            .statements
+            .get(statement_id)
+            .and_then(|stmt| stmt.stmt_type.as_ref())
+            .map_or(SqlKind::Other, SqlKind::from);
+        StatementContext::new(namespace, statement_id, kind).with_session(self.id, self.transaction.is_some())
+    }
+
+    /// 事务已因超时中止时拒绝继续执行
+    fn ensure_transaction_usable(&self) -> Result<()> {
+        if self.transaction_aborted {
+            return Err(MapperRuntimeError::Transaction(
+                "事务已因语句超时中止，请先 rollback".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 事务连接上的语句超时：丢弃事务（连接关闭而不归还连接池，事务由数据库回滚），并标记为已中止
+    fn abort_transaction_on_timeout<T>(&mut self, result: &Result<T>) {
+        if matches!(result, Err(MapperRuntimeError::Timeout { .. })) {
+            if let Some(tx) = self.transaction.take() {
+                tx.discard();
+            }
+            self.transaction_aborted = true;
+            std::mem::take(&mut self.transaction_span).record_end("aborted");
+            self.hooks.rolled_back(RollbackReason::Timeout);
+        }
+    }
+
     /// 内部：按事务状态选择执行目标并取回原始行
-    async fn fetch_rows(&mut self, bound: &BoundSql) -> Result<Vec<sqlx::any::AnyRow>> {
+    ///
+    /// 事务外的查询按重试策略重试瞬时错误（见 [`RetryPolicy`]）；事务内不重试。
+    async fn fetch_rows(
+        &mut self,
+        statement: &StatementContext,
+        bound: &BoundSql,
+        timeout: Option<&StatementTimeout>,
+    ) -> Result<Vec<sqlx::any::AnyRow>> {
+        self.ensure_transaction_usable()?;
+        if let Some(tx) = self.transaction.as_mut() {
+            let result = self
+                .transaction_span
+                .instrument(self.executor.query_rows_within(bound, &mut **tx, statement, timeout))
+                .await;
+            self.abort_transaction_on_timeout(&result);
+            return result;
+        }
+        let mut retrier = Retrier::new(&self.retry_policy, &self.event_bus);
+        loop {
+            match self.fetch_pooled_rows(statement, bound, timeout).await {
+                Err(e) if retrier.should_retry(&e) => {
+                    retrier
+                        .wait(&e, || RetryScope::Statement {
+                            namespace: statement.namespace.clone(),
+                            id: statement.statement_id.clone(),
+                        })
+                        .await
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// 内部：事务外执行一次查询
+    ///
+    /// 显式取出池连接执行（以便度量连接池等待），超时后该连接关闭而不归还连接池。
+    async fn fetch_pooled_rows(
+        &self,
+        statement: &StatementContext,
+        bound: &BoundSql,
+        timeout: Option<&StatementTimeout>,
+    ) -> Result<Vec<sqlx::any::AnyRow>> {
+        let mut conn = self.acquire_for(statement).await?;
+        let result = self.executor.query_rows_within(bound, &mut *conn, statement, timeout).await;
+        if matches!(result, Err(MapperRuntimeError::Timeout { .. })) {
+            conn.close_on_drop();
+        }
+        result
+    }
+
+    /// 内部：按事务状态选择执行目标并执行写语句（超时处理同 [`fetch_rows`](Self::fetch_rows)）
+    async fn execute_bound(
+        &mut self,
+        statement: &StatementContext,
+        bound: &BoundSql,
+        timeout: Option<&StatementTimeout>,
+    ) -> Result<sqlx::any::AnyQueryResult> {
+        self.ensure_transaction_usable()?;
         let executor = &self.executor;
-        match self.transaction.as_mut() {
-            Some(tx) => executor.query_rows(bound, &mut **tx).await,
-            None => executor.query_rows(bound, self.environment.pool()).await,
+        if let Some(tx) = self.transaction.as_mut() {
+            let result = self
+                .transaction_span
+                .instrument(executor.execute_within(bound, &mut **tx, statement, timeout))
+                .await;
+            self.abort_transaction_on_timeout(&result);
+            return result;
+        }
+        let mut conn = self.acquire_for(statement).await?;
+        let result = executor.execute_within(bound, &mut *conn, statement, timeout).await;
+        if matches!(result, Err(MapperRuntimeError::Timeout { .. })) {
+            conn.close_on_drop();
+        }
+        result
+    }
+
+    /// 从连接池取出一个连接
+    async fn acquire(&self) -> Result<sqlx::pool::PoolConnection<sqlx::Any>> {
+        self.environment
+            .pool()
+            .acquire()
+            .await
+            .map_err(MapperRuntimeError::Database)
+    }
+
+    /// 为 `statement` 从连接池取连接，并派发 [`PoolAcquireEvent`]（无监听器时不计时）
+    async fn acquire_for(&self, statement: &StatementContext) -> Result<sqlx::pool::PoolConnection<sqlx::Any>> {
+        let start = self.event_bus.has_listeners::<PoolAcquireEvent>().then(Instant::now);
+        let conn = self.acquire().await?;
+        if let Some(start) = start {
+            self.event_bus.dispatch(&PoolAcquireEvent { statement: statement.clone(), wait: start.elapsed() });
         }
+        Ok(conn)
     }
 
     // ─── 查询接口 ──────────────────────────────────────────────────
@@ -196,15 +646,20 @@ impl SqlSession {
         // 单次注册表查找：同时取 ResultMap（借用）与生成 SQL，避免二次加锁
         let mapper = self.get_mapper(namespace)?;
         let result_map = Self::result_map_of(&mapper, statement_id)?;
-        let bound = mapper
-            .build_bound_sql(statement_id, params)
-            .map_err(MapperRuntimeError::from)?;
-        let rows = self.fetch_rows(&bound).await?;
+        let bound = self.bind_statement(&mapper, namespace, statement_id, params)?;
+        let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+        let statement = self.statement_context(&mapper, namespace, statement_id);
+        let rows = self
+            .fetch_rows(&statement, &bound, timeout.as_ref())
+            .await?;
         match result_map {
-            Some(rm) => crate::handler::result_set::ResultSetHandler::map_row_with_result_map::<T>(rows, rm),
+            Some(rm) => self.result_set_handler.map_row_with_result_map::<T>(rows, rm),
             None => match rows.len() {
                 0 => Ok(None),
-                1 => Ok(Some(crate::handler::result_set::ResultSetHandler::map_row(&rows[0])?)),
+                1 => Ok(Some(
+                    self.result_set_handler
+                        .map_row_as(&rows[0], Self::result_type_of(&mapper, statement_id))?,
+                )),
                 n => Err(MapperRuntimeError::TooManyRows { actual: n }),
             },
         }
@@ -220,21 +675,185 @@ impl SqlSession {
         // 单次注册表查找：同时取 ResultMap（借用）与生成 SQL，避免二次加锁
         let mapper = self.get_mapper(namespace)?;
         let result_map = Self::result_map_of(&mapper, statement_id)?;
+        let bound = self.bind_statement(&mapper, namespace, statement_id, params)?;
+        let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+        let statement = self.statement_context(&mapper, namespace, statement_id);
+        let rows = self
+            .fetch_rows(&statement, &bound, timeout.as_ref())
+            .await?;
+        match result_map {
+            Some(rm) => self.result_set_handler.map_rows_with_result_map::<T>(rows, rm),
+            None => self
+                .result_set_handler
+                .map_rows_as::<T>(rows, Self::result_type_of(&mapper, statement_id)),
+        }
+    }
+
+    /// 查询单个标量或元组（期望 0 或 1 行；多于 1 行报 `TooManyRows` 错误）
+    ///
+    /// 按列位置映射：单列为标量（如 `SELECT COUNT(*)` → `i64`），多列为元组（`(i64, String)`）；
+    /// 语句的 `resultType` 为标量类型（`long` / `string` 等）时只取首列并经对应处理器解码。
+    /// 忽略语句声明的 ResultMap。
+    pub async fn select_scalar<T: DeserializeOwned + Send>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<Option<T>> {
+        let (rows, result_type) = self.fetch_positional(namespace, statement_id, params).await?;
+        match rows.len() {
+            0 => Ok(None),
+            1 => self
+                .result_set_handler
+                .map_row_positional(&rows[0], result_type.as_deref())
+                .map(Some),
+            n => Err(MapperRuntimeError::TooManyRows { actual: n }),
+        }
+    }
+
+    /// 查询多行标量或元组（映射规则见 [`select_scalar`](Self::select_scalar)）
+    pub async fn select_values<T: DeserializeOwned + Send>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<Vec<T>> {
+        let (rows, result_type) = self.fetch_positional(namespace, statement_id, params).await?;
+        rows.iter()
+            .map(|row| self.result_set_handler.map_row_positional(row, result_type.as_deref()))
+            .collect()
+    }
+
+    /// 内部：取回按位置映射所需的原始行与语句的 `resultType`
+    async fn fetch_positional(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<(Vec<sqlx::any::AnyRow>, Option<String>)> {
+        let mapper = self.get_mapper(namespace)?;
+        let bound = self.bind_statement(&mapper, namespace, statement_id, params)?;
+        let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+        let statement = self.statement_context(&mapper, namespace, statement_id);
+        let result_type = Self::result_type_of(&mapper, statement_id).map(str::to_string);
+        let rows = self
+            .fetch_rows(&statement, &bound, timeout.as_ref())
+            .await?;
+        Ok((rows, result_type))
+    }
+
+    /// 查询多行并按属性建立 `HashMap`（MyBatis `@MapKey`），重复键报错
+    ///
+    /// `key_property` 为映射后的属性名（ResultMap 的 `property`，无 ResultMap 时为列名），
+    /// 支持 `.` 访问嵌套对象。重复键的其它处理方式见 [`select_map_with`](Self::select_map_with)。
+    pub async fn select_map<K, V>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        key_property: &str,
+    ) -> Result<HashMap<K, V>>
+    where
+        K: DeserializeOwned + Eq + Hash,
+        V: DeserializeOwned,
+    {
+        self.select_map_with(namespace, statement_id, params, key_property, DuplicateKeyPolicy::Error)
+            .await
+    }
+
+    /// 同 [`select_map`](Self::select_map)，按 `policy` 处理重复键
+    pub async fn select_map_with<K, V>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        key_property: &str,
+        policy: DuplicateKeyPolicy,
+    ) -> Result<HashMap<K, V>>
+    where
+        K: DeserializeOwned + Eq + Hash,
+        V: DeserializeOwned,
+    {
+        let rows: Vec<Value> = self.select_list(namespace, statement_id, params).await?;
+        key_rows_by(rows, key_property, policy)
+    }
+
+    /// 分页查询：先查总数，再按方言追加 `LIMIT/OFFSET` 查询当前页。
+    ///
+    /// 总数默认由原 SQL 包装为 `SELECT COUNT(*) FROM (...)` 得到；若同一 Mapper 中存在
+    /// `<select id="{statement_id}_count">`，则改用该语句计数（参数相同）。总数为 0 时不再执行数据查询。
+    ///
+    /// 注意：配合含 `<collection>` 的 ResultMap 使用时，`LIMIT` 作用于 JOIN 后的原始行而非父对象。
+    pub async fn select_page<T: DeserializeOwned + Send>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        request: PageRequest,
+    ) -> Result<Page<T>> {
+        let mapper = self.get_mapper(namespace)?;
+        let result_map = Self::result_map_of(&mapper, statement_id)?;
+        // 来源注释加在计数 / 分页改写之后，保持在 SQL 末尾
         let bound = mapper
             .build_bound_sql(statement_id, params)
             .map_err(MapperRuntimeError::from)?;
-        let rows = self.fetch_rows(&bound).await?;
-        match result_map {
-            Some(rm) => crate::handler::result_set::ResultSetHandler::map_rows_with_result_map::<T>(rows, rm),
-            None => crate::handler::result_set::ResultSetHandler::map_rows::<T>(rows),
+
+        let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+        let statement = self.statement_context(&mapper, namespace, statement_id);
+
+        let count_id = format!("{}{}", statement_id, page::COUNT_STATEMENT_SUFFIX);
+        let has_count_statement = mapper.statements.contains_key(&count_id);
+        let (mut count_bound, count_timeout) = if has_count_statement {
+            let count_bound = mapper
+                .build_bound_sql(&count_id, params)
+                .map_err(MapperRuntimeError::from)?;
+            (count_bound, self.statement_timeout(&mapper, namespace, &count_id))
+        } else {
+            (page::count_bound_sql(&bound), timeout.clone())
+        };
+        let count_statement_id = if has_count_statement { count_id.as_str() } else { statement_id };
+        self.tag_sql(&mut count_bound, namespace, count_statement_id);
+        let count_statement = self.statement_context(&mapper, namespace, count_statement_id);
+        let count_rows = self
+            .fetch_rows(&count_statement, &count_bound, count_timeout.as_ref())
+            .await?;
+        let total = Self::first_column_count(&count_rows)?;
+        if total == 0 {
+            return Ok(Page::new(request, Vec::new(), 0));
         }
+
+        let mut page_bound = page::limit_bound_sql(bound, self.environment.dialect(), request);
+        self.tag_sql(&mut page_bound, namespace, statement_id);
+        let rows = self
+            .fetch_rows(&statement, &page_bound, timeout.as_ref())
+            .await?;
+        let items = match result_map {
+            Some(rm) => self.result_set_handler.map_rows_with_result_map::<T>(rows, rm)?,
+            None => self
+                .result_set_handler
+                .map_rows_as::<T>(rows, Self::result_type_of(&mapper, statement_id))?,
+        };
+        Ok(Page::new(request, items, total))
+    }
+
+    /// 读取计数查询结果（首行首列；无行视为 0）
+    fn first_column_count(rows: &[sqlx::any::AnyRow]) -> Result<u64> {
+        use sqlx::Row;
+        let Some(row) = rows.first() else {
+            return Ok(0);
+        };
+        let count: i64 = row
+            .try_get(0)
+            .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取总行数失败: {}", e)))?;
+        Ok(count.max(0) as u64)
     }
 
     /// 流式查询：逐行拉取并经回调处理，避免 [`select_list`](Self::select_list) 一次性物化整表，
     /// 适合大结果集的低内存峰值场景。
     ///
     /// 仅支持普通列映射（`AnyRow → T`），**不支持 ResultMap 嵌套分组**
-    ///（分组需聚集全部行，与流式语义冲突）。回调返回 `Err` 可提前终止并向上传递。
+    ///（按父 id 有序的 join 结果改用 [`select_stream_grouped`](Self::select_stream_grouped)）。
+    /// 回调返回 `Err` 可提前终止并向上传递。
     pub async fn select_for_each<T, F>(
         &mut self,
         namespace: &str,
@@ -246,17 +865,127 @@ impl SqlSession {
         T: DeserializeOwned + Send,
         F: FnMut(&T) -> Result<()>,
     {
-        let bound = self.build_bound_sql(namespace, statement_id, params)?;
+        let mut stream = self.select_stream::<T>(namespace, statement_id, params);
+        while let Some(item) = stream.next().await {
+            f(&item?)?;
+        }
+        Ok(())
+    }
+
+    /// [`select_for_each`](Self::select_for_each) 的异步回调版本：逐行等待回调完成后再拉取下一行，
+    /// 可把每行写入其它异步目标（HTTP、另一数据库）而无需缓冲整个结果集。
+    pub async fn select_for_each_async<T, F, Fut>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        mut f: F,
+    ) -> Result<()>
+    where
+        T: DeserializeOwned + Send,
+        F: FnMut(T) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let mut stream = self.select_stream::<T>(namespace, statement_id, params);
+        while let Some(item) = stream.next().await {
+            f(item?).await?;
+        }
+        Ok(())
+    }
+
+    /// 流式查询，直接返回逐行映射的 `Stream`
+    ///
+    /// 流持有自身的 [`BoundSql`] 并借用会话：事务中执行于事务连接，否则于连接池。
+    /// 创建时派发 [`BeforeSqlEvent`]，流结束（耗尽、出错或被提前丢弃）时记录 SQL 日志并派发
+    /// [`AfterSqlEvent`]。查找 Mapper 或生成 SQL 失败时，流产出该错误后结束。
+    /// 映射规则同 [`select_for_each`](Self::select_for_each)。
+    pub fn select_stream<'s, T>(
+        &'s mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> impl Stream<Item = Result<T>> + Send + 's
+    where
+        T: DeserializeOwned + Send + 's,
+    {
+        let prepared = self.ensure_transaction_usable().and_then(|()| {
+            let mapper = self.get_mapper(namespace)?;
+            let bound = self.bind_statement(&mapper, namespace, statement_id, params)?;
+            let statement = self.statement_context(&mapper, namespace, statement_id);
+            Ok((bound, statement, Self::result_type_of(&mapper, statement_id).map(str::to_string)))
+        });
+        let Self { executor, environment, transaction, transaction_span, result_set_handler, .. } = self;
+        let (rows, result_type) = match prepared {
+            Ok((bound, statement, result_type)) => {
+                let rows = match transaction.as_mut() {
+                    Some(tx) => transaction_span.in_scope(|| executor.query_rows_stream_owned(bound, &mut **tx, statement)),
+                    None => executor.query_rows_stream_owned(bound, environment.pool(), statement),
+                };
+                (rows, result_type)
+            }
+            Err(e) => {
+                let rows: Pin<Box<dyn Stream<Item = Result<sqlx::any::AnyRow>> + Send>> =
+                    Box::pin(futures_util::stream::once(async move { Err(e) }));
+                (rows, None)
+            }
+        };
+        rows.map(move |row| row.and_then(|row| result_set_handler.map_row_as::<T>(&row, result_type.as_deref())))
+    }
+
+    /// 流式 ResultMap 分组查询：按父对象 `<id>` 的变化逐个产出完整父对象（含 association / collection）
+    ///
+    /// 要求结果按父 id 排序（如 `ORDER BY u.id, o.id`）；内存只保留当前父对象（乱序检测见 [`ResultMapGrouper`](crate::ResultMapGrouper)）。
+    /// 乱序按 `policy` 处理：[`OutOfOrderPolicy::Error`] 返回
+    /// [`MapperRuntimeError::UnorderedGroup`]（此前的父对象已交给回调）；
+    /// [`OutOfOrderPolicy::Buffer`] 不做流式分组，缓冲全部行后整体分组再逐个回调。
+    /// 语句未声明 ResultMap 时等同 [`select_for_each`](Self::select_for_each)。
+    pub async fn select_stream_grouped<T, F>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        policy: OutOfOrderPolicy,
+        mut f: F,
+    ) -> Result<()>
+    where
+        T: DeserializeOwned + Send,
+        F: FnMut(&T) -> Result<()>,
+    {
+        let mapper = self.get_mapper(namespace)?;
+        let Some(result_map) = Self::result_map_of(&mapper, statement_id)? else {
+            return self.select_for_each(namespace, statement_id, params, f).await;
+        };
+        let bound = self.bind_statement(&mapper, namespace, statement_id, params)?;
+
+        if policy == OutOfOrderPolicy::Buffer {
+            let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+            let statement = self.statement_context(&mapper, namespace, statement_id);
+            let rows = self
+                .fetch_rows(&statement, &bound, timeout.as_ref())
+                .await?;
+            for item in self.result_set_handler.map_rows_with_result_map::<T>(rows, result_map)? {
+                f(&item)?;
+            }
+            return Ok(());
+        }
+
+        self.ensure_transaction_usable()?;
+        let statement = self.statement_context(&mapper, namespace, statement_id);
         let executor = &self.executor;
-        // 流借用 bound（局部）与执行器；bound 在本函数内活过整个循环，借用有效。
+        let mut grouper = self.result_set_handler.grouper::<T>(result_map);
         let mut stream = match self.transaction.as_mut() {
-            Some(tx) => executor.query_rows_stream(&bound, &mut **tx),
-            None => executor.query_rows_stream(&bound, self.environment.pool()),
+            Some(tx) => self
+                .transaction_span
+                .in_scope(|| executor.query_rows_stream_with(&bound, &mut **tx, statement)),
+            None => executor.query_rows_stream_with(&bound, self.environment.pool(), statement),
         };
         while let Some(row_res) = stream.next().await {
-            let row = row_res?;
-            let item = crate::handler::result_set::ResultSetHandler::map_row::<T>(&row)?;
-            f(&item)?;
+            if let Some(parent) = grouper.push(&row_res?)? {
+                f(&parent)?;
+            }
+        }
+        if let Some(parent) = grouper.finish()? {
+            f(&parent)?;
         }
         Ok(())
     }
@@ -275,62 +1004,64 @@ impl SqlSession {
         statement_id: &str,
         params: &T,
     ) -> Result<Option<i64>> {
+        self.ensure_writable(namespace, statement_id)?;
+        self.ensure_transaction_usable()?;
         let params = Self::params_to_map(params)?;
-        let bound = self.build_bound_sql(namespace, statement_id, &params)?;
-        let args = crate::handler::parameter::ParameterHandler::bind_arguments(&bound)?;
+        let mapper = self.get_mapper(namespace)?;
+        let bound = self.bind_statement(&mapper, namespace, statement_id, &params)?;
+        let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+        let statement = self.statement_context(&mapper, namespace, statement_id);
+        let args = crate::handler::parameter::ParameterHandler::bind_arguments_with(
+            &bound,
+            &self.type_handler_registry,
+        )?;
         let driver = self.environment.driver();
 
-        self.event_bus.dispatch_if(|| BeforeSqlEvent {
-            raw_sql: bound.sql.clone(),
-            params: bound.parameters.clone(),
-            kind: SqlKind::Insert,
-        });
-        // 仅当 SQL 日志开启 或 有 AfterSqlEvent 监听器时才计时（全关时零开销）
-        let need_timing = self.sql_log.enabled || self.event_bus.has_listeners::<AfterSqlEvent>();
-        let start = need_timing.then(Instant::now);
+        let observer = self.executor.observer();
+        let observation = self.transaction_span.in_scope(|| observer.begin(&statement, &bound));
         if let Some(tx) = self.transaction.as_mut() {
-            let conn: &mut sqlx::AnyConnection = tx; // deref coercion: &mut Transaction → &mut AnyConnection
-            let exec_result = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args)
-                .execute(&mut *conn)
+            let conn: &mut sqlx::AnyConnection = tx; // deref coercion: &mut SessionTransaction → &mut AnyConnection
+            let exec = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args).execute(&mut *conn);
+            let exec_result = observation
+                .span
+                .instrument(with_timeout(timeout.as_ref(), async {
+                    exec.await.map_err(MapperRuntimeError::Database)
+                }))
                 .await;
-            let elapsed = start.map(|s| s.elapsed()).unwrap_or_default();
-            crate::sql_log::log_execution(&self.sql_log, &bound, elapsed);
-            self.event_bus.dispatch_if(|| AfterSqlEvent {
-                raw_sql: bound.sql.clone(),
-                params: bound.parameters.clone(),
-                kind: SqlKind::Insert,
-                elapsed,
-                outcome: match &exec_result {
-                    Ok(r) => SqlOutcome::Affected(r.rows_affected()),
-                    Err(e) => SqlOutcome::Failed(e.to_string()),
-                },
-            });
-            exec_result.map_err(MapperRuntimeError::Database)?;
-            Ok(Self::fetch_last_insert_id(conn, driver).await?)
+            let outcome = || match &exec_result {
+                Ok(r) => SqlOutcome::Affected(r.rows_affected()),
+                Err(e) => SqlOutcome::Failed(e.to_string()),
+            };
+            observer.finish(&observation, &statement, &bound, outcome).await;
+            let result = match exec_result {
+                Ok(_) => Self::fetch_last_insert_id(conn, driver).await,
+                Err(e) => Err(e),
+            };
+            self.abort_transaction_on_timeout(&result);
+            result
         } else {
-            let mut conn = self
-                .environment
-                .pool()
-                .acquire()
-                .await
-                .map_err(MapperRuntimeError::Database)?;
-            let exec_result = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args)
-                .execute(&mut *conn)
+            let mut conn = self.acquire_for(&statement).await?;
+            let exec = sqlx::query_with(sqlx::AssertSqlSafe(&*bound.sql), args).execute(&mut *conn);
+            let exec_result = observation
+                .span
+                .instrument(with_timeout(timeout.as_ref(), async {
+                    exec.await.map_err(MapperRuntimeError::Database)
+                }))
                 .await;
-            let elapsed = start.map(|s| s.elapsed()).unwrap_or_default();
-            crate::sql_log::log_execution(&self.sql_log, &bound, elapsed);
-            self.event_bus.dispatch_if(|| AfterSqlEvent {
-                raw_sql: bound.sql.clone(),
-                params: bound.parameters.clone(),
-                kind: SqlKind::Insert,
-                elapsed,
-                outcome: match &exec_result {
-                    Ok(r) => SqlOutcome::Affected(r.rows_affected()),
-                    Err(e) => SqlOutcome::Failed(e.to_string()),
-                },
-            });
-            exec_result.map_err(MapperRuntimeError::Database)?;
-            Ok(Self::fetch_last_insert_id(&mut conn, driver).await?)
+            let outcome = || match &exec_result {
+                Ok(r) => SqlOutcome::Affected(r.rows_affected()),
+                Err(e) => SqlOutcome::Failed(e.to_string()),
+            };
+            observer.finish(&observation, &statement, &bound, outcome).await;
+            match exec_result {
+                Ok(_) => Self::fetch_last_insert_id(&mut conn, driver).await,
+                Err(e) => {
+                    if matches!(e, MapperRuntimeError::Timeout { .. }) {
+                        conn.close_on_drop();
+                    }
+                    Err(e)
+                }
+            }
         }
     }
 
@@ -369,13 +1100,13 @@ impl SqlSession {
         statement_id: &str,
         params: &T,
     ) -> Result<u64> {
+        self.ensure_writable(namespace, statement_id)?;
         let params = Self::params_to_map(params)?;
-        let bound = self.build_bound_sql(namespace, statement_id, &params)?;
-        let executor = &self.executor;
-        let result = match self.transaction.as_mut() {
-            Some(tx) => executor.execute(&bound, &mut **tx).await,
-            None => executor.execute(&bound, self.environment.pool()).await,
-        }?;
+        let mapper = self.get_mapper(namespace)?;
+        let bound = self.bind_statement(&mapper, namespace, statement_id, &params)?;
+        let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+        let statement = self.statement_context(&mapper, namespace, statement_id);
+        let result = self.execute_bound(&statement, &bound, timeout.as_ref()).await?;
         Ok(result.rows_affected())
     }
 
@@ -386,54 +1117,212 @@ impl SqlSession {
         statement_id: &str,
         params: &T,
     ) -> Result<u64> {
+        self.ensure_writable(namespace, statement_id)?;
         let params = Self::params_to_map(params)?;
-        let bound = self.build_bound_sql(namespace, statement_id, &params)?;
-        let executor = &self.executor;
-        let result = match self.transaction.as_mut() {
-            Some(tx) => executor.execute(&bound, &mut **tx).await,
-            None => executor.execute(&bound, self.environment.pool()).await,
-        }?;
+        let mapper = self.get_mapper(namespace)?;
+        let bound = self.bind_statement(&mapper, namespace, statement_id, &params)?;
+        let timeout = self.statement_timeout(&mapper, namespace, statement_id);
+        let statement = self.statement_context(&mapper, namespace, statement_id);
+        let result = self.execute_bound(&statement, &bound, timeout.as_ref()).await?;
         Ok(result.rows_affected())
     }
 
+    // ─── 执行计划 ──────────────────────────────────────────────────
+
+    /// 取语句的执行计划：按方言执行 `EXPLAIN` / `EXPLAIN QUERY PLAN`（绑定与实际执行相同的参数）
+    ///
+    /// 事务内在事务连接上执行（可见未提交的结构变更），否则从连接池取连接。不派发 SQL 事件、
+    /// 不记录 SQL 日志，也不受慢查询捕获的限流约束。`EXPLAIN`（不带 `ANALYZE`）不会真正执行语句，
+    /// 写语句同样适用。
+    pub async fn explain<T: Serialize>(
+        &mut self,
+        namespace: &str,
+        statement_id: &str,
+        params: &T,
+    ) -> Result<QueryPlan> {
+        self.ensure_transaction_usable()?;
+        let params = Self::params_to_map(params)?;
+        let bound = self.build_bound_sql(namespace, statement_id, &params)?;
+        let dialect = self.environment.dialect();
+        let registry = &self.type_handler_registry;
+        match self.transaction.as_mut() {
+            Some(tx) => explain_bound(dialect, &bound, &mut **tx, registry).await,
+            None => {
+                let mut conn = self.acquire().await?;
+                explain_bound(dialect, &bound, &mut *conn, registry).await
+            }
+        }
+    }
+
     // ─── 事务管理 ──────────────────────────────────────────────────
 
     /// 开启事务
     pub async fn begin(&mut self) -> Result<()> {
+        self.begin_with(TransactionOptions::default()).await
+    }
+
+    /// 按选项开启事务（隔离级别 / 只读 / 可延迟），语句按方言生成，
+    /// 见 [`Dialect::begin_statements`](crate::Dialect::begin_statements)。
+    pub async fn begin_with(&mut self, options: TransactionOptions) -> Result<()> {
         if self.transaction.is_some() {
             return Err(MapperRuntimeError::Transaction(
                 "事务已开启，请先 commit 或 rollback".to_string(),
             ));
         }
-        let tx = self
-            .environment
-            .pool()
-            .begin()
+        self.ensure_transaction_usable()?;
+        let span = Span::transaction(self.id, &options);
+        let statements = self.environment.dialect().begin_statements(&options);
+        let mut conn = self.acquire().await?;
+        if let Some(sql) = &statements.before {
+            sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| MapperRuntimeError::Transaction(format!("设置事务选项失败: {}", e)))?;
+        }
+        // 以已取得的池连接开启事务（mysql 的 SET TRANSACTION 须与随后的 BEGIN 在同一连接上）
+        let mut tx = SessionTransaction::begin(conn, &statements.begin)
             .await
             .map_err(MapperRuntimeError::Database)?;
+        if let Some(sql) = &statements.after {
+            sqlx::query(sqlx::AssertSqlSafe(sql.as_str()))
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| MapperRuntimeError::Transaction(format!("设置事务选项失败: {}", e)))?;
+        }
         self.transaction = Some(tx);
+        self.transaction_span = span;
+        self.read_only = options.read_only;
+        self.hooks.begun(options);
         Ok(())
     }
 
     /// 提交事务并消费 session
     pub async fn commit(mut self) -> Result<()> {
-        if let Some(tx) = self.transaction.take() {
-            tx.commit().await.map_err(|e| {
-                MapperRuntimeError::Transaction(format!("提交失败: {}", e))
-            })?;
-        }
+        self.finish_transaction(true).await?;
         self.closed = true;
         Ok(())
     }
 
     /// 回滚事务并消费 session
     pub async fn rollback(mut self) -> Result<()> {
-        if let Some(tx) = self.transaction.take() {
-            tx.rollback().await.map_err(|e| {
+        self.finish_transaction(false).await?;
+        self.closed = true;
+        Ok(())
+    }
+
+    /// 内部：提交或回滚当前事务（无事务时为空操作），session 保持可用
+    pub(crate) async fn finish_transaction(&mut self, commit: bool) -> Result<()> {
+        self.end_transaction(commit, RollbackReason::Requested).await
+    }
+
+    /// 内部：提交或按 `reason` 回滚当前事务，派发事务事件并执行对应回调
+    async fn end_transaction(&mut self, commit: bool, reason: RollbackReason) -> Result<()> {
+        let aborted = std::mem::take(&mut self.transaction_aborted);
+        let Some(tx) = self.transaction.take() else {
+            if aborted && commit {
+                return Err(MapperRuntimeError::Transaction(
+                    "事务已因语句超时中止并回滚，无法提交".to_string(),
+                ));
+            }
+            return Ok(());
+        };
+        self.savepoint_seq = 0;
+        self.read_only = false;
+        let span = std::mem::take(&mut self.transaction_span);
+        span.record_end(if commit { "commit" } else { "rollback" });
+        if commit {
+            match span.instrument(tx.commit()).await {
+                Ok(()) => {
+                    self.hooks.committed();
+                    Ok(())
+                }
+                Err(e) => {
+                    if commit_rejected(&e) {
+                        self.hooks.rolled_back(RollbackReason::CommitFailed(e.to_string()));
+                    } else {
+                        self.hooks.outcome_unknown(e.to_string());
+                    }
+                    // 保留数据库错误，提交时的锁冲突 / 序列化失败同样参与事务重试
+                    Err(MapperRuntimeError::Database(e))
+                }
+            }
+        } else {
+            // 回滚语句失败时数据库同样不会提交该事务，事件与回调照常触发
+            let result = span.instrument(tx.rollback()).await.map_err(|e| {
                 MapperRuntimeError::Transaction(format!("回滚失败: {}", e))
-            })?;
+            });
+            self.hooks.rolled_back(reason);
+            result
         }
-        self.closed = true;
+    }
+
+    /// 登记事务提交成功后执行的回调（如发布消息、失效缓存），按登记顺序执行
+    ///
+    /// - 事务内：提交成功后执行；回滚（含数据库拒绝提交）或提交结果未知时丢弃；回滚到保存点时丢弃该保存点之后登记的回调。
+    /// - 无事务（自动提交）：立即执行；事务已因超时中止时丢弃。
+    ///
+    /// 回调为同步闭包，耗时或异步工作请在其中 `tokio::spawn`；回调 panic 被捕获并告警，不影响其余回调。
+    pub fn after_commit<F>(&mut self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.transaction.is_some() {
+            self.hooks.after_commit.push(Box::new(hook));
+        } else if !self.transaction_aborted {
+            run_hooks(vec![Box::new(hook)], "after_commit");
+        }
+    }
+
+    /// 登记事务回滚后执行的回调（显式回滚、`close`、数据库拒绝提交、语句超时中止或 session 被 drop），按登记顺序执行
+    ///
+    /// 无事务时丢弃；提交成功或提交结果未知（如提交时连接中断，见 [`TransactionOutcomeUnknownEvent`]）时丢弃。
+    pub fn after_rollback<F>(&mut self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        if self.transaction.is_some() {
+            self.hooks.after_rollback.push(Box::new(hook));
+        }
+    }
+
+    /// 在当前事务内创建保存点，返回保存点名（须已 `begin`）
+    pub async fn savepoint(&mut self) -> Result<String> {
+        self.savepoint_seq += 1;
+        let name = format!("hirust_sp_{}", self.savepoint_seq);
+        self.execute_on_transaction(&format!("SAVEPOINT {}", name), "创建保存点")
+            .await?;
+        self.hooks.savepoint_created(&name);
+        Ok(name)
+    }
+
+    /// 释放保存点（保留其后的修改）
+    pub async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        self.execute_on_transaction(&format!("RELEASE SAVEPOINT {}", name), "释放保存点")
+            .await?;
+        self.hooks.savepoint_released(name);
+        Ok(())
+    }
+
+    /// 回滚到保存点（撤销其后的修改，事务继续）；其后登记的 `after_commit` 回调一并丢弃
+    pub async fn rollback_to_savepoint(&mut self, name: &str) -> Result<()> {
+        self.execute_on_transaction(&format!("ROLLBACK TO SAVEPOINT {}", name), "回滚到保存点")
+            .await?;
+        self.hooks.savepoint_rolled_back(name);
+        Ok(())
+    }
+
+    /// 内部：在事务连接上执行一条无参控制语句
+    async fn execute_on_transaction(&mut self, sql: &str, action: &str) -> Result<()> {
+        let Some(tx) = self.transaction.as_mut() else {
+            return Err(MapperRuntimeError::Transaction(format!(
+                "未开启事务，无法{}",
+                action
+            )));
+        };
+        sqlx::query(sqlx::AssertSqlSafe(sql))
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| MapperRuntimeError::Transaction(format!("{}失败: {}", action, e)))?;
         Ok(())
     }
 
@@ -441,9 +1330,7 @@ impl SqlSession {
 
     /// 关闭 session（未提交的事务将回滚）
     pub async fn close(&mut self) -> Result<()> {
-        if let Some(tx) = self.transaction.take() {
-            let _ = tx.rollback().await; // 关闭时回滚未提交事务
-        }
+        let _ = self.end_transaction(false, RollbackReason::Closed).await; // 关闭时回滚未提交事务
         self.closed = true;
         Ok(())
     }
@@ -463,6 +1350,15 @@ impl SqlSession {
     }
 }
 
+/// 依次执行事务回调；单个回调 panic 时告警并继续
+fn run_hooks(hooks: Vec<TransactionHook>, kind: &str) {
+    for hook in hooks {
+        if std::panic::catch_unwind(std::panic::AssertUnwindSafe(hook)).is_err() {
+            crate::telemetry::warn(&format!("事务 {} 回调 panic", kind));
+        }
+    }
+}
+
 // ─── MapperProxy：命名空间代理 ─────────────────────────────────────
 
 /// 命名空间代理：绑定到特定 namespace
@@ -490,6 +1386,69 @@ impl<'s> MapperProxy<'s> {
         self.session.select_list(&self.namespace, statement_id, params).await
     }
 
+    /// 查询单个标量或元组
+    pub async fn select_scalar<T: DeserializeOwned + Send>(
+        &mut self,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<Option<T>> {
+        self.session.select_scalar(&self.namespace, statement_id, params).await
+    }
+
+    /// 查询多行标量或元组
+    pub async fn select_values<T: DeserializeOwned + Send>(
+        &mut self,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<Vec<T>> {
+        self.session.select_values(&self.namespace, statement_id, params).await
+    }
+
+    /// 查询多行并按属性建立 `HashMap`（重复键报错）
+    pub async fn select_map<K, V>(
+        &mut self,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        key_property: &str,
+    ) -> Result<HashMap<K, V>>
+    where
+        K: DeserializeOwned + Eq + Hash,
+        V: DeserializeOwned,
+    {
+        self.session
+            .select_map(&self.namespace, statement_id, params, key_property)
+            .await
+    }
+
+    /// 查询多行并按属性建立 `HashMap`，按 `policy` 处理重复键
+    pub async fn select_map_with<K, V>(
+        &mut self,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        key_property: &str,
+        policy: DuplicateKeyPolicy,
+    ) -> Result<HashMap<K, V>>
+    where
+        K: DeserializeOwned + Eq + Hash,
+        V: DeserializeOwned,
+    {
+        self.session
+            .select_map_with(&self.namespace, statement_id, params, key_property, policy)
+            .await
+    }
+
+    /// 分页查询
+    pub async fn select_page<T: DeserializeOwned + Send>(
+        &mut self,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        request: PageRequest,
+    ) -> Result<Page<T>> {
+        self.session
+            .select_page(&self.namespace, statement_id, params, request)
+            .await
+    }
+
     /// 插入
     pub async fn insert<T: Serialize>(&mut self, statement_id: &str, params: &T) -> Result<Option<i64>> {
         self.session.insert(&self.namespace, statement_id, params).await
@@ -505,8 +1464,59 @@ impl<'s> MapperProxy<'s> {
         self.session.delete(&self.namespace, statement_id, params).await
     }
 
+    /// 执行计划
+    pub async fn explain<T: Serialize>(&mut self, statement_id: &str, params: &T) -> Result<QueryPlan> {
+        self.session.explain(&self.namespace, statement_id, params).await
+    }
+
     /// namespace
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_commit_rejected_only_for_database_errors() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new().max_connections(1).connect("sqlite::memory:").await.unwrap();
+        let db_err = sqlx::query("SELECT * FROM missing").execute(&pool).await.unwrap_err();
+        assert!(commit_rejected(&db_err));
+        let io_err = sqlx::Error::Io(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset"));
+        assert!(!commit_rejected(&io_err));
+        assert!(!commit_rejected(&sqlx::Error::Protocol("unexpected eof".into())));
+    }
+
+    #[test]
+    fn test_outcome_unknown_skips_hooks() {
+        let bus = Arc::new(EventBus::new());
+        let unknown = Arc::new(Mutex::new(Vec::new()));
+        let rollbacks = Arc::new(AtomicU64::new(0));
+        let sink = Arc::clone(&unknown);
+        bus.on(move |e: &TransactionOutcomeUnknownEvent| sink.lock().unwrap().push(e.error.clone())).detach();
+        let counter = Arc::clone(&rollbacks);
+        bus.on(move |_: &TransactionRollbackEvent| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .detach();
+
+        let ran = Arc::new(AtomicBool::new(false));
+        let mut hooks = TransactionHooks::new(1, Arc::clone(&bus));
+        hooks.begun(TransactionOptions::default());
+        let flag = Arc::clone(&ran);
+        hooks.after_commit.push(Box::new(move || flag.store(true, Ordering::SeqCst)));
+        let flag = Arc::clone(&ran);
+        hooks.after_rollback.push(Box::new(move || flag.store(true, Ordering::SeqCst)));
+        hooks.outcome_unknown("connection reset".into());
+        drop(hooks);
+
+        assert_eq!(*unknown.lock().unwrap(), vec!["connection reset".to_string()]);
+        assert_eq!(rollbacks.load(Ordering::SeqCst), 0, "结果未知时不派发回滚事件（drop 也不补发）");
+        assert!(!ran.load(Ordering::SeqCst), "结果未知时不执行任何回调");
+    }
+}
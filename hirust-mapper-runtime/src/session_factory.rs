@@ -2,22 +2,36 @@
 //!
 //! `SqlSessionFactory` 是应用级的长生命周期对象（对应 MyBatis 的 `SqlSessionFactory`），
 //! 持有 Mapper 注册表、数据库环境、类型别名与类型处理器注册表。
-//! 通过 `build()` 从配置构建，通过 `open_session()` 创建请求级的 [`SqlSession`]。
+//! 通过 `build()` 从配置构建，通过 `open_session()` 创建请求级的 [`SqlSession`]；
+//! 需要注册自定义类型处理器时改用 [`SqlSessionFactory::builder`]。
 
+use std::collections::HashMap;
+use std::future::Future;
 use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::config::HirustMapperConfig;
+use crate::config::{HirustMapperConfig, TypeHandlerEntry};
 use crate::environment::Environment;
-use crate::error::Result;
+use crate::error::{MapperRuntimeError, Result};
 use crate::event::EventBus;
 use crate::hot_reload::{extract_watch_dirs, MapperWatcher};
-use crate::registry::{MapperRegistry, TypeAliasRegistry};
+use crate::explain::SlowQueryExplainer;
+use crate::metrics::{MetricsRegistry, MetricsSnapshot};
+use crate::redact::Redactor;
+use crate::registry::{MapperModel, MapperRegistry, ModelRegistry, TypeAliasRegistry};
+use crate::retry::RetryPolicy;
+use crate::sql_comment::{SqlCommenter, TraceContext, TraceContextProvider};
 use crate::sql_log::SqlLogConfig;
-use crate::type_handler::TypeHandlerRegistry;
+use crate::transaction::{self, Propagation, ScopedSession, TxSession};
+use crate::type_handler::{BytesHandler, TypeHandler, TypeHandlerRegistry};
 
 pub use crate::session::{MapperProxy, SqlSession};
 
+/// 工厂实例编号（区分不同工厂的环境事务）
+static NEXT_FACTORY_ID: AtomicU64 = AtomicU64::new(1);
+
 /// SqlSession 工厂（应用级，线程安全）
 ///
 /// 生命周期与整个应用相同。持有一个连接池、一个线程安全的 Mapper 注册表，
@@ -31,13 +45,27 @@ pub use crate::session::{MapperProxy, SqlSession};
 /// let mut session = factory.open_session();
 /// ```
 pub struct SqlSessionFactory {
+    /// 进程内唯一编号
+    id: u64,
     environment: Environment,
     /// Mapper 注册表（内部自带 RwLock，无需外层再包一层锁）
     mapper_registry: Arc<MapperRegistry>,
     type_alias_registry: Arc<TypeAliasRegistry>,
     type_handler_registry: Arc<TypeHandlerRegistry>,
+    /// 经构建器注册的模型列注解
+    model_registry: Arc<ModelRegistry>,
     sql_log: Arc<SqlLogConfig>,
     event_bus: Arc<EventBus>,
+    /// 语句级指标（`[settings] metrics` 开启时已订阅事件总线）
+    metrics: Option<MetricsRegistry>,
+    /// 慢查询执行计划捕获器（`[settings] slow_query_explain` 开启且设置了慢查询阈值时创建）
+    slow_query_explainer: Option<Arc<SlowQueryExplainer>>,
+    /// SQL 日志与事件的敏感参数打码器（配置规则 + 已注册模型的 redact 字段）
+    redactor: Arc<Redactor>,
+    /// SQL 来源注释生成器（`[settings.sql_comment]` 开启时创建）
+    sql_commenter: Option<Arc<SqlCommenter>>,
+    /// 瞬时错误重试策略（所有 session 共享）
+    retry_policy: Arc<RetryPolicy>,
     config: HirustMapperConfig,
     base_dir: std::path::PathBuf,
     /// 热重载监视器（None 表示未启用热重载）
@@ -55,6 +83,152 @@ impl std::fmt::Debug for SqlSessionFactory {
     }
 }
 
+/// [`SqlSessionFactory`] 构建器：在构建前按名称注册自定义类型处理器与模型
+///
+/// 注册的处理器可在 XML 中经 `typeHandler="Money"` / `#{amount, typeHandler=Money}` 引用；
+/// 配置中的 `[[type_handlers]]` 按 `handler` 名称绑定到已注册的处理器，
+/// 并以 `type` 注册，使 `rustType` 声明为该类型的列与参数使用它。
+/// 注册的 [`MapperModel`] 作用于 `resultType` 为该类型的查询。
+///
+/// ```ignore
+/// let factory = SqlSessionFactory::builder(config, ".")
+///     .type_handler("MoneyHandler", MoneyHandler)
+///     .model::<User>()
+///     .build()
+///     .await?;
+/// ```
+pub struct SqlSessionFactoryBuilder {
+    config: HirustMapperConfig,
+    base_dir: std::path::PathBuf,
+    /// 按名称注册的自定义处理器（保持注册顺序）
+    type_handlers: Vec<(String, Arc<dyn TypeHandler>)>,
+    models: ModelRegistry,
+    trace_context: Option<TraceContextProvider>,
+}
+
+impl std::fmt::Debug for SqlSessionFactoryBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlSessionFactoryBuilder")
+            .field("base_dir", &self.base_dir)
+            .field("type_handlers", &self.type_handlers.iter().map(|(n, _)| n).collect::<Vec<_>>())
+            .field("models", &self.models.len())
+            .field("trace_context", &self.trace_context.is_some())
+            .finish()
+    }
+}
+
+impl SqlSessionFactoryBuilder {
+    /// 从配置与基准目录创建构建器
+    pub fn new<P: AsRef<Path>>(config: HirustMapperConfig, base_dir: P) -> Self {
+        Self {
+            config,
+            base_dir: base_dir.as_ref().to_path_buf(),
+            type_handlers: Vec::new(),
+            models: ModelRegistry::new(),
+            trace_context: None,
+        }
+    }
+
+    /// 按名称注册自定义类型处理器（同名后注册者覆盖先注册者）
+    pub fn type_handler(mut self, name: impl Into<String>, handler: impl TypeHandler) -> Self {
+        self.type_handlers.push((name.into(), Arc::new(handler)));
+        self
+    }
+
+    /// 注册模型：`resultType` 为 `M` 的查询按其 `#[mapper(column, type_handler)]` 注解映射
+    pub fn model<M: MapperModel>(mut self) -> Self {
+        self.models.register::<M>();
+        self
+    }
+
+    /// 设置追踪上下文提供者：开启 `[settings.sql_comment]` 时，其返回的 `traceparent` / `tracestate`
+    /// 写入 SQL 来源注释（在生成 SQL 的调用方上下文中求值，如读取当前 span 的 OpenTelemetry 上下文）
+    pub fn trace_context<F>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> Option<TraceContext> + Send + Sync + 'static,
+    {
+        self.trace_context = Some(Arc::new(provider));
+        self
+    }
+
+    /// 构建工厂；`[[type_handlers]]` 引用了未注册的处理器时返回 [`MapperRuntimeError::Config`]
+    pub async fn build(self) -> Result<SqlSessionFactory> {
+        let registry = self.type_handler_registry()?;
+        let mut factory = SqlSessionFactory::build_with(self.config, self.base_dir, registry, self.models).await?;
+        if let (Some(commenter), Some(provider)) = (factory.sql_commenter.take(), self.trace_context) {
+            factory.sql_commenter = Some(Arc::new(Arc::unwrap_or_clone(commenter).with_trace_context(provider)));
+        }
+        Ok(factory)
+    }
+
+    /// 内置处理器（bytes 按 `binary_encoding`）+ 按名称注册的处理器 + `[[type_handlers]]` 的类型绑定
+    fn type_handler_registry(&self) -> Result<TypeHandlerRegistry> {
+        let mut registry = TypeHandlerRegistry::with_defaults();
+        registry.register(Arc::new(BytesHandler::new(self.config.settings.binary_encoding)));
+        let named: HashMap<&str, &Arc<dyn TypeHandler>> =
+            self.type_handlers.iter().map(|(n, h)| (n.as_str(), h)).collect();
+        for (name, handler) in &self.type_handlers {
+            registry.register_as(name.clone(), Arc::clone(handler));
+        }
+        for entry in &self.config.type_handlers {
+            let handler = lookup_named(&named, &entry.handler_path).ok_or_else(|| unknown_handler(entry))?;
+            registry.register_as(entry.type_path.clone(), Arc::clone(handler));
+            // 同时以类型短名注册，XML 可写 rustType="Money" 而非全限定路径
+            let short = last_segment(&entry.type_path);
+            if short != entry.type_path {
+                registry.register_as(short, Arc::clone(handler));
+            }
+        }
+        Ok(registry)
+    }
+}
+
+/// 按名称查找处理器：先精确匹配，再按路径末段匹配（`myapp::MoneyHandler` ↔ `MoneyHandler`）
+fn lookup_named<'a>(
+    named: &HashMap<&str, &'a Arc<dyn TypeHandler>>,
+    path: &str,
+) -> Option<&'a Arc<dyn TypeHandler>> {
+    named.get(path).or_else(|| named.get(last_segment(path))).copied()
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit("::").next().unwrap_or(path).trim()
+}
+
+/// 按 `[settings] metrics` 创建指标注册表并订阅事件总线
+fn metrics_for(config: &HirustMapperConfig, event_bus: &EventBus) -> Option<MetricsRegistry> {
+    config.settings.metrics.then(|| {
+        let metrics = MetricsRegistry::new();
+        event_bus.add_subscriber(&metrics);
+        metrics
+    })
+}
+
+/// 按 `[settings] slow_query_explain` 创建慢查询执行计划捕获器（未设慢查询阈值时无从触发，不创建）
+fn slow_query_explainer_for(
+    config: &HirustMapperConfig,
+    environment: &Environment,
+    type_handler_registry: &Arc<TypeHandlerRegistry>,
+) -> Option<Arc<SlowQueryExplainer>> {
+    let settings = &config.settings;
+    (settings.slow_query_explain && settings.sql_log_slow_threshold_ms > 0).then(|| {
+        let explainer = SlowQueryExplainer::new(
+            environment.pool().clone(),
+            environment.dialect(),
+            Arc::clone(type_handler_registry),
+        )
+        .with_min_interval(Duration::from_millis(settings.slow_query_explain_interval_ms));
+        Arc::new(explainer)
+    })
+}
+
+fn unknown_handler(entry: &TypeHandlerEntry) -> MapperRuntimeError {
+    MapperRuntimeError::Config(format!(
+        "未注册的类型处理器 '{}'（[[type_handlers]] type = '{}'），请通过 SqlSessionFactory::builder(..).type_handler(..) 注册",
+        entry.handler_path, entry.type_path
+    ))
+}
+
 impl SqlSessionFactory {
     /// 从配置构建 SqlSessionFactory
     ///
@@ -65,12 +239,27 @@ impl SqlSessionFactory {
     /// 4. 返回就绪的工厂实例
     ///
     /// `base_dir` 用于解析 mapper 文件的相对 glob 路径，通常传入项目根目录。
+    ///
+    /// 配置了 `[[type_handlers]]` 时需经 [`builder`](Self::builder) 注册对应的处理器，
+    /// 否则构建失败。
     pub async fn build<P: AsRef<Path>>(
         config: HirustMapperConfig,
         base_dir: P,
     ) -> Result<Self> {
-        let base_dir = base_dir.as_ref().to_path_buf();
+        Self::builder(config, base_dir).build().await
+    }
 
+    /// 创建工厂构建器（注册自定义类型处理器后再构建）
+    pub fn builder<P: AsRef<Path>>(config: HirustMapperConfig, base_dir: P) -> SqlSessionFactoryBuilder {
+        SqlSessionFactoryBuilder::new(config, base_dir)
+    }
+
+    async fn build_with(
+        config: HirustMapperConfig,
+        base_dir: std::path::PathBuf,
+        type_handler_registry: TypeHandlerRegistry,
+        model_registry: ModelRegistry,
+    ) -> Result<Self> {
         // 1. 创建数据库环境
         let environment = Environment::from_config(&config.environment).await?;
 
@@ -81,16 +270,17 @@ impl SqlSessionFactory {
 
         // 3. 初始化类型别名 / 类型处理器注册表
         let type_alias_registry = Arc::new(TypeAliasRegistry::from_map(config.type_aliases.clone()));
-        let type_handler_registry = Arc::new(TypeHandlerRegistry::with_defaults());
+        let type_handler_registry = Arc::new(type_handler_registry);
 
         // SQL 执行日志配置（从 settings 解析，默认关闭）
-        let sql_log = Arc::new(SqlLogConfig {
-            enabled: config.settings.sql_log,
-            slow_threshold_ms: config.settings.sql_log_slow_threshold_ms,
-        });
+        let sql_log = Arc::new(SqlLogConfig::from_settings(&config.settings));
 
         // 事件总线（SQL 执行前/后生命周期事件；无监听器时派发零开销）
         let event_bus = Arc::new(EventBus::new());
+        let metrics = metrics_for(&config, &event_bus);
+        let slow_query_explainer = slow_query_explainer_for(&config, &environment, &type_handler_registry);
+        let redactor =
+            Arc::new(Redactor::new(&config.settings.redaction).with_names(model_registry.redacted_fields()));
 
         // 4. 热重载（当 refresh_interval > 0 时启动）
         let watcher = if config.settings.mapper_refresh_interval_ms > 0 {
@@ -103,15 +293,15 @@ impl SqlSessionFactory {
                 config.settings.mapper_refresh_interval_ms,
             ) {
                 Ok(w) => {
-                    eprintln!(
-                        "[hirust-mapper] 热重载已启用: 间隔 {}ms",
+                    crate::telemetry::info(&format!(
+                        "热重载已启用: 间隔 {}ms",
                         config.settings.mapper_refresh_interval_ms
-                    );
+                    ));
                     Some(w)
                 }
                 Err(e) => {
                     // 热重载失败不阻断工厂构建（ORM 仍可用，仅失去热重载能力）
-                    eprintln!("[hirust-mapper][WARN] 热重载启动失败（已禁用）: {}", e);
+                    crate::telemetry::warn(&format!("热重载启动失败（已禁用）: {}", e));
                     None
                 }
             }
@@ -120,12 +310,19 @@ impl SqlSessionFactory {
         };
 
         Ok(Self {
+            id: NEXT_FACTORY_ID.fetch_add(1, Ordering::Relaxed),
             environment,
             mapper_registry,
             type_alias_registry,
             type_handler_registry,
+            model_registry: Arc::new(model_registry),
             sql_log,
             event_bus,
+            metrics,
+            slow_query_explainer,
+            redactor,
+            sql_commenter: SqlCommenter::from_config(&config.settings.sql_comment).map(Arc::new),
+            retry_policy: Arc::new(config.settings.retry.clone()),
             config,
             base_dir,
             watcher,
@@ -141,17 +338,26 @@ impl SqlSessionFactory {
         config: HirustMapperConfig,
         base_dir: std::path::PathBuf,
     ) -> Self {
-        let sql_log = Arc::new(SqlLogConfig {
-            enabled: config.settings.sql_log,
-            slow_threshold_ms: config.settings.sql_log_slow_threshold_ms,
-        });
+        let sql_log = Arc::new(SqlLogConfig::from_settings(&config.settings));
+        let event_bus = Arc::new(EventBus::new());
+        let metrics = metrics_for(&config, &event_bus);
+        let type_handler_registry = Arc::new(type_handler_registry);
+        let slow_query_explainer = slow_query_explainer_for(&config, &environment, &type_handler_registry);
+        let redactor = Arc::new(Redactor::new(&config.settings.redaction));
         Self {
+            id: NEXT_FACTORY_ID.fetch_add(1, Ordering::Relaxed),
             environment,
             mapper_registry: Arc::new(mapper_registry),
             type_alias_registry: Arc::new(type_alias_registry),
-            type_handler_registry: Arc::new(type_handler_registry),
+            type_handler_registry,
+            model_registry: Arc::new(ModelRegistry::new()),
             sql_log,
-            event_bus: Arc::new(EventBus::new()),
+            event_bus,
+            metrics,
+            slow_query_explainer,
+            redactor,
+            sql_commenter: SqlCommenter::from_config(&config.settings.sql_comment).map(Arc::new),
+            retry_policy: Arc::new(config.settings.retry.clone()),
             config,
             base_dir,
             watcher: None,
@@ -163,6 +369,14 @@ impl SqlSessionFactory {
         &self.event_bus
     }
 
+    /// 语句级指标快照（调用次数、错误、行数、耗时与连接池等待直方图）
+    ///
+    /// 需开启 `[settings] metrics`（或 [`with_metrics`](HirustMapperConfig::with_metrics)），
+    /// 未开启时返回空快照。[`MetricsSnapshot::to_prometheus`] 可直接作为 `/metrics` 响应体。
+    pub fn metrics_snapshot(&self) -> MetricsSnapshot {
+        self.metrics.as_ref().map(MetricsRegistry::snapshot).unwrap_or_default()
+    }
+
     /// 数据库环境引用
     pub fn environment(&self) -> &Environment {
         &self.environment
@@ -210,6 +424,10 @@ impl SqlSessionFactory {
 
     /// 打开一个新的 SqlSession（请求级，共享工厂的连接池和注册表）
     pub fn open_session(&self) -> SqlSession {
+        let default_timeout = match self.config.settings.default_statement_timeout_ms {
+            0 => None,
+            ms => Some(Duration::from_millis(ms)),
+        };
         SqlSession::new(
             self.environment.clone(),
             Arc::clone(&self.mapper_registry),
@@ -218,6 +436,73 @@ impl SqlSessionFactory {
             Arc::clone(&self.sql_log),
             Arc::clone(&self.event_bus),
         )
+        .with_default_timeout(default_timeout)
+        .with_binary_encoding(self.config.settings.binary_encoding)
+        .with_models(Arc::clone(&self.model_registry))
+        .with_retry_policy(Arc::clone(&self.retry_policy))
+        .with_slow_query_explainer(self.slow_query_explainer.clone())
+        .with_redactor(Arc::clone(&self.redactor))
+        .with_sql_commenter(self.sql_commenter.clone())
+    }
+
+    /// 工厂实例编号（进程内唯一）
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// 取当前 session：当前 task 处于本工厂的 [`transaction`](Self::transaction) 闭包内时加入该事务，
+    /// 否则打开新 session。`#[dao]` 生成的方法经由此处获取 session。
+    pub async fn current_session(&self) -> ScopedSession {
+        transaction::current_session(self).await
+    }
+
+    /// 闭包式事务：闭包返回 `Ok` 提交，返回 `Err` 或 panic 回滚。
+    ///
+    /// 传播方式为 [`Propagation::Nested`]：在已有事务内嵌套调用时创建 SAVEPOINT，
+    /// 内层失败只回滚到保存点。
+    ///
+    /// ```ignore
+    /// let id = factory.transaction(|tx| async move {
+    ///     let id = tx.insert("app.UserDao", "insert", &user).await?;
+    ///     tx.update("app.AccountDao", "open", &json!({ "userId": id })).await?;
+    ///     Ok(id)
+    /// }).await?;
+    /// ```
+    pub async fn transaction<F, Fut, R>(&self, f: F) -> Result<R>
+    where
+        F: FnOnce(TxSession) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        self.transaction_with(Propagation::default(), f).await
+    }
+
+    /// 指定传播方式的闭包式事务（见 [`Propagation`]）
+    pub async fn transaction_with<F, Fut, R>(&self, propagation: Propagation, f: F) -> Result<R>
+    where
+        F: FnOnce(TxSession) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        transaction::run(self, propagation, f).await
+    }
+
+    /// 可重试的闭包式事务：按 `[settings.retry]` 策略，在瞬时错误（忙、死锁、序列化失败）时
+    /// 回滚并重新执行整个闭包，每次重试派发 [`RetryEvent`](crate::RetryEvent)。
+    ///
+    /// 闭包可能被调用多次，因此须为 `FnMut`，且除数据库操作外不应有不可重复的副作用。
+    /// 已处于环境事务中时按 [`Propagation::Nested`] 执行一次、不重试（由外层事务决定）。
+    ///
+    /// ```ignore
+    /// factory.transaction_with_retry(|tx| async move {
+    ///     tx.update("app.AccountDao", "debit", &json!({ "id": 1, "amount": 100 })).await?;
+    ///     tx.update("app.AccountDao", "credit", &json!({ "id": 2, "amount": 100 })).await
+    /// }).await?;
+    /// ```
+    pub async fn transaction_with_retry<F, Fut, R>(&self, f: F) -> Result<R>
+    where
+        F: FnMut(TxSession) -> Fut,
+        Fut: Future<Output = Result<R>>,
+    {
+        transaction::run_with_retry(self, &self.retry_policy, f).await
     }
 
     /// 关闭工厂，释放连接池资源
@@ -0,0 +1,213 @@
+//! SQL 注释标记（sqlcommenter）
+//!
+//! 开启 `[settings.sql_comment] enabled` 后，运行时由 Mapper 语句生成的每条 SQL 末尾追加一段
+//! [sqlcommenter](https://google.github.io/sqlcommenter/spec/) 格式的注释，标明语句来源，
+//! 便于把数据库侧的慢日志 / 活动会话与代码对应起来：
+//!
+//! ```text
+//! SELECT id, name FROM users WHERE id = ? /*app='order-service',id='findById',ns='app.UserDao'*/
+//! ```
+//!
+//! - 键按字典序排列：`app`（配置了应用名时）、`id`（语句 id）、`ns`（命名空间），
+//!   以及 [`TraceContextProvider`] 返回的 `traceparent` / `tracestate`。
+//! - 键与值经 URL 编码（仅保留 `A-Z a-z 0-9 - _ . ~`），值内的 `'`、`*/`、`?` 等均被编码，无法提前闭合注释，
+//!   也不会被当作占位符；注释只追加在 SQL 末尾（末尾的 `;` 之前），参数顺序不变。
+//! - 按规范，已含注释（`/*`）的 SQL（如优化器提示）保持原样。
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// 当前调用的追踪上下文（W3C Trace Context）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    /// `traceparent`，如 `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`
+    pub traceparent: String,
+    /// `tracestate`（可选）
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    pub fn new(traceparent: impl Into<String>) -> Self {
+        Self { traceparent: traceparent.into(), tracestate: None }
+    }
+
+    /// 设置 `tracestate`
+    pub fn with_tracestate(mut self, tracestate: impl Into<String>) -> Self {
+        self.tracestate = Some(tracestate.into());
+        self
+    }
+}
+
+/// 追踪上下文提供者：生成 SQL 时在调用方所在的上下文中求值（如从当前 span 读取 OpenTelemetry 上下文），
+/// 返回 `None` 时注释不含追踪字段
+pub type TraceContextProvider = Arc<dyn Fn() -> Option<TraceContext> + Send + Sync>;
+
+/// SQL 注释配置（对应 `[settings.sql_comment]`）
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+pub struct SqlCommentConfig {
+    /// 是否追加注释（默认关闭）
+    #[serde(default)]
+    pub enabled: bool,
+    /// 应用名（注释中的 `app` 字段，未设置时省略）
+    #[serde(default)]
+    pub app: Option<String>,
+}
+
+impl SqlCommentConfig {
+    /// 开启注释，不带应用名
+    pub fn enabled() -> Self {
+        Self { enabled: true, app: None }
+    }
+
+    /// 设置应用名
+    pub fn with_app(mut self, app: impl Into<String>) -> Self {
+        self.app = Some(app.into());
+        self
+    }
+}
+
+/// SQL 注释生成器（工厂按配置创建，所有 session 共享）
+#[derive(Clone, Default)]
+pub struct SqlCommenter {
+    /// 已编码的 `app='..'` 片段
+    app: Option<String>,
+    trace_context: Option<TraceContextProvider>,
+}
+
+impl std::fmt::Debug for SqlCommenter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SqlCommenter")
+            .field("app", &self.app)
+            .field("trace_context", &self.trace_context.is_some())
+            .finish()
+    }
+}
+
+impl SqlCommenter {
+    /// 按配置创建；未开启时为 `None`
+    pub fn from_config(config: &SqlCommentConfig) -> Option<Self> {
+        config.enabled.then(|| {
+            let commenter = Self::default();
+            match &config.app {
+                Some(app) => commenter.with_app(app),
+                None => commenter,
+            }
+        })
+    }
+
+    /// 设置应用名（注释中的 `app` 字段）
+    pub fn with_app(mut self, app: &str) -> Self {
+        self.app = Some(encode(app));
+        self
+    }
+
+    /// 设置追踪上下文提供者
+    pub fn with_trace_context(mut self, provider: TraceContextProvider) -> Self {
+        self.trace_context = Some(provider);
+        self
+    }
+
+    /// 语句的注释，如 `/*id='findById',ns='app.UserDao'*/`
+    pub fn comment(&self, namespace: &str, statement_id: &str) -> String {
+        let trace = self.trace_context.as_ref().and_then(|provider| provider());
+        // 键按字典序：app < id < ns < traceparent < tracestate
+        let mut pairs: Vec<(&str, String)> = Vec::with_capacity(5);
+        if let Some(app) = &self.app {
+            pairs.push(("app", app.clone()));
+        }
+        pairs.push(("id", encode(statement_id)));
+        pairs.push(("ns", encode(namespace)));
+        if let Some(trace) = trace {
+            pairs.push(("traceparent", encode(&trace.traceparent)));
+            if let Some(state) = &trace.tracestate {
+                pairs.push(("tracestate", encode(state)));
+            }
+        }
+        let mut out = String::from("/*");
+        for (i, (key, value)) in pairs.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let _ = write!(out, "{}='{}'", key, value);
+        }
+        out.push_str("*/");
+        out
+    }
+
+    /// 在 SQL 末尾（末尾的 `;` 之前）追加语句注释；已含注释的 SQL 保持原样
+    pub fn apply(&self, sql: &mut String, namespace: &str, statement_id: &str) {
+        if sql.contains("/*") {
+            return;
+        }
+        let comment = self.comment(namespace, statement_id);
+        let trimmed = sql.trim_end();
+        let (body, terminator) = match trimmed.strip_suffix(';') {
+            Some(body) => (body.trim_end(), ";"),
+            None => (trimmed, ""),
+        };
+        *sql = format!("{} {}{}", body, comment, terminator);
+    }
+}
+
+/// URL 编码：保留非保留字符 `A-Z a-z 0-9 - _ . ~`，其余字节按 `%XX` 编码
+fn encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for &b in value.as_bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b'~') {
+            out.push(b as char);
+        } else {
+            let _ = write!(out, "%{:02X}", b);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_sorted_and_encoded() {
+        let commenter = SqlCommenter::from_config(&SqlCommentConfig::enabled().with_app("order service")).unwrap();
+        assert_eq!(
+            commenter.comment("app.UserDao", "findById"),
+            "/*app='order%20service',id='findById',ns='app.UserDao'*/"
+        );
+        // 恶意值无法闭合注释或引入占位符
+        let comment = commenter.comment("app.UserDao", "x'*/ DROP TABLE users; --?");
+        assert_eq!(comment.matches("*/").count(), 1);
+        assert!(!comment.contains('?'));
+        assert!(comment.contains("id='x%27%2A%2F%20DROP%20TABLE%20users%3B%20--%3F'"));
+        assert!(SqlCommenter::from_config(&SqlCommentConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_comment_with_trace_context() {
+        let commenter = SqlCommenter::default().with_trace_context(Arc::new(|| {
+            Some(TraceContext::new("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01").with_tracestate("congo=t61"))
+        }));
+        assert_eq!(
+            commenter.comment("app.UserDao", "findById"),
+            "/*id='findById',ns='app.UserDao',\
+             traceparent='00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01',tracestate='congo%3Dt61'*/"
+        );
+        let untraced = SqlCommenter::default().with_trace_context(Arc::new(|| None));
+        assert_eq!(untraced.comment("ns", "id"), "/*id='id',ns='ns'*/");
+    }
+
+    #[test]
+    fn test_apply_appends_at_end() {
+        let commenter = SqlCommenter::default();
+        let mut sql = "SELECT * FROM users WHERE id = ?\n  ".to_string();
+        commenter.apply(&mut sql, "app.UserDao", "findById");
+        assert_eq!(sql, "SELECT * FROM users WHERE id = ? /*id='findById',ns='app.UserDao'*/");
+
+        let mut sql = "DELETE FROM users WHERE id = ? ;".to_string();
+        commenter.apply(&mut sql, "app.UserDao", "delete");
+        assert_eq!(sql, "DELETE FROM users WHERE id = ? /*id='delete',ns='app.UserDao'*/;");
+
+        let mut hinted = "SELECT /*+ INDEX(users idx_name) */ * FROM users".to_string();
+        commenter.apply(&mut hinted, "app.UserDao", "findAll");
+        assert_eq!(hinted, "SELECT /*+ INDEX(users idx_name) */ * FROM users");
+    }
+}
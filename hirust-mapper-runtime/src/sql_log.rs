@@ -1,7 +1,8 @@
 //! SQL 执行日志
 //!
 //! 受配置 `[settings] sql_log` 开关控制。启用后，在每次 SQL 执行点记录
-//! 「耗时 + 可读 SQL（参数内联进 `?`）」一条日志，经 `log` facade 输出。
+//! 「耗时 + 语句上下文（`namespace.id [类型] session=N tx`）+ 可读 SQL（参数内联进 `?`）」
+//! 一条日志，经 `log` facade 输出。
 //!
 //! ## 输出示例
 //!
@@ -9,9 +10,32 @@
 //!
 //! ```text
 //! [2026-08-12 15:32:03 INFO hirust_mapper::sql] Consume Time: 44 ms
+//!  Statement: app.ExamDao.findByIds [SELECT] session=3
 //!  Execute SQL: SELECT `examId`, `examName` FROM exam WHERE (`examId` IN (69902) AND `isDelete` = 0)
 //! ```
 //!
+//! 开启 `[settings] slow_query_explain` 时，慢查询（达到 `sql_log_slow_threshold_ms`）的日志追加执行计划：
+//!
+//! ```text
+//!  Query Plan:
+//!    SEARCH exam USING INTEGER PRIMARY KEY (rowid=?)
+//! ```
+//!
+//! `[settings] sql_log_format = "json"` 时每次执行输出一行 JSON 对象（字段见 [`ExecutionLog::to_json`]）：
+//!
+//! ```text
+//! {"timestamp":"2026-08-12T07:32:03.044Z","statement":"app.ExamDao.findByIds","kind":"SELECT","session_id":3,"in_transaction":false,"sql":"SELECT `examId` FROM exam WHERE `examId` IN (?)","params":[69902],"elapsed_ms":44,"rows":1,"outcome":"ok"}
+//! ```
+//!
+//! ## 采样与级别
+//!
+//! - `sql_log_sample_rate`（`0.0` ~ `1.0`）按概率记录高频语句；失败与慢查询总是记录。
+//! - `[settings.sql_log_levels]` 按命名空间（或其点分前缀）覆盖日志级别，`off` 关闭该命名空间的日志；
+//!   未覆盖时为 `info`。
+//!
+//! 执行点记录前先经 [`Redactor`](crate::Redactor) 打码，敏感参数（`#{password, redact=true}`、
+//! `[settings.redaction] patterns`、模型 `#[mapper(redact)]` 字段）以掩码内联。
+//!
 //! ## 消费方
 //!
 //! 本 crate 仅通过 `log` facade 发射日志，**不自带输出后端**。要看到输出，
@@ -22,24 +46,126 @@
 //! // 或按 target 精确过滤：RUST_LOG=hirust_mapper::sql=info
 //! ```
 
-use std::time::Duration;
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use hirust_mapper_core::BoundSql;
-use serde_json::Value;
+use serde_json::{Map, Value};
+
+use crate::config::SettingsConfig;
+use crate::event::lifecycle::{SqlOutcome, StatementContext};
+use crate::explain::QueryPlan;
 
 /// 日志 target（便于用 `RUST_LOG=hirust_mapper::sql=info` 精确过滤）
 pub const LOG_TARGET: &str = "hirust_mapper::sql";
 
+/// SQL 日志输出格式（对应 `[settings] sql_log_format`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlLogFormat {
+    /// 人读格式：耗时 + 语句上下文 + 参数内联的 SQL（默认）
+    #[default]
+    Text,
+    /// 每次执行一行 JSON 对象（见 [`ExecutionLog::to_json`]）
+    Json,
+}
+
+impl SqlLogFormat {
+    /// 由配置取值解析（`text` / `json`，大小写不敏感）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "text" => Some(SqlLogFormat::Text),
+            "json" => Some(SqlLogFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// SQL 日志级别（对应 `[settings.sql_log_levels]` 的取值）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SqlLogLevel {
+    /// 不记录
+    Off,
+    Error,
+    Warn,
+    /// 默认级别
+    #[default]
+    Info,
+    Debug,
+    Trace,
+}
+
+impl SqlLogLevel {
+    /// 由配置取值解析（`off` / `error` / `warn` / `info` / `debug` / `trace`，大小写不敏感）
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(SqlLogLevel::Off),
+            "error" => Some(SqlLogLevel::Error),
+            "warn" => Some(SqlLogLevel::Warn),
+            "info" => Some(SqlLogLevel::Info),
+            "debug" => Some(SqlLogLevel::Debug),
+            "trace" => Some(SqlLogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    /// 对应的 `log` 级别（`Off` 为 `None`）
+    pub fn to_level(self) -> Option<log::Level> {
+        match self {
+            SqlLogLevel::Off => None,
+            SqlLogLevel::Error => Some(log::Level::Error),
+            SqlLogLevel::Warn => Some(log::Level::Warn),
+            SqlLogLevel::Info => Some(log::Level::Info),
+            SqlLogLevel::Debug => Some(log::Level::Debug),
+            SqlLogLevel::Trace => Some(log::Level::Trace),
+        }
+    }
+}
+
 /// SQL 日志配置（从 `[settings]` 解析）
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct SqlLogConfig {
     /// 是否开启 SQL 执行日志
     pub enabled: bool,
     /// 慢查询阈值（毫秒）；仅记录耗时 ≥ 此值的 SQL。`0` 表示记录全部。
     pub slow_threshold_ms: u64,
+    /// 输出格式
+    pub format: SqlLogFormat,
+    /// 采样率（`0.0` ~ `1.0`，默认 `1.0` 全部记录）；失败与慢查询不参与采样，总是记录
+    pub sample_rate: f64,
+    /// 按命名空间覆盖日志级别：键为命名空间或其点分前缀（`app` 覆盖 `app.UserDao`），最长前缀优先；
+    /// 未匹配（含未命名语句）时为 `info`
+    pub namespace_levels: HashMap<String, SqlLogLevel>,
+}
+
+impl Default for SqlLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            slow_threshold_ms: 0,
+            format: SqlLogFormat::default(),
+            sample_rate: 1.0,
+            namespace_levels: HashMap::new(),
+        }
+    }
 }
 
 impl SqlLogConfig {
+    /// 由 `[settings]` 的 `sql_log*` 项构造
+    pub fn from_settings(settings: &SettingsConfig) -> Self {
+        Self {
+            enabled: settings.sql_log,
+            slow_threshold_ms: settings.sql_log_slow_threshold_ms,
+            format: settings.sql_log_format,
+            sample_rate: settings.sql_log_sample_rate,
+            namespace_levels: settings.sql_log_levels.clone(),
+        }
+    }
+
     /// 该次执行是否应被记录（开关开启 + 达到慢查询阈值）
     pub fn should_log(&self, elapsed: Duration) -> bool {
         if !self.enabled {
@@ -50,6 +176,68 @@ impl SqlLogConfig {
         }
         elapsed.as_millis() as u64 >= self.slow_threshold_ms
     }
+
+    /// 是否为慢查询（设置了阈值且耗时达到阈值；与日志开关无关）
+    pub fn is_slow(&self, elapsed: Duration) -> bool {
+        self.slow_threshold_ms > 0 && elapsed.as_millis() as u64 >= self.slow_threshold_ms
+    }
+
+    /// 命名空间的日志级别（按最长点分前缀匹配 `namespace_levels`；`off` 为 `None`）
+    pub fn level_for(&self, namespace: &str) -> Option<log::Level> {
+        if self.namespace_levels.is_empty() || namespace.is_empty() {
+            return SqlLogLevel::Info.to_level();
+        }
+        let mut key = namespace;
+        loop {
+            if let Some(level) = self.namespace_levels.get(key) {
+                return level.to_level();
+            }
+            match key.rfind('.') {
+                Some(dot) => key = &key[..dot],
+                None => return SqlLogLevel::Info.to_level(),
+            }
+        }
+    }
+
+    /// 该次执行的日志级别；不记录（开关关闭、未达阈值、级别为 `off` 或后端未启用该级别、未被采样）时为 `None`
+    pub fn decide(&self, statement: &StatementContext, elapsed: Duration, failed: bool) -> Option<log::Level> {
+        self.sampled_level(statement, elapsed, failed)
+            .filter(|&level| log::log_enabled!(target: LOG_TARGET, level))
+    }
+
+    /// 同 [`decide`](Self::decide)，但不询问日志后端
+    fn sampled_level(&self, statement: &StatementContext, elapsed: Duration, failed: bool) -> Option<log::Level> {
+        if !self.should_log(elapsed) {
+            return None;
+        }
+        let level = self.level_for(&statement.namespace)?;
+        (failed || self.is_slow(elapsed) || self.sampled()).then_some(level)
+    }
+
+    fn sampled(&self) -> bool {
+        if self.sample_rate >= 1.0 {
+            true
+        } else if self.sample_rate <= 0.0 {
+            false
+        } else {
+            next_random() < self.sample_rate
+        }
+    }
+}
+
+/// `[0, 1)` 均匀分布的伪随机数（线程本地 xorshift，以随机哈希种子初始化；仅用于日志采样）
+fn next_random() -> f64 {
+    thread_local! {
+        static STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+    }
+    STATE.with(|state| {
+        let mut x = state.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        state.set(x);
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    })
 }
 
 /// 把 [`BoundSql`] 的参数内联进 `?` 占位符，生成可读的日志 SQL。
@@ -113,31 +301,137 @@ fn collapse_whitespace(s: &str) -> String {
     out
 }
 
-/// 若配置启用且达到阈值，记录一条 SQL 执行日志（耗时 + 可读 SQL）。
+/// 一次执行的日志内容
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutionLog<'a> {
+    /// 语句上下文
+    pub statement: &'a StatementContext,
+    /// 执行的 SQL 与参数（已打码）
+    pub bound: &'a BoundSql,
+    /// 执行耗时
+    pub elapsed: Duration,
+    /// 执行结果（行数 / 错误）；未知时为 `None`
+    pub outcome: Option<&'a SqlOutcome>,
+    /// 慢查询执行计划
+    pub plan: Option<&'a QueryPlan>,
+}
+
+impl ExecutionLog<'_> {
+    /// 人读格式：`Consume Time` / `Statement` / `Execute SQL`（参数内联）及可选的 `Query Plan` 段
+    pub fn render_text(&self) -> String {
+        let plan = self.plan.map(render_plan_for_log).unwrap_or_default();
+        format!(
+            "Consume Time: {} ms\n Statement: {}\n Execute SQL: {}{}",
+            self.elapsed.as_millis(),
+            self.statement,
+            render_sql_for_log(self.bound),
+            plan
+        )
+    }
+
+    /// 结构化格式：单个 JSON 对象
+    ///
+    /// 字段：`timestamp`（UTC RFC 3339，毫秒）、`statement`（`namespace.id`，未命名语句为 `null`）、`kind`、
+    /// `session_id`、`in_transaction`、`sql`（保留 `?` 占位符，空白折叠）、`params`、`elapsed_ms`、
+    /// `rows`（行数 / 受影响行数，失败或未知为 `null`）、`outcome`（`ok` / `error`，未知为 `null`），
+    /// 失败时附 `error`，捕获到执行计划时附 `plan`（每行一条）。
+    pub fn to_json(&self) -> Value {
+        let statement = self.statement;
+        let mut obj = Map::new();
+        obj.insert("timestamp".into(), Value::String(format_timestamp(SystemTime::now())));
+        obj.insert(
+            "statement".into(),
+            if statement.is_named() { Value::String(statement.qualified_id()) } else { Value::Null },
+        );
+        obj.insert("kind".into(), Value::String(statement.kind.to_string()));
+        obj.insert("session_id".into(), statement.session_id.map(Value::from).unwrap_or(Value::Null));
+        obj.insert("in_transaction".into(), Value::Bool(statement.in_transaction));
+        obj.insert("sql".into(), Value::String(collapse_whitespace(&self.bound.sql)));
+        obj.insert("params".into(), Value::Array(self.bound.parameters.clone()));
+        obj.insert("elapsed_ms".into(), Value::from(self.elapsed.as_millis() as u64));
+        let (rows, outcome) = match self.outcome {
+            Some(SqlOutcome::Fetched(n)) => (Value::from(*n as u64), Value::from("ok")),
+            Some(SqlOutcome::Affected(n)) => (Value::from(*n), Value::from("ok")),
+            Some(SqlOutcome::Failed(err)) => {
+                obj.insert("error".into(), Value::String(err.clone()));
+                (Value::Null, Value::from("error"))
+            }
+            None => (Value::Null, Value::Null),
+        };
+        obj.insert("rows".into(), rows);
+        obj.insert("outcome".into(), outcome);
+        if let Some(plan) = self.plan {
+            obj.insert("plan".into(), Value::Array(plan.lines().into_iter().map(Value::String).collect()));
+        }
+        Value::Object(obj)
+    }
+}
+
+/// 若配置启用且达到阈值，记录一条 SQL 执行日志（耗时 + 语句上下文 + 可读 SQL）。
 ///
-/// 成功与失败路径均会记录（耗时本身有诊断价值）。
-pub fn log_execution(config: &SqlLogConfig, bound: &BoundSql, elapsed: Duration) {
-    if !config.should_log(elapsed) {
-        return;
+/// 成功与失败路径均会记录（耗时本身有诊断价值）。未命名语句（不经 Mapper 执行）只输出语句类型。
+/// 级别、采样与格式按 [`SqlLogConfig`]；执行结果未知，JSON 格式的 `rows` / `outcome` 为 `null`。
+pub fn log_execution(config: &SqlLogConfig, statement: &StatementContext, bound: &BoundSql, elapsed: Duration) {
+    if let Some(level) = config.decide(statement, elapsed, false) {
+        write_execution(config, level, &ExecutionLog { statement, bound, elapsed, outcome: None, plan: None });
+    }
+}
+
+/// 按配置的格式在指定级别输出一条执行日志（不再判断开关、阈值与采样，见 [`SqlLogConfig::decide`]）
+pub fn write_execution(config: &SqlLogConfig, level: log::Level, record: &ExecutionLog<'_>) {
+    match config.format {
+        SqlLogFormat::Text => log::log!(target: LOG_TARGET, level, "{}", record.render_text()),
+        SqlLogFormat::Json => log::log!(target: LOG_TARGET, level, "{}", record.to_json()),
+    }
+}
+
+fn render_plan_for_log(plan: &QueryPlan) -> String {
+    let mut out = String::from("\n Query Plan:");
+    for line in plan.lines() {
+        out.push_str("\n   ");
+        out.push_str(&line);
     }
-    let sql = render_sql_for_log(bound);
-    log::info!(
-        target: LOG_TARGET,
-        "Consume Time: {} ms\n Execute SQL: {}",
-        elapsed.as_millis(),
-        sql
-    );
+    out
+}
+
+/// UTC RFC 3339 时间戳（毫秒精度），如 `2026-08-12T07:32:03.044Z`
+fn format_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    // 公历日期换算（days-from-civil 的逆运算，以 0000-03-01 为纪元起点）
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60,
+        since_epoch.subsec_millis()
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
+    use crate::event::lifecycle::SqlKind;
 
     fn bound(sql: &str, params: Vec<Value>) -> BoundSql {
         BoundSql {
             sql: sql.to_string(),
             parameters: params,
+            ..Default::default()
         }
     }
 
@@ -237,7 +531,7 @@ mod tests {
 
     #[test]
     fn test_should_log_threshold() {
-        let mut cfg = SqlLogConfig { enabled: true, slow_threshold_ms: 0 };
+        let mut cfg = SqlLogConfig { enabled: true, ..Default::default() };
         assert!(cfg.should_log(Duration::from_millis(1)));
         assert!(cfg.should_log(Duration::from_millis(1000)));
 
@@ -251,4 +545,113 @@ mod tests {
         cfg.slow_threshold_ms = 0;
         assert!(!cfg.should_log(Duration::from_secs(10)));
     }
+
+    #[test]
+    fn test_is_slow_ignores_enabled() {
+        let mut cfg = SqlLogConfig::default();
+        assert!(!cfg.is_slow(Duration::from_secs(10)), "未设阈值时无慢查询");
+        cfg.slow_threshold_ms = 100;
+        assert!(!cfg.is_slow(Duration::from_millis(99)));
+        assert!(cfg.is_slow(Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_level_for_longest_prefix() {
+        let cfg = SqlLogConfig {
+            namespace_levels: HashMap::from([
+                ("app".to_string(), SqlLogLevel::Debug),
+                ("app.AuditDao".to_string(), SqlLogLevel::Off),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(cfg.level_for("app.UserDao"), Some(log::Level::Debug));
+        assert_eq!(cfg.level_for("app.AuditDao"), None);
+        assert_eq!(cfg.level_for("app.AuditDaoExt"), Some(log::Level::Debug), "按点分段匹配，不按字符前缀");
+        assert_eq!(cfg.level_for("other.Dao"), Some(log::Level::Info));
+        assert_eq!(cfg.level_for(""), Some(log::Level::Info));
+    }
+
+    #[test]
+    fn test_sampling_keeps_failures_and_slow_queries() {
+        let stmt = StatementContext::new("app.UserDao", "findById", SqlKind::Select);
+        let mut cfg = SqlLogConfig { enabled: true, sample_rate: 0.0, ..Default::default() };
+        assert_eq!(cfg.sampled_level(&stmt, Duration::from_millis(1), false), None);
+        assert_eq!(cfg.sampled_level(&stmt, Duration::from_millis(1), true), Some(log::Level::Info));
+        cfg.slow_threshold_ms = 10;
+        assert_eq!(cfg.sampled_level(&stmt, Duration::from_millis(20), false), Some(log::Level::Info));
+
+        let cfg = SqlLogConfig { enabled: true, sample_rate: 0.5, ..Default::default() };
+        let logged = (0..10_000)
+            .filter(|_| cfg.sampled_level(&stmt, Duration::ZERO, false).is_some())
+            .count();
+        assert!((4_000..6_000).contains(&logged), "采样率 0.5 记录了 {logged} / 10000");
+    }
+
+    #[test]
+    fn test_execution_log_json() {
+        let stmt = StatementContext::new("app.UserDao", "findById", SqlKind::Select).with_session(3, true);
+        let b = bound("SELECT id\n  FROM users WHERE id = ?", vec![json!(7)]);
+        let outcome = SqlOutcome::Fetched(1);
+        let record = ExecutionLog {
+            statement: &stmt,
+            bound: &b,
+            elapsed: Duration::from_millis(44),
+            outcome: Some(&outcome),
+            plan: None,
+        };
+        let mut value = record.to_json();
+        let timestamp = value.as_object_mut().unwrap().remove("timestamp").unwrap();
+        assert!(timestamp.as_str().unwrap().ends_with('Z'));
+        assert_eq!(
+            value,
+            json!({
+                "statement": "app.UserDao.findById",
+                "kind": "SELECT",
+                "session_id": 3,
+                "in_transaction": true,
+                "sql": "SELECT id FROM users WHERE id = ?",
+                "params": [7],
+                "elapsed_ms": 44,
+                "rows": 1,
+                "outcome": "ok",
+            })
+        );
+        assert!(record.render_text().contains("Execute SQL: SELECT id FROM users WHERE id = 7"));
+
+        let unnamed = StatementContext::unnamed(SqlKind::Update);
+        let failed = SqlOutcome::Failed("no such table".into());
+        let value = ExecutionLog { statement: &unnamed, outcome: Some(&failed), ..record }.to_json();
+        assert_eq!(value["statement"], Value::Null);
+        assert_eq!(value["rows"], Value::Null);
+        assert_eq!(value["outcome"], "error");
+        assert_eq!(value["error"], "no such table");
+        assert_eq!(ExecutionLog { outcome: None, ..record }.to_json()["outcome"], Value::Null);
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        let t = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+        assert_eq!(format_timestamp(t), "2023-11-14T22:13:20.123Z");
+        let leap = UNIX_EPOCH + Duration::from_secs(951_782_400);
+        assert_eq!(format_timestamp(leap), "2000-02-29T00:00:00.000Z");
+    }
+
+    #[test]
+    fn test_parse_format_and_level() {
+        assert_eq!(SqlLogFormat::parse(" JSON "), Some(SqlLogFormat::Json));
+        assert_eq!(SqlLogFormat::parse("yaml"), None);
+        assert_eq!(SqlLogLevel::parse("Debug"), Some(SqlLogLevel::Debug));
+        assert_eq!(SqlLogLevel::parse("off").and_then(SqlLogLevel::to_level), None);
+        assert_eq!(SqlLogLevel::parse("loud"), None);
+    }
+
+    #[test]
+    fn test_render_plan_for_log() {
+        let plan = QueryPlan {
+            sql: "EXPLAIN QUERY PLAN SELECT * FROM users".into(),
+            rows: vec![json!({"id": 2, "detail": "SCAN users"}), json!({"id": 3, "detail": "USE TEMP B-TREE"})],
+        };
+        assert_eq!(render_plan_for_log(&plan), "\n Query Plan:\n   SCAN users\n   USE TEMP B-TREE");
+    }
 }
@@ -0,0 +1,166 @@
+//! 可观测性：`tracing` span 与运行时诊断消息
+//!
+//! 开启 `tracing` feature 后：
+//!
+//! - 每次 SQL 执行开启一个 [`SQL_SPAN`] span，字段为 `namespace` / `statement_id` / `kind` /
+//!   `session_id` / `in_transaction`，执行结束时记录 `rows` / `elapsed_ms`，失败时记录 `error`；
+//!   执行期间 sqlx 自身的 tracing 事件挂在该 span 下。
+//! - 每个事务（`begin` .. `commit` / `rollback`）开启一个 [`TRANSACTION_SPAN`] span，
+//!   事务内的 SQL span 为其子 span，结束时记录 `outcome`（`commit` / `rollback` / `aborted`）。
+//!
+//! 未开启时 span 为零大小的空操作。运行时诊断消息（热重载等）经 [`RUNTIME_TARGET`] 输出：
+//! 开启 `tracing` 时为 tracing 事件，否则经 `log` facade。
+
+use std::future::Future;
+use std::time::Duration;
+
+use crate::event::lifecycle::{SqlOutcome, StatementContext};
+use crate::transaction::TransactionOptions;
+
+/// SQL 执行 span 名
+pub const SQL_SPAN: &str = "hirust_mapper.sql";
+
+/// 事务 span 名
+pub const TRANSACTION_SPAN: &str = "hirust_mapper.transaction";
+
+/// span 与运行时诊断消息的 target（便于用 `RUST_LOG=hirust_mapper::runtime=info` 过滤）
+pub const RUNTIME_TARGET: &str = "hirust_mapper::runtime";
+
+/// 一次 SQL 执行或一个事务的 span（未开启 `tracing` feature 时为空操作）
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    inner: Option<tracing::Span>,
+}
+
+#[cfg(feature = "tracing")]
+impl Span {
+    /// SQL 执行 span（父 span 为当前上下文，事务内即事务 span）
+    pub(crate) fn sql(statement: &StatementContext) -> Self {
+        let span = tracing::info_span!(
+            target: RUNTIME_TARGET,
+            SQL_SPAN,
+            namespace = %statement.namespace,
+            statement_id = %statement.statement_id,
+            kind = %statement.kind,
+            session_id = statement.session_id,
+            in_transaction = statement.in_transaction,
+            rows = tracing::field::Empty,
+            elapsed_ms = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        Self { inner: Some(span) }
+    }
+
+    /// 事务 span
+    pub(crate) fn transaction(session_id: u64, options: &TransactionOptions) -> Self {
+        let span = tracing::info_span!(
+            target: RUNTIME_TARGET,
+            TRANSACTION_SPAN,
+            session_id,
+            isolation = options.isolation.map(|level| level.as_sql()),
+            read_only = options.read_only,
+            outcome = tracing::field::Empty,
+        );
+        Self { inner: Some(span) }
+    }
+
+    /// span 是否会被订阅者记录（决定是否需要计时）
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.inner.as_ref().is_some_and(|span| !span.is_disabled())
+    }
+
+    /// 记录 SQL 执行结果（`outcome` 仅在 span 被订阅时求值）
+    pub(crate) fn record_outcome(&self, elapsed: Duration, outcome: impl FnOnce() -> SqlOutcome) {
+        let Some(span) = self.inner.as_ref().filter(|span| !span.is_disabled()) else {
+            return;
+        };
+        span.record("elapsed_ms", elapsed.as_secs_f64() * 1000.0);
+        match &outcome() {
+            SqlOutcome::Fetched(n) => span.record("rows", *n as u64),
+            SqlOutcome::Affected(n) => span.record("rows", *n),
+            SqlOutcome::Failed(e) => span.record("error", e.as_str()),
+        };
+    }
+
+    /// 记录事务结束方式
+    pub(crate) fn record_end(&self, outcome: &'static str) {
+        if let Some(span) = &self.inner {
+            span.record("outcome", outcome);
+        }
+    }
+
+    /// 在 span 内同步执行 `f`（其中创建的 span 以本 span 为父）
+    pub(crate) fn in_scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        match &self.inner {
+            Some(span) => span.in_scope(f),
+            None => f(),
+        }
+    }
+
+    /// 在 span 内执行 `fut`（每次 poll 时进入 span）
+    pub(crate) fn instrument<F: Future>(&self, fut: F) -> impl Future<Output = F::Output> {
+        use tracing::Instrument;
+        fut.instrument(self.inner.clone().unwrap_or_else(tracing::Span::none))
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl Span {
+    pub(crate) fn sql(_statement: &StatementContext) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn transaction(_session_id: u64, _options: &TransactionOptions) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        false
+    }
+
+    pub(crate) fn record_outcome(&self, _elapsed: Duration, _outcome: impl FnOnce() -> SqlOutcome) {}
+
+    pub(crate) fn record_end(&self, _outcome: &'static str) {}
+
+    pub(crate) fn in_scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+
+    pub(crate) fn instrument<F: Future>(&self, fut: F) -> F {
+        fut
+    }
+}
+
+/// 运行时诊断消息（info 级）
+pub(crate) fn info(message: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(target: RUNTIME_TARGET, "{}", message);
+    #[cfg(not(feature = "tracing"))]
+    log::info!(target: RUNTIME_TARGET, "{}", message);
+}
+
+/// 运行时诊断消息（warn 级）
+pub(crate) fn warn(message: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(target: RUNTIME_TARGET, "{}", message);
+    #[cfg(not(feature = "tracing"))]
+    log::warn!(target: RUNTIME_TARGET, "{}", message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::lifecycle::SqlKind;
+
+    #[test]
+    fn test_span_without_subscriber_is_passthrough() {
+        let span = Span::sql(&StatementContext::new("app.UserDao", "findById", SqlKind::Select));
+        assert!(!span.is_enabled());
+        span.record_outcome(Duration::from_millis(3), || SqlOutcome::Fetched(1));
+        assert_eq!(span.in_scope(|| 42), 42);
+        let tx = Span::transaction(1, &TransactionOptions::default());
+        tx.record_end("commit");
+        assert_eq!(futures_util::FutureExt::now_or_never(tx.instrument(async { 7 })), Some(7));
+    }
+}
@@ -0,0 +1,413 @@
+//! 闭包式事务
+//!
+//! [`SqlSessionFactory::transaction`](crate::SqlSessionFactory::transaction) 以闭包界定事务边界：
+//! 闭包返回 `Ok` 提交，返回 `Err` 或 panic 回滚（panic 在回滚后继续向上传播）。
+//!
+//! 事务期间当前 task 持有一个「环境事务」（task-local）：
+//! - 嵌套的 `transaction` 调用按 [`Propagation`] 加入、创建 SAVEPOINT 或另开新事务；
+//! - [`SqlSessionFactory::current_session`](crate::SqlSessionFactory::current_session)
+//!   （`#[dao]` 生成的方法经由它获取 session）自动加入环境事务，使多个 DAO 调用共享同一事务。
+//!
+//! 环境事务只对当前 task 可见，`tokio::spawn` 出的子任务不继承。
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use futures_util::FutureExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::{Mutex, MutexGuard, OwnedMutexGuard};
+
+use crate::error::{MapperRuntimeError, Result};
+use crate::event::lifecycle::RetryScope;
+use crate::page::{Page, PageRequest};
+use crate::retry::{Retrier, RetryPolicy};
+use crate::session::SqlSession;
+use crate::session_factory::SqlSessionFactory;
+
+tokio::task_local! {
+    /// 当前 task 的环境事务
+    static CURRENT_TX: TxSession;
+}
+
+/// 事务隔离级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// SQL 关键字形式（如 `READ COMMITTED`）
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// 事务选项（[`SqlSession::begin_with`](crate::SqlSession::begin_with)）
+///
+/// 各方言对应的语句见 [`Dialect::begin_statements`](crate::Dialect::begin_statements)。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TransactionOptions {
+    /// 隔离级别（None 表示数据库默认）
+    pub isolation: Option<IsolationLevel>,
+    /// 只读事务：session 在执行 insert/update/delete 前即拒绝
+    pub read_only: bool,
+    /// 可延迟（仅 postgres 的 `SERIALIZABLE READ ONLY` 事务有效）
+    pub deferrable: bool,
+}
+
+impl TransactionOptions {
+    /// 默认选项
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置隔离级别
+    pub fn with_isolation(mut self, isolation: IsolationLevel) -> Self {
+        self.isolation = Some(isolation);
+        self
+    }
+
+    /// 设置只读
+    pub fn with_read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// 设置可延迟
+    pub fn with_deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+}
+
+/// 事务传播方式（嵌套调用 `transaction_with` 时如何对待已存在的环境事务）
+///
+/// 无环境事务时三者行为相同：开启新事务。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Propagation {
+    /// 加入已有事务（不建保存点）；闭包失败时将整个事务标记为仅回滚
+    Required,
+    /// 总是在新连接上开启独立事务，提交/回滚与外层互不影响
+    RequiresNew,
+    /// 在已有事务内创建 SAVEPOINT：闭包失败只回滚到保存点（默认）
+    #[default]
+    Nested,
+}
+
+/// 事务句柄：闭包式事务中传给闭包的 session 共享引用（可克隆，`'static`）
+///
+/// 提供与 [`SqlSession`] 同名的常用 CRUD 方法（每次调用短暂加锁）；
+/// 其余能力经 [`lock`](Self::lock) 取得 `&mut SqlSession` 使用。
+/// 持有 `lock()` 的守卫期间不要调用 DAO 方法或嵌套事务，否则会因重复加锁而挂起。
+#[derive(Clone)]
+pub struct TxSession {
+    factory_id: u64,
+    session: Arc<Mutex<SqlSession>>,
+    rollback_only: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for TxSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TxSession")
+            .field("factory_id", &self.factory_id)
+            .field("rollback_only", &self.is_rollback_only())
+            .finish()
+    }
+}
+
+impl TxSession {
+    fn new(factory_id: u64, session: SqlSession) -> Self {
+        Self {
+            factory_id,
+            session: Arc::new(Mutex::new(session)),
+            rollback_only: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// 当前 task 中属于指定工厂的环境事务
+    pub(crate) fn current(factory_id: u64) -> Option<TxSession> {
+        CURRENT_TX
+            .try_with(|tx| tx.clone())
+            .ok()
+            .filter(|tx| tx.factory_id == factory_id)
+    }
+
+    /// 独占访问底层 session
+    pub async fn lock(&self) -> MutexGuard<'_, SqlSession> {
+        self.session.lock().await
+    }
+
+    /// 将事务标记为仅回滚：最外层闭包即使返回 `Ok` 也会回滚
+    pub fn set_rollback_only(&self) {
+        self.rollback_only.store(true, Ordering::SeqCst);
+    }
+
+    /// 事务是否已被标记为仅回滚
+    pub fn is_rollback_only(&self) -> bool {
+        self.rollback_only.load(Ordering::SeqCst)
+    }
+
+    /// 登记事务提交成功后执行的回调，见 [`SqlSession::after_commit`]
+    ///
+    /// 加入外层事务（[`Propagation::Required`] / [`Propagation::Nested`]）时随外层事务提交执行；
+    /// 所在保存点被回滚时丢弃。
+    pub async fn after_commit<F>(&self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.lock().await.after_commit(hook)
+    }
+
+    /// 登记事务回滚后执行的回调，见 [`SqlSession::after_rollback`]
+    pub async fn after_rollback<F>(&self, hook: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.lock().await.after_rollback(hook)
+    }
+
+    /// 查询单行
+    pub async fn select_one<T: DeserializeOwned + Send>(
+        &self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<Option<T>> {
+        self.lock().await.select_one(namespace, statement_id, params).await
+    }
+
+    /// 查询多行
+    pub async fn select_list<T: DeserializeOwned + Send>(
+        &self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+    ) -> Result<Vec<T>> {
+        self.lock().await.select_list(namespace, statement_id, params).await
+    }
+
+    /// 分页查询
+    pub async fn select_page<T: DeserializeOwned + Send>(
+        &self,
+        namespace: &str,
+        statement_id: &str,
+        params: &HashMap<String, Value>,
+        request: PageRequest,
+    ) -> Result<Page<T>> {
+        self.lock()
+            .await
+            .select_page(namespace, statement_id, params, request)
+            .await
+    }
+
+    /// 插入
+    pub async fn insert<T: Serialize>(
+        &self,
+        namespace: &str,
+        statement_id: &str,
+        params: &T,
+    ) -> Result<Option<i64>> {
+        self.lock().await.insert(namespace, statement_id, params).await
+    }
+
+    /// 更新
+    pub async fn update<T: Serialize>(
+        &self,
+        namespace: &str,
+        statement_id: &str,
+        params: &T,
+    ) -> Result<u64> {
+        self.lock().await.update(namespace, statement_id, params).await
+    }
+
+    /// 删除
+    pub async fn delete<T: Serialize>(
+        &self,
+        namespace: &str,
+        statement_id: &str,
+        params: &T,
+    ) -> Result<u64> {
+        self.lock().await.delete(namespace, statement_id, params).await
+    }
+}
+
+/// [`SqlSessionFactory::current_session`](crate::SqlSessionFactory::current_session) 的返回值：
+/// 独立的新 session，或加入环境事务的 session 守卫。均可解引用为 `SqlSession`。
+pub enum ScopedSession {
+    /// 无环境事务：新开的独立 session
+    Owned(Box<SqlSession>),
+    /// 加入当前 task 的环境事务（持有期间独占该事务连接）
+    Joined(OwnedMutexGuard<SqlSession>),
+}
+
+impl ScopedSession {
+    /// 是否加入了环境事务
+    pub fn is_joined(&self) -> bool {
+        matches!(self, ScopedSession::Joined(_))
+    }
+}
+
+impl Deref for ScopedSession {
+    type Target = SqlSession;
+
+    fn deref(&self) -> &SqlSession {
+        match self {
+            ScopedSession::Owned(s) => s,
+            ScopedSession::Joined(g) => g,
+        }
+    }
+}
+
+impl DerefMut for ScopedSession {
+    fn deref_mut(&mut self) -> &mut SqlSession {
+        match self {
+            ScopedSession::Owned(s) => s,
+            ScopedSession::Joined(g) => g,
+        }
+    }
+}
+
+/// 取当前 session：有环境事务则加入，否则新开
+pub(crate) async fn current_session(factory: &SqlSessionFactory) -> ScopedSession {
+    match TxSession::current(factory.id()) {
+        Some(tx) => ScopedSession::Joined(tx.session.lock_owned().await),
+        None => ScopedSession::Owned(Box::new(factory.open_session())),
+    }
+}
+
+/// 按传播方式执行一个事务单元
+pub(crate) async fn run<F, Fut, R>(
+    factory: &SqlSessionFactory,
+    propagation: Propagation,
+    f: F,
+) -> Result<R>
+where
+    F: FnOnce(TxSession) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    match (propagation, TxSession::current(factory.id())) {
+        (Propagation::Required, Some(tx)) => run_joined(tx, f).await,
+        (Propagation::Nested, Some(tx)) => run_savepoint(tx, f).await,
+        _ => run_new(factory, f).await,
+    }
+}
+
+/// 可重试的事务单元：无环境事务时，新事务整体失败于瞬时错误则按策略重试
+pub(crate) async fn run_with_retry<F, Fut, R>(
+    factory: &SqlSessionFactory,
+    policy: &RetryPolicy,
+    mut f: F,
+) -> Result<R>
+where
+    F: FnMut(TxSession) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    if let Some(tx) = TxSession::current(factory.id()) {
+        return run_savepoint(tx, f).await;
+    }
+    let mut retrier = Retrier::new(policy, factory.event_bus());
+    loop {
+        match run_new(factory, &mut f).await {
+            Err(e) if retrier.should_retry(&e) => retrier.wait(&e, || RetryScope::Transaction).await,
+            result => return result,
+        }
+    }
+}
+
+/// 新事务：新 session + begin，闭包结果决定提交或回滚
+async fn run_new<F, Fut, R>(factory: &SqlSessionFactory, f: F) -> Result<R>
+where
+    F: FnOnce(TxSession) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    let mut session = factory.open_session();
+    session.begin().await?;
+    let tx = TxSession::new(factory.id(), session);
+    let outcome = AssertUnwindSafe(CURRENT_TX.scope(tx.clone(), f(tx.clone())))
+        .catch_unwind()
+        .await;
+
+    let mut session = tx.lock().await;
+    match outcome {
+        Ok(Ok(value)) if !tx.is_rollback_only() => {
+            session.finish_transaction(true).await?;
+            Ok(value)
+        }
+        Ok(Ok(_)) => {
+            session.finish_transaction(false).await?;
+            Err(MapperRuntimeError::Transaction(
+                "事务已被标记为仅回滚，已回滚".to_string(),
+            ))
+        }
+        Ok(Err(e)) => {
+            // 回滚失败不覆盖业务错误（连接归还时 sqlx 仍会回滚未完成事务）
+            let _ = session.finish_transaction(false).await;
+            Err(e)
+        }
+        Err(panic) => {
+            let _ = session.finish_transaction(false).await;
+            drop(session);
+            std::panic::resume_unwind(panic)
+        }
+    }
+}
+
+/// 加入已有事务：失败时标记仅回滚，由最外层决定回滚
+async fn run_joined<F, Fut, R>(tx: TxSession, f: F) -> Result<R>
+where
+    F: FnOnce(TxSession) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    match AssertUnwindSafe(f(tx.clone())).catch_unwind().await {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(e)) => {
+            tx.set_rollback_only();
+            Err(e)
+        }
+        Err(panic) => {
+            tx.set_rollback_only();
+            std::panic::resume_unwind(panic)
+        }
+    }
+}
+
+/// 嵌套事务：SAVEPOINT 包裹闭包，失败只回滚到保存点
+async fn run_savepoint<F, Fut, R>(tx: TxSession, f: F) -> Result<R>
+where
+    F: FnOnce(TxSession) -> Fut,
+    Fut: Future<Output = Result<R>>,
+{
+    let savepoint = tx.lock().await.savepoint().await?;
+    let outcome = AssertUnwindSafe(f(tx.clone())).catch_unwind().await;
+
+    let mut session = tx.lock().await;
+    match outcome {
+        Ok(Ok(value)) => {
+            session.release_savepoint(&savepoint).await?;
+            Ok(value)
+        }
+        Ok(Err(e)) => {
+            let _ = session.rollback_to_savepoint(&savepoint).await;
+            Err(e)
+        }
+        Err(panic) => {
+            let _ = session.rollback_to_savepoint(&savepoint).await;
+            drop(session);
+            std::panic::resume_unwind(panic)
+        }
+    }
+}
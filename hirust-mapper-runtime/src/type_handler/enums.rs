@@ -0,0 +1,184 @@
+//! 枚举类型处理器
+//!
+//! 枚举在 `serde_json::Value` 中统一表示为变体名字符串（与 serde 对单元变体的默认表示一致），
+//! 库中可按名称（[`EnumStringHandler`]）或序号（[`EnumOrdinalHandler`]）存储。
+//! 名称 ↔ 序号对照表由 [`MapperEnum`] 提供，通常经 `#[derive(MapperEnum)]` 生成。
+
+use std::marker::PhantomData;
+
+use serde_json::Value;
+use sqlx::any::{AnyArguments, AnyRow};
+use sqlx::{Arguments, Row};
+
+use crate::error::{MapperRuntimeError, Result};
+use crate::type_handler::trait_def::TypeHandler;
+
+/// 可映射的枚举：变体名 ↔ 序号对照表
+///
+/// 变体名须与枚举的 serde 表示一致（`#[serde(rename = ..)]` 时同步声明
+/// `#[mapper(name = ..)]`），否则结果无法反序列化为枚举。
+pub trait MapperEnum: Send + Sync + 'static {
+    /// 枚举类型名（处理器的 `type_name`）
+    const TYPE_NAME: &'static str;
+    /// `(变体名, 序号)`，按声明顺序
+    const VARIANTS: &'static [(&'static str, i64)];
+
+    /// 按变体名查序号
+    fn ordinal_of(name: &str) -> Option<i64> {
+        Self::VARIANTS.iter().find(|(n, _)| *n == name).map(|(_, o)| *o)
+    }
+
+    /// 按序号查变体名
+    fn name_of(ordinal: i64) -> Option<&'static str> {
+        Self::VARIANTS.iter().find(|(_, o)| *o == ordinal).map(|(n, _)| *n)
+    }
+}
+
+fn unknown_name<E: MapperEnum>(name: &str) -> MapperRuntimeError {
+    MapperRuntimeError::TypeConversion(format!("'{}' 不是 {} 的变体", name, E::TYPE_NAME))
+}
+
+fn unknown_ordinal<E: MapperEnum>(ordinal: i64) -> MapperRuntimeError {
+    MapperRuntimeError::TypeConversion(format!("序号 {} 不对应 {} 的任何变体", ordinal, E::TYPE_NAME))
+}
+
+/// 参数值 → 变体名（接受变体名或序号）
+fn variant_name<E: MapperEnum>(value: &Value) -> Result<&'static str> {
+    match value {
+        Value::String(s) => E::VARIANTS
+            .iter()
+            .find(|(n, _)| n == s)
+            .map(|(n, _)| *n)
+            .ok_or_else(|| unknown_name::<E>(s)),
+        Value::Number(n) => {
+            let ordinal = n.as_i64().ok_or_else(|| unknown_name::<E>(&n.to_string()))?;
+            E::name_of(ordinal).ok_or_else(|| unknown_ordinal::<E>(ordinal))
+        }
+        other => Err(MapperRuntimeError::TypeConversion(format!(
+            "{} 枚举处理器无法绑定 {:?}（期望变体名或序号）", E::TYPE_NAME, other
+        ))),
+    }
+}
+
+fn check_add(r: std::result::Result<(), sqlx::error::BoxDynError>, type_name: &str) -> Result<()> {
+    r.map_err(|e| MapperRuntimeError::TypeConversion(format!("{} 处理器绑定参数失败: {}", type_name, e)))
+}
+
+/// 按变体名存储的枚举处理器（VARCHAR 列）
+pub struct EnumStringHandler<E>(PhantomData<fn() -> E>);
+
+impl<E: MapperEnum> EnumStringHandler<E> {
+    /// 创建处理器（按 `E` 的变体名读写）
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: MapperEnum> Default for EnumStringHandler<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: MapperEnum> TypeHandler for EnumStringHandler<E> {
+    fn type_name(&self) -> &'static str {
+        E::TYPE_NAME
+    }
+
+    fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+        let v: Option<String> = row.try_get(column).map_err(|e| {
+            MapperRuntimeError::TypeConversion(format!("读取 {} 列 '{}': {}", E::TYPE_NAME, column, e))
+        })?;
+        match v {
+            Some(name) => variant_name::<E>(&Value::String(name)).map(Value::from),
+            None => Ok(Value::Null),
+        }
+    }
+
+    fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+        if value.is_null() {
+            return check_add(arguments.add(Option::<String>::None), E::TYPE_NAME);
+        }
+        check_add(arguments.add(variant_name::<E>(value)?), E::TYPE_NAME)
+    }
+}
+
+/// 按序号存储的枚举处理器（整数列）
+pub struct EnumOrdinalHandler<E>(PhantomData<fn() -> E>);
+
+impl<E: MapperEnum> EnumOrdinalHandler<E> {
+    /// 创建处理器（按 `E` 的序号读写）
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: MapperEnum> Default for EnumOrdinalHandler<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: MapperEnum> TypeHandler for EnumOrdinalHandler<E> {
+    fn type_name(&self) -> &'static str {
+        E::TYPE_NAME
+    }
+
+    fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+        let v: Option<i64> = row.try_get(column).map_err(|e| {
+            MapperRuntimeError::TypeConversion(format!("读取 {} 列 '{}': {}", E::TYPE_NAME, column, e))
+        })?;
+        match v {
+            Some(ordinal) => E::name_of(ordinal)
+                .map(Value::from)
+                .ok_or_else(|| unknown_ordinal::<E>(ordinal)),
+            None => Ok(Value::Null),
+        }
+    }
+
+    fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+        if value.is_null() {
+            return check_add(arguments.add(Option::<i64>::None), E::TYPE_NAME);
+        }
+        let name = variant_name::<E>(value)?;
+        let ordinal = E::ordinal_of(name).ok_or_else(|| unknown_name::<E>(name))?;
+        check_add(arguments.add(ordinal), E::TYPE_NAME)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    struct Level;
+
+    impl MapperEnum for Level {
+        const TYPE_NAME: &'static str = "Level";
+        const VARIANTS: &'static [(&'static str, i64)] = &[("Low", 1), ("High", 5)];
+    }
+
+    #[test]
+    fn test_lookup_tables() {
+        assert_eq!(Level::ordinal_of("High"), Some(5));
+        assert_eq!(Level::name_of(1), Some("Low"));
+        assert_eq!(Level::name_of(2), None);
+    }
+
+    #[test]
+    fn test_set_parameter_accepts_name_or_ordinal() {
+        let mut args = AnyArguments::default();
+        EnumOrdinalHandler::<Level>::new().set_parameter(&json!("High"), &mut args).unwrap();
+        EnumOrdinalHandler::<Level>::new().set_parameter(&json!(1), &mut args).unwrap();
+        EnumStringHandler::<Level>::new().set_parameter(&json!(5), &mut args).unwrap();
+        EnumStringHandler::<Level>::new().set_parameter(&json!(null), &mut args).unwrap();
+        assert_eq!(args.len(), 4);
+
+        let err = EnumStringHandler::<Level>::new()
+            .set_parameter(&json!("Medium"), &mut args)
+            .unwrap_err();
+        assert!(err.to_string().contains("'Medium' 不是 Level 的变体"), "{}", err);
+        assert!(EnumOrdinalHandler::<Level>::new().set_parameter(&json!(3), &mut args).is_err());
+        assert!(EnumOrdinalHandler::<Level>::new().set_parameter(&json!(true), &mut args).is_err());
+    }
+}
@@ -3,18 +3,28 @@
 //! 在 `serde_json::Value` 与数据库列值之间双向转换。
 //!
 //! - [`TypeHandler`]：类型处理器 trait
-//! - 标准实现：`I64Handler` / `I32Handler` / `F64Handler` / `BoolHandler` / `StringHandler`
-//! - feature-gated：`ChronoHandler`（`chrono`）/ `UuidHandler`（`uuid`）
+//! - 标准实现：`I64Handler` / `I32Handler` / `U64Handler` / `F64Handler` / `BoolHandler` /
+//!   `StringHandler` / `BytesHandler`（结果编码见 [`BinaryEncoding`]）
+//! - feature-gated：`ChronoHandler` / `ChronoNaiveHandler` / `ChronoDateHandler`（`chrono`）、
+//!   `TimeHandler` / `TimeDateHandler`（`time`）、`UuidHandler`（`uuid`）、`DecimalHandler`（`rust_decimal`）
+//! - 枚举：`EnumStringHandler` / `EnumOrdinalHandler`（名称 ↔ 序号表见 [`MapperEnum`]）
 //! - [`TypeHandlerRegistry`]：按类型名查找处理器
 
+pub mod enums;
 pub mod standard;
 pub mod trait_def;
 
+pub use enums::{EnumOrdinalHandler, EnumStringHandler, MapperEnum};
 pub use standard::{
-    BoolHandler, F64Handler, I32Handler, I64Handler, StringHandler, TypeHandlerRegistry,
+    BinaryEncoding, BoolHandler, BytesHandler, F64Handler, I32Handler, I64Handler, StringHandler,
+    TypeHandlerRegistry, U64Handler,
 };
+#[cfg(feature = "rust_decimal")]
+pub use standard::DecimalHandler;
 #[cfg(feature = "chrono")]
-pub use standard::ChronoHandler;
+pub use standard::{ChronoDateHandler, ChronoHandler, ChronoNaiveHandler};
+#[cfg(feature = "time")]
+pub use standard::{TimeDateHandler, TimeHandler};
 #[cfg(feature = "uuid")]
 pub use standard::UuidHandler;
 pub use trait_def::TypeHandler;
@@ -1,13 +1,15 @@
 //! 内置类型处理器与注册表
 //!
-//! 提供 i32 / i64 / String / bool / f64 五种基础类型的标准处理器，
-//! 以及 feature-gated 的 `chrono`（日期时间）与 `uuid` 处理器。
+//! 提供 i32 / i64 / u64 / String / bool / f64 基础类型与二进制（bytes）的标准处理器，
+//! 以及 feature-gated 的 `chrono` / `time`（日期时间）、`uuid` 与 `rust_decimal`（精确小数）处理器。
 //!
 //! [`TypeHandlerRegistry`] 持有这些处理器，供 ResultSetHandler 按类型名查找。
 
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use base64::Engine;
+
 use crate::error::{MapperRuntimeError, Result};
 use crate::type_handler::trait_def::TypeHandler;
 use serde_json::{Number, Value};
@@ -15,6 +17,7 @@ use sqlx::any::{AnyArguments, AnyRow, AnyTypeInfoKind};
 use sqlx::Arguments;
 use sqlx::Column;
 use sqlx::Row;
+use sqlx::ValueRef;
 
 // ─── 辅助：读取列的类型种类 ─────────────────────────────────────────
 
@@ -30,6 +33,14 @@ fn column_kind(row: &AnyRow, column: &str) -> Result<AnyTypeInfoKind> {
     )))
 }
 
+/// 取列中**实际值**的类型种类（计算列、sqlite 的 NUMERIC 等声明类型与实际存储不一致时使用）
+fn value_kind(row: &AnyRow, column: &str) -> Result<AnyTypeInfoKind> {
+    let raw = row
+        .try_get_raw(column)
+        .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取列 '{}': {}", column, e)))?;
+    Ok(raw.type_info().kind())
+}
+
 /// 将 add 的 BoxDynError 结果转为 MapperRuntimeError
 fn check_add(
     r: std::result::Result<(), sqlx::error::BoxDynError>,
@@ -82,6 +93,64 @@ impl TypeHandler for I64Handler {
     }
 }
 
+// ─── u64 处理器 ────────────────────────────────────────────────────
+
+/// u64 类型处理器
+///
+/// 驱动层整数统一为 i64：不超过 `i64::MAX` 的值按整数绑定，更大的值以十进制文本绑定
+///（sqlite / mysql 按列类型隐式转换；postgres 不隐式转换文本参数，须在 SQL 中显式 `CAST`）；
+/// 读取时兼容整数列与十进制文本列。
+pub struct U64Handler;
+
+impl TypeHandler for U64Handler {
+    fn type_name(&self) -> &'static str {
+        "u64"
+    }
+
+    fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+        if value_kind(row, column)? == AnyTypeInfoKind::Text {
+            let v: Option<String> = row
+                .try_get(column)
+                .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 u64 列 '{}': {}", column, e)))?;
+            return v
+                .map(|s| {
+                    s.trim().parse::<u64>().map(Value::from).map_err(|e| {
+                        MapperRuntimeError::TypeConversion(format!("解析 u64 '{}' 失败: {}", s, e))
+                    })
+                })
+                .unwrap_or(Ok(Value::Null));
+        }
+        let v: Option<i64> = row
+            .try_get(column)
+            .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 u64 列 '{}': {}", column, e)))?;
+        match v {
+            Some(x) if x < 0 => Err(MapperRuntimeError::TypeConversion(format!(
+                "u64 列 '{}' 含负数 {}", column, x
+            ))),
+            Some(x) => Ok(Value::from(x as u64)),
+            None => Ok(Value::Null),
+        }
+    }
+
+    fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+        let n = match value {
+            Value::Null => return check_add(arguments.add(Option::<i64>::None), "u64"),
+            Value::Number(n) => n.as_u64(),
+            Value::String(s) => s.trim().parse::<u64>().ok(),
+            _ => None,
+        };
+        match n {
+            Some(n) => match i64::try_from(n) {
+                Ok(i) => check_add(arguments.add(i), "u64"),
+                Err(_) => check_add(arguments.add(n.to_string()), "u64"),
+            },
+            None => Err(MapperRuntimeError::TypeConversion(format!(
+                "u64 处理器无法绑定 {:?}（仅接受非负整数或 null）", value
+            ))),
+        }
+    }
+}
+
 // ─── i32 处理器 ────────────────────────────────────────────────────
 
 /// i32 类型处理器
@@ -183,6 +252,13 @@ impl TypeHandler for BoolHandler {
     }
 
     fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+        // sqlite / mysql 以整数 0/1 存储布尔
+        if column_kind(row, column)?.is_integer() {
+            let v: Option<i64> = row
+                .try_get(column)
+                .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 bool 列 '{}': {}", column, e)))?;
+            return Ok(v.map(|x| Value::Bool(x != 0)).unwrap_or(Value::Null));
+        }
         let v: Option<bool> = row
             .try_get(column)
             .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 bool 列 '{}': {}", column, e)))?;
@@ -241,14 +317,192 @@ impl TypeHandler for StringHandler {
     }
 }
 
+// ─── 二进制处理器 ──────────────────────────────────────────────────
+
+/// 二进制列（Blob）在 `serde_json::Value` 中的表示（对应 `[settings] binary_encoding`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BinaryEncoding {
+    /// 标准 base64 字符串（默认）
+    #[default]
+    Base64,
+    /// 字节数组 `[0, 255, ...]`，可直接反序列化为 `Vec<u8>` / `serde_bytes::ByteBuf`
+    Array,
+}
+
+impl BinaryEncoding {
+    /// 将字节编码为 `Value`
+    pub fn encode(self, bytes: &[u8]) -> Value {
+        match self {
+            BinaryEncoding::Base64 => {
+                Value::String(base64::engine::general_purpose::STANDARD.encode(bytes))
+            }
+            BinaryEncoding::Array => Value::Array(bytes.iter().map(|b| Value::from(*b)).collect()),
+        }
+    }
+
+    /// 将 `Value` 还原为字节：接受字节数组或 base64 字符串（两种编码均可），`Null` 为 `None`
+    pub fn decode(value: &Value) -> Result<Option<Vec<u8>>> {
+        match value {
+            Value::Null => Ok(None),
+            Value::String(s) => base64::engine::general_purpose::STANDARD
+                .decode(s)
+                .map(Some)
+                .map_err(|e| MapperRuntimeError::TypeConversion(format!("无效的 base64 字节串: {}", e))),
+            Value::Array(items) => items
+                .iter()
+                .map(|item| {
+                    item.as_u64().and_then(|n| u8::try_from(n).ok()).ok_or_else(|| {
+                        MapperRuntimeError::TypeConversion(format!("字节数组含非 u8 元素: {}", item))
+                    })
+                })
+                .collect::<Result<Vec<u8>>>()
+                .map(Some),
+            other => Err(MapperRuntimeError::TypeConversion(format!(
+                "bytes 处理器无法绑定 {:?}（仅接受字节数组、base64 字符串或 null）", other
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for BinaryEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BinaryEncoding::Base64 => write!(f, "base64"),
+            BinaryEncoding::Array => write!(f, "array"),
+        }
+    }
+}
+
+/// 二进制处理器（Blob 列 / `Vec<u8>` 参数）
+///
+/// 读取时按 [`BinaryEncoding`] 编码；绑定时直接以 `Vec<u8>` 传给驱动（不经 JSON 文本）。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BytesHandler {
+    encoding: BinaryEncoding,
+}
+
+impl BytesHandler {
+    /// 指定结果编码创建
+    pub fn new(encoding: BinaryEncoding) -> Self {
+        Self { encoding }
+    }
+
+    /// 结果编码
+    pub fn encoding(&self) -> BinaryEncoding {
+        self.encoding
+    }
+}
+
+impl TypeHandler for BytesHandler {
+    fn type_name(&self) -> &'static str {
+        "bytes"
+    }
+
+    fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+        // 以文本存储的列按 UTF-8 字节读取
+        if value_kind(row, column)? == AnyTypeInfoKind::Text {
+            let v: Option<String> = row
+                .try_get(column)
+                .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 bytes 列 '{}': {}", column, e)))?;
+            return Ok(v.map(|s| self.encoding.encode(s.as_bytes())).unwrap_or(Value::Null));
+        }
+        let v: Option<Vec<u8>> = row
+            .try_get(column)
+            .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 bytes 列 '{}': {}", column, e)))?;
+        Ok(v.map(|b| self.encoding.encode(&b)).unwrap_or(Value::Null))
+    }
+
+    fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+        check_add(arguments.add(BinaryEncoding::decode(value)?), "bytes")
+    }
+}
+
+// ─── feature-gated: 日期时间公共解析 ───────────────────────────────
+
+/// 日期时间列 / 参数的原始表示：文本（RFC3339、`YYYY-MM-DD HH:MM:SS[.f]`、`YYYY-MM-DD`）
+/// 或整数 Unix 时间戳（秒，如 sqlite 的 `unixepoch()`）
+#[cfg(any(feature = "chrono", feature = "time"))]
+enum RawTemporal {
+    Text(String),
+    Seconds(i64),
+}
+
+#[cfg(any(feature = "chrono", feature = "time"))]
+impl RawTemporal {
+    /// 按列中实际值的类型读取（sqlite 的日期列声明类型不可靠）
+    fn read(row: &AnyRow, column: &str, type_name: &str) -> Result<Option<Self>> {
+        let read_err = |e: sqlx::Error| {
+            MapperRuntimeError::TypeConversion(format!("读取 {} 列 '{}': {}", type_name, column, e))
+        };
+        match value_kind(row, column)? {
+            AnyTypeInfoKind::Null => Ok(None),
+            kind if kind.is_integer() => Ok(row.try_get::<Option<i64>, _>(column).map_err(read_err)?.map(Self::Seconds)),
+            AnyTypeInfoKind::Text => Ok(row.try_get::<Option<String>, _>(column).map_err(read_err)?.map(Self::Text)),
+            other => Err(MapperRuntimeError::TypeConversion(format!(
+                "{} 列 '{}' 的值类型 {:?} 无法解析为日期时间", type_name, column, other
+            ))),
+        }
+    }
+
+    /// 参数值：字符串或整数时间戳，`null` 为 None
+    fn from_param(value: &Value, type_name: &str) -> Result<Option<Self>> {
+        match value {
+            Value::Null => Ok(None),
+            Value::String(s) => Ok(Some(Self::Text(s.clone()))),
+            Value::Number(n) if n.as_i64().is_some() => Ok(n.as_i64().map(Self::Seconds)),
+            other => Err(MapperRuntimeError::TypeConversion(format!(
+                "{} 处理器无法绑定 {:?}（期望日期时间字符串或 Unix 时间戳）", type_name, other
+            ))),
+        }
+    }
+
+    fn invalid(&self, type_name: &str) -> MapperRuntimeError {
+        let shown = match self {
+            Self::Text(t) => t.clone(),
+            Self::Seconds(s) => s.to_string(),
+        };
+        MapperRuntimeError::TypeConversion(format!("无法将 '{}' 解析为 {}", shown, type_name))
+    }
+}
+
 // ─── feature-gated: chrono 处理器 ──────────────────────────────────
 
 #[cfg(feature = "chrono")]
 mod chrono_handler {
     use super::*;
-    use chrono::{DateTime, Utc};
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+
+    /// 绑定参数时的文本格式：与 sqlite `CURRENT_TIMESTAMP`、mysql `DATETIME` 字面量一致
+    const SQL_DATETIME: &str = "%Y-%m-%d %H:%M:%S%.f";
+
+    /// 原始值 → UTC 时刻（无时区的文本按 UTC 解释）
+    fn to_utc(raw: &RawTemporal, type_name: &str) -> Result<DateTime<Utc>> {
+        let parsed = match raw {
+            RawTemporal::Seconds(secs) => DateTime::from_timestamp(*secs, 0),
+            RawTemporal::Text(text) => {
+                let text = text.trim();
+                DateTime::parse_from_rfc3339(text)
+                    .or_else(|_| DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z"))
+                    .map(|dt| dt.to_utc())
+                    .ok()
+                    .or_else(|| {
+                        ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+                            .iter()
+                            .find_map(|fmt| NaiveDateTime::parse_from_str(text, fmt).ok())
+                            .or_else(|| NaiveDate::parse_from_str(text, "%Y-%m-%d").ok().map(|d| d.and_time(Default::default())))
+                            .map(|n| n.and_utc())
+                    })
+            }
+        };
+        parsed.ok_or_else(|| raw.invalid(type_name))
+    }
 
-    /// chrono::DateTime<Utc> 处理器（以 RFC3339 字符串存储）
+    /// `chrono::DateTime<Tz>` 处理器
+    ///
+    /// 读取兼容 RFC3339、`YYYY-MM-DD HH:MM:SS[.f][±HH]`、纯日期与整数 Unix 时间戳，
+    /// 结果归一为 UTC 的 RFC3339 字符串（`2024-01-02T03:04:05Z`）；绑定为 UTC 的
+    /// `YYYY-MM-DD HH:MM:SS[.f]` 文本（Any 驱动无原生日期参数类型）。
     pub struct ChronoHandler;
 
     impl TypeHandler for ChronoHandler {
@@ -257,45 +511,217 @@ mod chrono_handler {
         }
 
         fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
-            let s: Option<String> = row
-                .try_get(column)
-                .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 chrono 列 '{}': {}", column, e)))?;
-            match s {
-                Some(text) => {
-                    let dt: DateTime<Utc> = text.parse().map_err(|e| {
-                        MapperRuntimeError::TypeConversion(format!("解析日期时间 '{}' 失败: {}", text, e))
-                    })?;
-                    Ok(Value::String(dt.to_rfc3339()))
-                }
+            match RawTemporal::read(row, column, "chrono")? {
+                Some(raw) => Ok(Value::String(to_utc(&raw, "DateTime")?.to_rfc3339_opts(SecondsFormat::AutoSi, true))),
                 None => Ok(Value::Null),
             }
         }
 
         fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
-            match value {
-                Value::String(s) => {
-                    // 验证可解析
-                    let dt: DateTime<Utc> = s.parse().map_err(|e| {
-                        MapperRuntimeError::TypeConversion(format!("无效的日期时间 '{}': {}", s, e))
-                    })?;
-                    check_add(arguments.add(dt.to_rfc3339()), "chrono")?;
-                }
-                Value::Null => {
-                    check_add(arguments.add(Option::<String>::None), "chrono")?;
-                }
-                other => {
-                    return Err(MapperRuntimeError::TypeConversion(format!(
-                        "chrono 处理器无法绑定 {:?}（期望 RFC3339 字符串）", other
-                    )));
+            let text = RawTemporal::from_param(value, "chrono")?
+                .map(|raw| to_utc(&raw, "DateTime").map(|dt| dt.format(SQL_DATETIME).to_string()))
+                .transpose()?;
+            check_add(arguments.add(text), "chrono")
+        }
+    }
+
+    /// `chrono::NaiveDateTime` 处理器
+    ///
+    /// 结果为无时区的 `YYYY-MM-DDTHH:MM:SS[.f]`（带时区的文本先换算为 UTC），
+    /// 绑定为 `YYYY-MM-DD HH:MM:SS[.f]` 文本。
+    pub struct ChronoNaiveHandler;
+
+    impl TypeHandler for ChronoNaiveHandler {
+        fn type_name(&self) -> &'static str {
+            "chrono_naive"
+        }
+
+        fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+            match RawTemporal::read(row, column, "chrono_naive")? {
+                Some(raw) => {
+                    let naive = to_utc(&raw, "NaiveDateTime")?.naive_utc();
+                    Ok(Value::String(naive.format("%Y-%m-%dT%H:%M:%S%.f").to_string()))
                 }
+                None => Ok(Value::Null),
+            }
+        }
+
+        fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+            let text = RawTemporal::from_param(value, "chrono_naive")?
+                .map(|raw| to_utc(&raw, "NaiveDateTime").map(|dt| dt.naive_utc().format(SQL_DATETIME).to_string()))
+                .transpose()?;
+            check_add(arguments.add(text), "chrono_naive")
+        }
+    }
+
+    /// `chrono::NaiveDate` 处理器（结果与绑定均为 `YYYY-MM-DD`）
+    pub struct ChronoDateHandler;
+
+    fn to_date(raw: &RawTemporal) -> Result<NaiveDate> {
+        // 纯日期文本直接解析，避免带时区的日期时间跨日换算
+        if let RawTemporal::Text(text) = raw
+            && let Ok(date) = NaiveDate::parse_from_str(text.trim(), "%Y-%m-%d")
+        {
+            return Ok(date);
+        }
+        to_utc(raw, "NaiveDate").map(|dt| dt.date_naive())
+    }
+
+    impl TypeHandler for ChronoDateHandler {
+        fn type_name(&self) -> &'static str {
+            "chrono_date"
+        }
+
+        fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+            match RawTemporal::read(row, column, "chrono_date")? {
+                Some(raw) => Ok(Value::String(to_date(&raw)?.format("%Y-%m-%d").to_string())),
+                None => Ok(Value::Null),
             }
-            Ok(())
+        }
+
+        fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+            let text = RawTemporal::from_param(value, "chrono_date")?
+                .map(|raw| to_date(&raw).map(|d| d.format("%Y-%m-%d").to_string()))
+                .transpose()?;
+            check_add(arguments.add(text), "chrono_date")
         }
     }
 }
 
 #[cfg(feature = "chrono")]
-pub use chrono_handler::ChronoHandler;
+pub use chrono_handler::{ChronoDateHandler, ChronoHandler, ChronoNaiveHandler};
+
+// ─── feature-gated: time 处理器 ────────────────────────────────────
+
+#[cfg(feature = "time")]
+mod time_handler {
+    use super::*;
+    use time::format_description::well_known::Rfc3339;
+    use time::macros::format_description;
+    use time::{Date, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+    /// 原始值 → UTC 时刻（无时区的文本按 UTC 解释）
+    fn to_utc(raw: &RawTemporal, type_name: &str) -> Result<OffsetDateTime> {
+        let parsed = match raw {
+            RawTemporal::Seconds(secs) => OffsetDateTime::from_unix_timestamp(*secs).ok(),
+            RawTemporal::Text(text) => {
+                let text = text.trim();
+                OffsetDateTime::parse(text, &Rfc3339)
+                    .or_else(|_| {
+                        OffsetDateTime::parse(
+                            text,
+                            format_description!(
+                                "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]][offset_hour sign:mandatory][optional [:[offset_minute]]]"
+                            ),
+                        )
+                    })
+                    .ok()
+                    .or_else(|| {
+                        PrimitiveDateTime::parse(
+                            text,
+                            format_description!("[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]]"),
+                        )
+                        .or_else(|_| {
+                            PrimitiveDateTime::parse(
+                                text,
+                                format_description!("[year]-[month]-[day]T[hour]:[minute]:[second][optional [.[subsecond]]]"),
+                            )
+                        })
+                        .ok()
+                        .or_else(|| {
+                            Date::parse(text, format_description!("[year]-[month]-[day]"))
+                                .ok()
+                                .map(|d| d.midnight())
+                        })
+                        .map(|p| p.assume_utc())
+                    })
+                    .map(|dt| dt.to_offset(UtcOffset::UTC))
+            }
+        };
+        parsed.ok_or_else(|| raw.invalid(type_name))
+    }
+
+    /// UTC 时刻 → 绑定文本 `YYYY-MM-DD HH:MM:SS[.f]`（与 chrono 处理器一致）
+    fn sql_datetime(dt: OffsetDateTime) -> Result<String> {
+        let formatted = if dt.nanosecond() == 0 {
+            dt.format(format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"))
+        } else {
+            dt.format(format_description!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"))
+        };
+        formatted.map_err(|e| MapperRuntimeError::TypeConversion(format!("格式化日期时间失败: {}", e)))
+    }
+
+    /// `time::OffsetDateTime` 处理器
+    ///
+    /// 读取规则同 `ChronoHandler`，结果归一为 UTC 的
+    /// RFC3339 字符串——反序列化字段需声明 `#[serde(with = "time::serde::rfc3339")]`。
+    pub struct TimeHandler;
+
+    impl TypeHandler for TimeHandler {
+        fn type_name(&self) -> &'static str {
+            "time"
+        }
+
+        fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+            match RawTemporal::read(row, column, "time")? {
+                Some(raw) => {
+                    let text = to_utc(&raw, "OffsetDateTime")?
+                        .format(&Rfc3339)
+                        .map_err(|e| MapperRuntimeError::TypeConversion(format!("格式化日期时间失败: {}", e)))?;
+                    Ok(Value::String(text))
+                }
+                None => Ok(Value::Null),
+            }
+        }
+
+        fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+            let text = RawTemporal::from_param(value, "time")?
+                .map(|raw| to_utc(&raw, "OffsetDateTime").and_then(sql_datetime))
+                .transpose()?;
+            check_add(arguments.add(text), "time")
+        }
+    }
+
+    /// `time::Date` 处理器（结果与绑定均为 `YYYY-MM-DD`）
+    pub struct TimeDateHandler;
+
+    fn to_date(raw: &RawTemporal) -> Result<Date> {
+        if let RawTemporal::Text(text) = raw
+            && let Ok(date) = Date::parse(text.trim(), format_description!("[year]-[month]-[day]"))
+        {
+            return Ok(date);
+        }
+        to_utc(raw, "Date").map(|dt| dt.date())
+    }
+
+    fn format_date(date: Date) -> Result<String> {
+        date.format(format_description!("[year]-[month]-[day]"))
+            .map_err(|e| MapperRuntimeError::TypeConversion(format!("格式化日期失败: {}", e)))
+    }
+
+    impl TypeHandler for TimeDateHandler {
+        fn type_name(&self) -> &'static str {
+            "time_date"
+        }
+
+        fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+            match RawTemporal::read(row, column, "time_date")? {
+                Some(raw) => Ok(Value::String(format_date(to_date(&raw)?)?)),
+                None => Ok(Value::Null),
+            }
+        }
+
+        fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+            let text = RawTemporal::from_param(value, "time_date")?
+                .map(|raw| to_date(&raw).and_then(format_date))
+                .transpose()?;
+            check_add(arguments.add(text), "time_date")
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+pub use time_handler::{TimeDateHandler, TimeHandler};
 
 // ─── feature-gated: uuid 处理器 ────────────────────────────────────
 
@@ -303,60 +729,139 @@ pub use chrono_handler::ChronoHandler;
 mod uuid_handler {
     use super::*;
 
-    /// uuid::Uuid 处理器（以字符串存储）
+    /// uuid::Uuid 处理器
+    ///
+    /// 读取兼容文本（任意大小写、simple / braced / urn 写法）与 16 字节二进制（如 mysql `BINARY(16)`），
+    /// 结果归一为小写连字符格式；绑定为连字符格式文本。
     pub struct UuidHandler;
 
+    fn parse_uuid(text: &str) -> Result<uuid::Uuid> {
+        text.trim()
+            .parse()
+            .map_err(|e| MapperRuntimeError::TypeConversion(format!("无效的 UUID '{}': {}", text, e)))
+    }
+
     impl TypeHandler for UuidHandler {
         fn type_name(&self) -> &'static str {
             "uuid"
         }
 
         fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
-            let s: Option<String> = row
-                .try_get(column)
-                .map_err(|e| MapperRuntimeError::TypeConversion(format!("读取 uuid 列 '{}': {}", column, e)))?;
-            match s {
-                Some(text) => {
-                    let _u: uuid::Uuid = text.parse().map_err(|e| {
-                        MapperRuntimeError::TypeConversion(format!("无效的 UUID '{}': {}", text, e))
-                    })?;
-                    Ok(Value::String(text))
+            let read_err = |e: sqlx::Error| {
+                MapperRuntimeError::TypeConversion(format!("读取 uuid 列 '{}': {}", column, e))
+            };
+            let uuid = match value_kind(row, column)? {
+                AnyTypeInfoKind::Null => None,
+                AnyTypeInfoKind::Blob => match row.try_get::<Option<Vec<u8>>, _>(column).map_err(read_err)? {
+                    Some(bytes) => Some(uuid::Uuid::from_slice(&bytes).map_err(|e| {
+                        MapperRuntimeError::TypeConversion(format!("列 '{}' 不是 16 字节 UUID: {}", column, e))
+                    })?),
+                    None => None,
+                },
+                _ => row
+                    .try_get::<Option<String>, _>(column)
+                    .map_err(read_err)?
+                    .map(|text| parse_uuid(&text))
+                    .transpose()?,
+            };
+            Ok(uuid.map(|u| Value::String(u.hyphenated().to_string())).unwrap_or(Value::Null))
+        }
+
+        fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
+            match value {
+                Value::String(s) => check_add(arguments.add(parse_uuid(s)?.hyphenated().to_string()), "uuid"),
+                Value::Null => check_add(arguments.add(Option::<String>::None), "uuid"),
+                other => Err(MapperRuntimeError::TypeConversion(format!(
+                    "uuid 处理器无法绑定 {:?}", other
+                ))),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+pub use uuid_handler::UuidHandler;
+
+// ─── feature-gated: rust_decimal 处理器 ────────────────────────────
+
+#[cfg(feature = "rust_decimal")]
+mod decimal_handler {
+    use super::*;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    /// rust_decimal::Decimal 处理器（精确小数，不经浮点）
+    ///
+    /// 读取时兼容文本、整数与浮点列（浮点按最短往返表示转为十进制，如 sqlite 的 NUMERIC 亲和列）；
+    /// 结果为十进制字符串，启用 `arbitrary_precision` 时为保留全部位数的 JSON 数字。
+    /// 绑定时以十进制文本传给驱动：sqlite / mysql 按列类型隐式转换；postgres 不会把文本参数隐式转换为
+    /// NUMERIC，须在 SQL 中显式转换（如 `CAST(#{amount, rustType=decimal} AS NUMERIC)`）。
+    pub struct DecimalHandler;
+
+    /// 十进制值 → `Value`（见 [`DecimalHandler`] 的结果表示）
+    fn decimal_value(d: Decimal) -> Value {
+        #[cfg(feature = "arbitrary_precision")]
+        if let Ok(n) = Number::from_str(&d.to_string()) {
+            return Value::Number(n);
+        }
+        Value::String(d.to_string())
+    }
+
+    fn parse_decimal(text: &str) -> Result<Decimal> {
+        let text = text.trim();
+        Decimal::from_str(text)
+            .or_else(|_| Decimal::from_scientific(text))
+            .map_err(|e| MapperRuntimeError::TypeConversion(format!("解析小数 '{}' 失败: {}", text, e)))
+    }
+
+    impl TypeHandler for DecimalHandler {
+        fn type_name(&self) -> &'static str {
+            "decimal"
+        }
+
+        fn get_result(&self, row: &AnyRow, column: &str) -> Result<Value> {
+            let read_err = |e: sqlx::Error| {
+                MapperRuntimeError::TypeConversion(format!("读取 decimal 列 '{}': {}", column, e))
+            };
+            let text = match value_kind(row, column)? {
+                AnyTypeInfoKind::Null => None,
+                kind if kind.is_integer() => row.try_get::<Option<i64>, _>(column).map_err(read_err)?.map(|i| i.to_string()),
+                AnyTypeInfoKind::Real | AnyTypeInfoKind::Double => {
+                    row.try_get::<Option<f64>, _>(column).map_err(read_err)?.map(|f| f.to_string())
                 }
+                _ => row.try_get::<Option<String>, _>(column).map_err(read_err)?,
+            };
+            match text {
+                Some(t) => Ok(decimal_value(parse_decimal(&t)?)),
                 None => Ok(Value::Null),
             }
         }
 
         fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> Result<()> {
-            match value {
-                Value::String(s) => {
-                    let u: uuid::Uuid = s.parse().map_err(|e| {
-                        MapperRuntimeError::TypeConversion(format!("无效的 UUID '{}': {}", s, e))
-                    })?;
-                    check_add(arguments.add(u.to_string()), "uuid")?;
-                }
-                Value::Null => {
-                    check_add(arguments.add(Option::<String>::None), "uuid")?;
-                }
+            let decimal = match value {
+                Value::Null => return check_add(arguments.add(Option::<String>::None), "decimal"),
+                Value::String(s) => parse_decimal(s)?,
+                Value::Number(n) => parse_decimal(&n.to_string())?,
                 other => {
                     return Err(MapperRuntimeError::TypeConversion(format!(
-                        "uuid 处理器无法绑定 {:?}", other
+                        "decimal 处理器无法绑定 {:?}（期望数字或十进制字符串）", other
                     )));
                 }
-            }
-            Ok(())
+            };
+            check_add(arguments.add(decimal.to_string()), "decimal")
         }
     }
 }
 
-#[cfg(feature = "uuid")]
-pub use uuid_handler::UuidHandler;
+#[cfg(feature = "rust_decimal")]
+pub use decimal_handler::DecimalHandler;
 
 // ─── 类型处理器注册表 ──────────────────────────────────────────────
 
 /// 类型处理器注册表
 ///
 /// 按类型名（"i64"、"string" 等）查找 [`TypeHandler`]。
-/// 默认注册 5 种基础类型；chrono/uuid 在对应 feature 启用时注册。
+/// 默认注册基础类型（含 `u64`）与 `bytes`；chrono/uuid/decimal 在对应 feature 启用时注册。
 #[derive(Clone, Default)]
 pub struct TypeHandlerRegistry {
     handlers: HashMap<String, Arc<dyn TypeHandler>>,
@@ -387,13 +892,26 @@ impl TypeHandlerRegistry {
     pub fn register_defaults(&mut self) {
         self.register(Arc::new(I64Handler));
         self.register(Arc::new(I32Handler));
+        self.register(Arc::new(U64Handler));
         self.register(Arc::new(F64Handler));
         self.register(Arc::new(BoolHandler));
         self.register(Arc::new(StringHandler));
+        self.register(Arc::new(BytesHandler::default()));
         #[cfg(feature = "chrono")]
-        self.register(Arc::new(ChronoHandler));
+        {
+            self.register(Arc::new(ChronoHandler));
+            self.register(Arc::new(ChronoNaiveHandler));
+            self.register(Arc::new(ChronoDateHandler));
+        }
+        #[cfg(feature = "time")]
+        {
+            self.register(Arc::new(TimeHandler));
+            self.register(Arc::new(TimeDateHandler));
+        }
         #[cfg(feature = "uuid")]
         self.register(Arc::new(UuidHandler));
+        #[cfg(feature = "rust_decimal")]
+        self.register(Arc::new(DecimalHandler));
     }
 
     /// 注册一个处理器（按 type_name 索引）
@@ -401,11 +919,87 @@ impl TypeHandlerRegistry {
         self.handlers.insert(handler.type_name().to_string(), handler);
     }
 
+    /// 以指定名称注册处理器（自定义处理器、`[[type_handlers]]` 绑定的 Rust 类型路径）
+    pub fn register_as(&mut self, name: impl Into<String>, handler: Arc<dyn TypeHandler>) {
+        self.handlers.insert(name.into(), handler);
+    }
+
     /// 按类型名查找处理器
     pub fn get(&self, type_name: &str) -> Option<&Arc<dyn TypeHandler>> {
         self.handlers.get(type_name)
     }
 
+    /// 依次查找，返回第一个已注册的处理器（同时启用 `chrono` 与 `time` 时 chrono 优先）
+    fn first_of(&self, type_names: &[&str]) -> Option<&Arc<dyn TypeHandler>> {
+        type_names.iter().find_map(|name| self.get(name))
+    }
+
+    /// 按 ResultMap 列声明的 `rustType` 查找处理器
+    ///
+    /// 先按原名查找，再按常见写法归一（`String` → `string`、`long` → `i64`、
+    /// `Option<T>` → `T`、`chrono::DateTime<Utc>` → `chrono`、`time::OffsetDateTime` → `time` 等）。
+    pub fn for_rust_type(&self, rust_type: &str) -> Option<&Arc<dyn TypeHandler>> {
+        let rust_type = rust_type.trim();
+        if let Some(handler) = self.get(rust_type) {
+            return Some(handler);
+        }
+        let inner = rust_type
+            .strip_prefix("Option<")
+            .and_then(|t| t.strip_suffix('>'))
+            .unwrap_or(rust_type)
+            .trim();
+        if matches!(
+            inner.replace(' ', "").as_str(),
+            "Vec<u8>" | "[u8]" | "&[u8]" | "ByteBuf" | "serde_bytes::ByteBuf" | "bytes::Bytes"
+        ) {
+            return self.get("bytes");
+        }
+        // 去掉泛型参数与路径前缀：chrono::DateTime<Utc> → DateTime
+        let base = inner.split('<').next().unwrap_or(inner);
+        let from_time_crate = base.starts_with("time::");
+        let base = base.rsplit("::").next().unwrap_or(base);
+        let name = match base.to_ascii_lowercase().as_str() {
+            "offsetdatetime" | "datetime" if from_time_crate => "time",
+            "date" if from_time_crate => "time_date",
+            "datetime" | "timestamp" => return self.first_of(&["chrono", "time"]),
+            "date" => return self.first_of(&["chrono_date", "time_date"]),
+            "naivedatetime" => "chrono_naive",
+            "naivedate" => "chrono_date",
+            "offsetdatetime" => "time",
+            "string" | "str" | "&str" => "string",
+            "i64" | "long" | "u32" => "i64",
+            "u64" | "usize" => "u64",
+            "decimal" | "bigdecimal" => "decimal",
+            "i32" | "int" | "integer" | "i16" | "i8" | "u16" | "u8" | "short" | "byte" => "i32",
+            "f64" | "f32" | "double" | "float" => "f64",
+            "bool" | "boolean" => "bool",
+            "uuid" => "uuid",
+            "bytes" | "blob" => "bytes",
+            other => return self.get(other),
+        };
+        self.get(name)
+    }
+
+    /// 按 ResultMap 列声明的 `jdbcType` 查找处理器（未知类型返回 `None`）
+    pub fn for_jdbc_type(&self, jdbc_type: &str) -> Option<&Arc<dyn TypeHandler>> {
+        let name = match jdbc_type.trim().to_ascii_uppercase().as_str() {
+            "BIGINT" => "i64",
+            "INTEGER" | "INT" | "SMALLINT" | "TINYINT" => "i32",
+            // 启用 rust_decimal 时精确解码，否则退回 f64
+            "DECIMAL" | "NUMERIC" if self.get("decimal").is_some() => "decimal",
+            "DOUBLE" | "FLOAT" | "REAL" | "DECIMAL" | "NUMERIC" => "f64",
+            "BOOLEAN" | "BIT" => "bool",
+            "CHAR" | "VARCHAR" | "LONGVARCHAR" | "NCHAR" | "NVARCHAR" | "LONGNVARCHAR"
+            | "CLOB" | "NCLOB" => "string",
+            "TIMESTAMP" | "TIMESTAMP_WITH_TIMEZONE" => return self.first_of(&["chrono", "time"]),
+            "DATE" => return self.first_of(&["chrono_date", "time_date"]),
+            "UUID" => "uuid",
+            "BLOB" | "BINARY" | "VARBINARY" | "LONGVARBINARY" | "BYTEA" => "bytes",
+            _ => return None,
+        };
+        self.get(name)
+    }
+
     /// 已注册的类型名列表
     pub fn type_names(&self) -> Vec<&str> {
         self.handlers.keys().map(|s| s.as_str()).collect()
@@ -439,6 +1033,40 @@ mod tests {
         assert!(reg.len() >= 5);
     }
 
+    #[test]
+    fn test_registry_lookup_by_rust_and_jdbc_type() {
+        let reg = TypeHandlerRegistry::with_defaults();
+        let name = |h: Option<&Arc<dyn TypeHandler>>| h.map(|h| h.type_name());
+        assert_eq!(name(reg.for_rust_type("String")), Some("string"));
+        assert_eq!(name(reg.for_rust_type("Option<i64>")), Some("i64"));
+        assert_eq!(name(reg.for_rust_type("java.lang.Long")), None);
+        assert_eq!(name(reg.for_rust_type("long")), Some("i64"));
+        assert_eq!(name(reg.for_rust_type("bool")), Some("bool"));
+        assert_eq!(name(reg.for_rust_type("MyStruct")), None);
+        assert_eq!(name(reg.for_jdbc_type("varchar")), Some("string"));
+        assert_eq!(name(reg.for_jdbc_type("BIGINT")), Some("i64"));
+        assert_eq!(name(reg.for_jdbc_type("BLOB")), Some("bytes"));
+        assert_eq!(name(reg.for_jdbc_type("CURSOR")), None);
+        assert_eq!(name(reg.for_rust_type("Vec<u8>")), Some("bytes"));
+        assert_eq!(name(reg.for_rust_type("Option<Vec<u8>>")), Some("bytes"));
+        assert_eq!(name(reg.for_rust_type("Vec<i32>")), None);
+    }
+
+    #[test]
+    fn test_binary_encoding_roundtrip() {
+        let bytes = [0u8, 159, 146, 150, 255];
+        let b64 = BinaryEncoding::Base64.encode(&bytes);
+        assert_eq!(b64, json!("AJ+Slv8="));
+        let arr = BinaryEncoding::Array.encode(&bytes);
+        assert_eq!(arr, json!([0, 159, 146, 150, 255]));
+        assert_eq!(BinaryEncoding::decode(&b64).unwrap(), Some(bytes.to_vec()));
+        assert_eq!(BinaryEncoding::decode(&arr).unwrap(), Some(bytes.to_vec()));
+        assert_eq!(BinaryEncoding::decode(&json!(null)).unwrap(), None);
+        assert!(BinaryEncoding::decode(&json!([256])).is_err());
+        assert!(BinaryEncoding::decode(&json!("not base64!")).is_err());
+        assert!(BinaryEncoding::decode(&json!(1)).is_err());
+    }
+
     #[test]
     fn test_registry_custom_register() {
         let mut reg = TypeHandlerRegistry::new();
@@ -521,4 +1149,157 @@ mod tests {
         F64Handler.set_parameter(&json!(null), &mut args).unwrap();
         assert_eq!(args.len(), 2);
     }
+
+    #[tokio::test]
+    async fn test_u64_handler_beyond_i64() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE u (small INTEGER, big TEXT)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut args = AnyArguments::default();
+        U64Handler.set_parameter(&json!(42), &mut args).unwrap();
+        U64Handler.set_parameter(&json!(u64::MAX), &mut args).unwrap();
+        sqlx::query_with("INSERT INTO u VALUES (?, ?)", args)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let row: AnyRow = sqlx::query("SELECT small, big FROM u").fetch_one(&pool).await.unwrap();
+        assert_eq!(U64Handler.get_result(&row, "small").unwrap(), json!(42));
+        assert_eq!(U64Handler.get_result(&row, "big").unwrap(), json!(u64::MAX));
+        assert!(U64Handler.set_parameter(&json!(-1), &mut AnyArguments::default()).is_err());
+
+        let reg = TypeHandlerRegistry::with_defaults();
+        assert_eq!(reg.for_rust_type("u64").map(|h| h.type_name()), Some("u64"));
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[tokio::test]
+    async fn test_decimal_handler_exact_roundtrip() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .min_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query("CREATE TABLE d (exact TEXT, approx REAL, whole INTEGER)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut args = AnyArguments::default();
+        DecimalHandler.set_parameter(&json!("12345678901234567.89"), &mut args).unwrap();
+        DecimalHandler.set_parameter(&json!(0.1), &mut args).unwrap();
+        DecimalHandler.set_parameter(&json!(7), &mut args).unwrap();
+        sqlx::query_with("INSERT INTO d VALUES (?, ?, ?)", args)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let row: AnyRow = sqlx::query("SELECT exact, approx, whole FROM d").fetch_one(&pool).await.unwrap();
+        let text = |v: Value| v.to_string().trim_matches('"').to_string();
+        assert_eq!(text(DecimalHandler.get_result(&row, "exact").unwrap()), "12345678901234567.89");
+        assert_eq!(text(DecimalHandler.get_result(&row, "approx").unwrap()), "0.1");
+        assert_eq!(text(DecimalHandler.get_result(&row, "whole").unwrap()), "7");
+        assert!(DecimalHandler.set_parameter(&json!("abc"), &mut AnyArguments::default()).is_err());
+
+        let reg = TypeHandlerRegistry::with_defaults();
+        assert_eq!(reg.for_rust_type("decimal").map(|h| h.type_name()), Some("decimal"));
+        assert_eq!(reg.for_rust_type("rust_decimal::Decimal").map(|h| h.type_name()), Some("decimal"));
+        assert_eq!(reg.for_jdbc_type("NUMERIC").map(|h| h.type_name()), Some("decimal"));
+    }
+
+    /// sqlite 内存库中插入一行文本 / 整数日期值，供日期时间处理器读取
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    async fn temporal_row() -> AnyRow {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            "SELECT '2024-01-02 03:04:05' AS plain, '2024-01-02T11:04:05.25+08:00' AS offset, \
+             '2024-01-02' AS day, 1704164645 AS epoch, NULL AS missing, 1.5 AS bad",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    #[tokio::test]
+    async fn test_chrono_handlers_normalize() {
+        let row = temporal_row().await;
+        assert_eq!(ChronoHandler.get_result(&row, "plain").unwrap(), json!("2024-01-02T03:04:05Z"));
+        assert_eq!(ChronoHandler.get_result(&row, "offset").unwrap(), json!("2024-01-02T03:04:05.250Z"));
+        assert_eq!(ChronoHandler.get_result(&row, "day").unwrap(), json!("2024-01-02T00:00:00Z"));
+        assert_eq!(ChronoHandler.get_result(&row, "epoch").unwrap(), json!("2024-01-02T03:04:05Z"));
+        assert_eq!(ChronoHandler.get_result(&row, "missing").unwrap(), Value::Null);
+        assert!(ChronoHandler.get_result(&row, "bad").is_err());
+        assert_eq!(ChronoNaiveHandler.get_result(&row, "offset").unwrap(), json!("2024-01-02T03:04:05.250"));
+        assert_eq!(ChronoDateHandler.get_result(&row, "plain").unwrap(), json!("2024-01-02"));
+
+        let mut args = AnyArguments::default();
+        ChronoHandler.set_parameter(&json!("2024-01-02T03:04:05Z"), &mut args).unwrap();
+        ChronoDateHandler.set_parameter(&json!(null), &mut args).unwrap();
+        assert!(ChronoHandler.set_parameter(&json!("yesterday"), &mut args).is_err());
+        assert!(ChronoHandler.set_parameter(&json!(true), &mut args).is_err());
+
+        let reg = TypeHandlerRegistry::with_defaults();
+        let name = |h: Option<&Arc<dyn TypeHandler>>| h.map(|h| h.type_name());
+        assert_eq!(name(reg.for_rust_type("chrono::DateTime<chrono::Utc>")), Some("chrono"));
+        assert_eq!(name(reg.for_rust_type("Option<NaiveDateTime>")), Some("chrono_naive"));
+        assert_eq!(name(reg.for_rust_type("chrono::NaiveDate")), Some("chrono_date"));
+        assert_eq!(name(reg.for_jdbc_type("TIMESTAMP")), Some("chrono"));
+        assert_eq!(name(reg.for_jdbc_type("DATE")), Some("chrono_date"));
+    }
+
+    #[cfg(feature = "time")]
+    #[tokio::test]
+    async fn test_time_handlers_normalize() {
+        let row = temporal_row().await;
+        assert_eq!(TimeHandler.get_result(&row, "plain").unwrap(), json!("2024-01-02T03:04:05Z"));
+        assert_eq!(TimeHandler.get_result(&row, "offset").unwrap(), json!("2024-01-02T03:04:05.25Z"));
+        assert_eq!(TimeHandler.get_result(&row, "epoch").unwrap(), json!("2024-01-02T03:04:05Z"));
+        assert_eq!(TimeDateHandler.get_result(&row, "day").unwrap(), json!("2024-01-02"));
+        assert!(TimeHandler.get_result(&row, "bad").is_err());
+
+        let reg = TypeHandlerRegistry::with_defaults();
+        let name = |h: Option<&Arc<dyn TypeHandler>>| h.map(|h| h.type_name());
+        assert_eq!(name(reg.for_rust_type("time::OffsetDateTime")), Some("time"));
+        assert_eq!(name(reg.for_rust_type("time::Date")), Some("time_date"));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[tokio::test]
+    async fn test_uuid_handler_text_and_blob() {
+        sqlx::any::install_default_drivers();
+        let pool = sqlx::any::AnyPoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let row: AnyRow = sqlx::query(
+            "SELECT '{67E55044-10B1-426F-9247-BB680E5FE0C8}' AS text, \
+             X'67E5504410B1426F9247BB680E5FE0C8' AS bin, X'00' AS short",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let expected = json!("67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(UuidHandler.get_result(&row, "text").unwrap(), expected);
+        assert_eq!(UuidHandler.get_result(&row, "bin").unwrap(), expected);
+        assert!(UuidHandler.get_result(&row, "short").is_err());
+        assert!(UuidHandler.set_parameter(&json!("not-a-uuid"), &mut AnyArguments::default()).is_err());
+    }
 }
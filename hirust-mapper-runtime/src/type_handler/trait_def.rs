@@ -17,9 +17,9 @@ use sqlx::any::{AnyArguments, AnyRow};
 /// # 设计说明
 ///
 /// 参数中间表示统一为 `serde_json::Value`（与 MyBatis 一致，最大灵活性）。
-/// 因此「参数写入方向」的通用入口是 [`crate::handler::ParameterHandler`]（按 Value
-/// 变体分派），而 TypeHandler 主要在「结果读取方向」发挥作用——尤其当 ResultMap
-/// 声明了特定 `rustType` 时，通过 [`TypeHandlerRegistry`] 查找对应处理器。
+/// 未声明类型时，参数按 Value 变体分派绑定（[`crate::handler::ParameterHandler`]）；
+/// 占位符声明了 `#{x, typeHandler=..}` / `rustType` / `jdbcType`，或 ResultMap 列声明了
+/// 对应属性时，通过 [`TypeHandlerRegistry`] 查找处理器完成绑定 / 读取。
 pub trait TypeHandler: Send + Sync + 'static {
     /// 处理器标识的类型名（如 "i64"、"string"），用于注册表查找
     fn type_name(&self) -> &'static str;
@@ -0,0 +1,133 @@
+//! 二进制列集成测试（sqlite BLOB）
+//!
+//! 验证 `Vec<u8>` 参数经 `BytesHandler` 以原始字节写入（不经 JSON 文本），
+//! 读取时按 `[settings] binary_encoding` 表示为 base64 字符串或字节数组，
+//! 普通映射与 ResultMap 映射一致，非 UTF-8 字节无损往返。
+
+use std::collections::HashMap;
+
+use hirust_mapper_runtime::{BinaryEncoding, EnvironmentConfig, HirustMapperConfig, SqlSessionFactory};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// 含非 UTF-8 序列的字节（lossy 转换会破坏）
+const PAYLOAD: [u8; 6] = [0x89, b'P', b'N', b'G', 0x00, 0xff];
+
+#[derive(Debug, Serialize)]
+struct NewFile {
+    id: i64,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct FileBytes {
+    id: i64,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct FileBase64 {
+    id: i64,
+    data: String,
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="f">
+    <resultMap id="fileMap" type="File">
+        <id property="id" column="id"/>
+        <result property="data" column="data" jdbcType="BLOB"/>
+    </resultMap>
+    <insert id="insert">
+        INSERT INTO files (id, data) VALUES (#{id}, #{data, rustType=bytes})
+    </insert>
+    <select id="findById">SELECT id, data FROM files WHERE id = #{id}</select>
+    <select id="findMapped" resultMap="fileMap">SELECT id, data FROM files WHERE id = #{id}</select>
+    <select id="findByData">SELECT id, data FROM files WHERE data = #{data, jdbcType=BLOB}</select>
+</mapper>"#;
+
+async fn setup(suffix: &str, encoding: BinaryEncoding) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_binary_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    let mappers_dir = temp.join("mappers");
+    std::fs::create_dir_all(&mappers_dir).unwrap();
+    std::fs::write(mappers_dir.join("F.xml"), MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+        .with_binary_encoding(encoding);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    sqlx::query("CREATE TABLE files (id INTEGER PRIMARY KEY, data BLOB)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    let mut session = factory.open_session();
+    session
+        .insert("f", "insert", &NewFile { id: 1, data: PAYLOAD.to_vec() })
+        .await
+        .unwrap();
+    (factory, temp)
+}
+
+fn by_id(id: i64) -> HashMap<String, Value> {
+    HashMap::from([("id".to_string(), json!(id))])
+}
+
+#[tokio::test]
+async fn test_blob_param_binds_raw_bytes() {
+    let (factory, temp) = setup("raw", BinaryEncoding::Base64).await;
+
+    // 以 BLOB 原样存储，而非 JSON 文本 "[137,80,...]"
+    let (kind, stored): (String, Vec<u8>) = sqlx::query_as("SELECT typeof(data), data FROM files WHERE id = 1")
+        .fetch_one(factory.environment().pool())
+        .await
+        .unwrap();
+    assert_eq!(kind, "blob");
+    assert_eq!(stored, PAYLOAD);
+
+    // base64 字符串参数同样按字节绑定，可用于等值比较
+    let mut session = factory.open_session();
+    let found: Vec<FileBase64> = session
+        .select_list("f", "findByData", &HashMap::from([("data".to_string(), json!("iVBORwD/"))]))
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_blob_result_as_base64() {
+    let (factory, temp) = setup("base64", BinaryEncoding::Base64).await;
+    let mut session = factory.open_session();
+
+    let expected = Some(FileBase64 { id: 1, data: "iVBORwD/".into() });
+    let plain: Option<FileBase64> = session.select_one("f", "findById", &by_id(1)).await.unwrap();
+    assert_eq!(plain, expected);
+    let mapped: Option<FileBase64> = session.select_one("f", "findMapped", &by_id(1)).await.unwrap();
+    assert_eq!(mapped, expected);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_blob_result_as_byte_array() {
+    let (factory, temp) = setup("array", BinaryEncoding::Array).await;
+    let mut session = factory.open_session();
+
+    let expected = vec![FileBytes { id: 1, data: PAYLOAD.to_vec() }];
+    let plain: Vec<FileBytes> = session.select_list("f", "findById", &by_id(1)).await.unwrap();
+    assert_eq!(plain, expected);
+    let mapped: Vec<FileBytes> = session.select_list("f", "findMapped", &by_id(1)).await.unwrap();
+    assert_eq!(mapped, expected);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
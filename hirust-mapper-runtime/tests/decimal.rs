@@ -0,0 +1,83 @@
+//! 精确小数集成测试（需 `--features rust_decimal`）
+//!
+//! 验证 `#{x, rustType=decimal}` 参数以十进制文本绑定、ResultMap 的 `rustType="decimal"` /
+//! `jdbcType="DECIMAL"` 列经 `DecimalHandler` 解码，金额全程不经浮点往返。
+#![cfg(feature = "rust_decimal")]
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, SqlSessionFactory};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Account {
+    id: i64,
+    balance: Decimal,
+    rate: Decimal,
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="a">
+    <resultMap id="accountMap" type="Account">
+        <id property="id" column="id"/>
+        <result property="balance" column="balance" rustType="decimal"/>
+        <result property="rate" column="rate" jdbcType="DECIMAL"/>
+    </resultMap>
+    <insert id="insert">
+        INSERT INTO accounts (id, balance, rate)
+        VALUES (#{id}, #{balance, rustType=decimal}, #{rate, rustType=decimal})
+    </insert>
+    <select id="findById" resultMap="accountMap">
+        SELECT id, balance, rate FROM accounts WHERE id = #{id}
+    </select>
+</mapper>"#;
+
+#[tokio::test]
+async fn test_decimal_columns_roundtrip_exactly() {
+    let temp = std::env::temp_dir().join("hirust_decimal");
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("A.xml"), MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    // balance 以 TEXT 存储保留全部位数；rate 为 sqlite NUMERIC 亲和列（存为 REAL）
+    sqlx::query("CREATE TABLE accounts (id INTEGER, balance TEXT, rate NUMERIC)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    let account = Account {
+        id: 1,
+        balance: Decimal::from_str("98765432109876543.21").unwrap(),
+        rate: Decimal::from_str("0.07").unwrap(),
+    };
+    let mut session = factory.open_session();
+    session.insert("a", "insert", &account).await.unwrap();
+
+    let loaded: Option<Account> = session
+        .select_one("a", "findById", &HashMap::from([("id".to_string(), json!(1))]))
+        .await
+        .unwrap();
+    assert_eq!(loaded, Some(account));
+
+    // 默认（非 arbitrary_precision）下结果为十进制字符串
+    let raw: Option<Value> = session
+        .select_one("a", "findById", &HashMap::from([("id".to_string(), json!(1))]))
+        .await
+        .unwrap();
+    let balance = raw.unwrap()["balance"].to_string();
+    assert_eq!(balance.trim_matches('"'), "98765432109876543.21");
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -1,5 +1,5 @@
 //! 事件系统集成测试：ORM 执行自动触发 BeforeSqlEvent / AfterSqlEvent，
-//! 以及 Subscriber 批量订阅模式。
+//! 以及 Subscriber 批量订阅模式与异步监听器。
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -7,7 +7,7 @@ use std::sync::{Arc, Mutex};
 
 use hirust_mapper_runtime::{
     AfterSqlEvent, BeforeSqlEvent, EnvironmentConfig, EventBus, HirustMapperConfig, SqlKind,
-    SqlOutcome, SqlSessionFactory, Subscriber,
+    SqlOutcome, SqlSessionFactory, StatementContext, Subscriber,
 };
 use serde::{Deserialize, Serialize};
 
@@ -67,11 +67,11 @@ async fn test_lifecycle_events_fire_with_correct_data() {
     let b = Arc::clone(&before_kinds);
     factory.event_bus().on(move |e: &BeforeSqlEvent| {
         b.lock().unwrap().push(e.kind);
-    });
+    }).detach();
     let a = Arc::clone(&after_summaries);
     factory.event_bus().on(move |e: &AfterSqlEvent| {
         a.lock().unwrap().push((e.kind, outcome_count(e)));
-    });
+    }).detach();
 
     let mut session = factory.open_session();
     session
@@ -83,15 +83,17 @@ async fn test_lifecycle_events_fire_with_correct_data() {
         .await
         .unwrap();
 
-    let b = before_kinds.lock().unwrap();
-    let a = after_summaries.lock().unwrap();
+    {
+        let b = before_kinds.lock().unwrap();
+        let a = after_summaries.lock().unwrap();
 
-    // before 事件按执行顺序：Insert 然后 Select
-    assert_eq!(*b, vec![SqlKind::Insert, SqlKind::Select]);
+        // before 事件按执行顺序：Insert 然后 Select
+        assert_eq!(*b, vec![SqlKind::Insert, SqlKind::Select]);
 
-    // after 事件含正确结果摘要
-    assert!(a.iter().any(|(k, n)| *k == SqlKind::Insert && *n == 1), "insert affected 1: {:?}", *a);
-    assert!(a.iter().any(|(k, n)| *k == SqlKind::Select && *n == 1), "select fetched 1: {:?}", *a);
+        // after 事件含正确结果摘要
+        assert!(a.iter().any(|(k, n)| *k == SqlKind::Insert && *n == 1), "insert affected 1: {:?}", *a);
+        assert!(a.iter().any(|(k, n)| *k == SqlKind::Select && *n == 1), "select fetched 1: {:?}", *a);
+    }
 
     // factory 与 session 共享同一事件总线
     assert!(factory.event_bus().listener_count::<AfterSqlEvent>() >= 1);
@@ -100,6 +102,44 @@ async fn test_lifecycle_events_fire_with_correct_data() {
     std::fs::remove_dir_all(temp).ok();
 }
 
+#[tokio::test]
+async fn test_events_carry_statement_context() {
+    let (factory, temp) = setup("context").await;
+
+    let seen = Arc::new(Mutex::new(Vec::<StatementContext>::new()));
+    let s = Arc::clone(&seen);
+    factory.event_bus().on(move |e: &AfterSqlEvent| {
+        assert_eq!(e.kind, e.statement.kind);
+        s.lock().unwrap().push(e.statement.clone());
+    }).detach();
+
+    let mut session = factory.open_session();
+    let session_id = session.id();
+    session
+        .insert("app.UserDao", "insert", &User { id: 0, name: "张三".into(), age: 30 })
+        .await
+        .unwrap();
+    session.begin().await.unwrap();
+    let _: Vec<User> = session
+        .select_list("app.UserDao", "findAll", &HashMap::new())
+        .await
+        .unwrap();
+    session.delete("app.UserDao", "deleteAll", &HashMap::<String, i64>::new()).await.unwrap();
+    session.commit().await.unwrap();
+    assert_ne!(factory.open_session().id(), session_id, "会话 id 应唯一");
+
+    let expected = [
+        StatementContext::new("app.UserDao", "insert", SqlKind::Insert).with_session(session_id, false),
+        StatementContext::new("app.UserDao", "findAll", SqlKind::Select).with_session(session_id, true),
+        StatementContext::new("app.UserDao", "deleteAll", SqlKind::Delete).with_session(session_id, true),
+    ];
+    assert_eq!(*seen.lock().unwrap(), expected);
+    assert_eq!(expected[1].qualified_id(), "app.UserDao.findAll");
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
 #[tokio::test]
 async fn test_subscriber_pattern_registers_multiple() {
     let (factory, temp) = setup("subscriber").await;
@@ -113,11 +153,11 @@ async fn test_subscriber_pattern_registers_multiple() {
             let before = Arc::clone(&self.before);
             bus.on(move |_: &BeforeSqlEvent| {
                 before.fetch_add(1, Ordering::Relaxed);
-            });
+            }).detach();
             let after = Arc::clone(&self.after);
             bus.on(move |_: &AfterSqlEvent| {
                 after.fetch_add(1, Ordering::Relaxed);
-            });
+            }).detach();
         }
     }
 
@@ -167,3 +207,37 @@ async fn test_no_listeners_zero_overhead() {
     factory.close().await;
     std::fs::remove_dir_all(temp).ok();
 }
+
+#[tokio::test]
+async fn test_async_listener_receives_sql_events() {
+    let (factory, temp) = setup("async").await;
+
+    // 异步审计：在后台任务中处理，SQL 执行不等待监听器
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let audit = factory.event_bus().on_async(move |e: AfterSqlEvent| {
+        let tx = tx.clone();
+        async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            tx.send(e.statement.qualified_id()).unwrap();
+        }
+    });
+
+    let mut session = factory.open_session();
+    session
+        .insert("app.UserDao", "insert", &User { id: 0, name: "张三".into(), age: 30 })
+        .await
+        .unwrap();
+    let _: Vec<User> = session.select_list("app.UserDao", "findAll", &HashMap::new()).await.unwrap();
+
+    assert_eq!(rx.recv().await.unwrap(), "app.UserDao.insert");
+    assert_eq!(rx.recv().await.unwrap(), "app.UserDao.findAll");
+    let stats = factory.event_bus().async_stats();
+    assert_eq!((stats.enqueued, stats.dropped), (2, 0));
+
+    // 取消订阅后后台任务退出，发送端随之关闭
+    drop(audit);
+    assert_eq!(rx.recv().await, None);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
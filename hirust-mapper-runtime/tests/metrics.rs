@@ -0,0 +1,102 @@
+//! 语句级指标集成测试
+//!
+//! 验证 `[settings] metrics` 开启后工厂按「命名空间 + 语句 id」聚合调用次数、错误、
+//! 行数、耗时与连接池等待，`metrics_snapshot().to_prometheus()` 输出 Prometheus 文本；未开启时快照为空。
+
+use std::collections::HashMap;
+
+use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, SqlSessionFactory};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: i64,
+    name: String,
+}
+
+const XML: &str = r#"<mapper namespace="app.UserDao">
+    <select id="findAll">SELECT id, name FROM users ORDER BY id</select>
+    <select id="broken">SELECT id FROM missing_table</select>
+    <insert id="insert">INSERT INTO users (name) VALUES (#{name})</insert>
+    <update id="rename">UPDATE users SET name = #{name}</update>
+</mapper>"#;
+
+async fn setup(suffix: &str, metrics: bool) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_metrics_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("UserDao.xml"), XML).unwrap();
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+        .with_metrics(metrics);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+    (factory, temp)
+}
+
+#[tokio::test]
+async fn test_metrics_per_statement() {
+    let (factory, temp) = setup("enabled", true).await;
+    let mut session = factory.open_session();
+    for name in ["张三", "李四"] {
+        session.insert("app.UserDao", "insert", &User { id: 0, name: name.into() }).await.unwrap();
+    }
+    for _ in 0..3 {
+        let _: Vec<User> = session.select_list("app.UserDao", "findAll", &HashMap::new()).await.unwrap();
+    }
+    assert!(session.select_list::<User>("app.UserDao", "broken", &HashMap::new()).await.is_err());
+    session.begin().await.unwrap();
+    let renamed = session
+        .update("app.UserDao", "rename", &HashMap::from([("name", "王五")]))
+        .await
+        .unwrap();
+    assert_eq!(renamed, 2);
+    session.commit().await.unwrap();
+
+    let snapshot = factory.metrics_snapshot();
+    let find_all = snapshot.get("app.UserDao", "findAll").unwrap();
+    assert_eq!((find_all.calls, find_all.errors, find_all.rows_fetched), (3, 0, 6));
+    assert_eq!(find_all.latency.count(), 3);
+    assert_eq!(find_all.pool_wait.count(), 3, "事务外查询记录连接池等待");
+
+    let insert = snapshot.get("app.UserDao", "insert").unwrap();
+    assert_eq!((insert.calls, insert.rows_affected), (2, 2));
+    assert_eq!(insert.pool_wait.count(), 2);
+
+    let broken = snapshot.get("app.UserDao", "broken").unwrap();
+    assert_eq!((broken.calls, broken.errors), (1, 1));
+
+    let rename = snapshot.get("app.UserDao", "rename").unwrap();
+    assert_eq!((rename.calls, rename.rows_affected), (1, 2));
+    assert_eq!(rename.pool_wait.count(), 0, "事务内复用事务连接，不记录等待");
+
+    let text = snapshot.to_prometheus();
+    assert!(text.contains("hirust_mapper_statement_calls_total{namespace=\"app.UserDao\",statement=\"findAll\"} 3\n"));
+    assert!(text.contains("hirust_mapper_statement_errors_total{namespace=\"app.UserDao\",statement=\"broken\"} 1\n"));
+    assert!(text.contains("hirust_mapper_statement_duration_seconds_count{namespace=\"app.UserDao\",statement=\"insert\"} 2\n"));
+    assert!(text.contains("hirust_mapper_pool_wait_seconds_count{namespace=\"app.UserDao\",statement=\"findAll\"} 3\n"));
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_metrics_disabled_by_default() {
+    let (factory, temp) = setup("disabled", false).await;
+    let mut session = factory.open_session();
+    let _: Vec<User> = session.select_list("app.UserDao", "findAll", &HashMap::new()).await.unwrap();
+    assert!(factory.metrics_snapshot().statements.is_empty());
+    assert_eq!(factory.metrics_snapshot().to_prometheus().lines().filter(|l| !l.starts_with('#')).count(), 0);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
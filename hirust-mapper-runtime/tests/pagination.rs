@@ -0,0 +1,136 @@
+//! 分页查询集成测试
+//!
+//! 验证 `select_page`：自动 COUNT 包装、方言 LIMIT/OFFSET 追加、
+//! `{id}_count` 自定义计数语句，以及 MapperProxy 入口。
+
+use std::collections::HashMap;
+
+use hirust_mapper_runtime::{
+    EnvironmentConfig, HirustMapperConfig, Page, PageRequest, SqlSessionFactory,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+    age: i64,
+}
+
+const USER_MAPPER_XML: &str = r#"<mapper namespace="com.test.UserDao">
+    <select id="findByMinAge">
+        SELECT id, name, age FROM users WHERE age >= #{minAge} ORDER BY id
+    </select>
+    <select id="findCounted">SELECT id, name, age FROM users ORDER BY id</select>
+    <select id="findCounted_count">SELECT 99</select>
+    <insert id="insert">INSERT INTO users (name, age) VALUES (#{name}, #{age})</insert>
+</mapper>"#;
+
+async fn setup(suffix: &str) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_page_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    let mappers_dir = temp.join("mappers");
+    std::fs::create_dir_all(&mappers_dir).unwrap();
+    std::fs::write(mappers_dir.join("UserDao.xml"), USER_MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT, age INTEGER)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    let mut session = factory.open_session();
+    for i in 0..7 {
+        session
+            .insert(
+                "com.test.UserDao",
+                "insert",
+                &User { id: 0, name: format!("user{}", i), age: 18 + i },
+            )
+            .await
+            .unwrap();
+    }
+
+    (factory, temp)
+}
+
+fn min_age(age: i64) -> HashMap<String, Value> {
+    HashMap::from([("minAge".to_string(), json!(age))])
+}
+
+/// 自动计数 + LIMIT/OFFSET：参数化条件与分页参数共存
+#[tokio::test]
+async fn test_select_page_auto_count() {
+    let (factory, temp) = setup("auto").await;
+    let mut session = factory.open_session();
+
+    // age >= 20 → user2..user6 共 5 行
+    let page: Page<User> = session
+        .select_page("com.test.UserDao", "findByMinAge", &min_age(20), PageRequest::new(2, 2))
+        .await
+        .unwrap();
+    assert_eq!(page.total, 5);
+    assert_eq!(page.page, 2);
+    assert_eq!(page.size, 2);
+    assert_eq!(page.total_pages(), 3);
+    let names: Vec<_> = page.items.iter().map(|u| u.name.as_str()).collect();
+    assert_eq!(names, vec!["user4", "user5"]);
+
+    // 最后一页不满
+    let last: Page<User> = session
+        .select_page("com.test.UserDao", "findByMinAge", &min_age(20), PageRequest::new(3, 2))
+        .await
+        .unwrap();
+    assert_eq!(last.items.len(), 1);
+    assert!(!last.has_next());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 无匹配行：total 为 0，items 为空
+#[tokio::test]
+async fn test_select_page_empty() {
+    let (factory, temp) = setup("empty").await;
+    let mut session = factory.open_session();
+
+    let page: Page<User> = session
+        .select_page("com.test.UserDao", "findByMinAge", &min_age(100), PageRequest::new(1, 10))
+        .await
+        .unwrap();
+    assert_eq!(page.total, 0);
+    assert!(page.items.is_empty());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 存在 `{id}_count` 语句时改用其计数
+#[tokio::test]
+async fn test_select_page_custom_count_statement() {
+    let (factory, temp) = setup("custom_count").await;
+    let mut session = factory.open_session();
+
+    let page: Page<User> = session
+        .mapper("com.test.UserDao")
+        .unwrap()
+        .select_page("findCounted", &HashMap::new(), PageRequest::new(1, 3))
+        .await
+        .unwrap();
+    assert_eq!(page.total, 99, "应使用 findCounted_count 的结果");
+    assert_eq!(page.items.len(), 3);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -0,0 +1,163 @@
+//! 敏感参数打码集成测试
+//!
+//! 验证 XML `redact=true`、`[settings.redaction] patterns` 与模型 `redacted_fields` 三种来源的参数在
+//! SQL 日志与 [`BeforeSqlEvent`] / [`AfterSqlEvent`] 中被替换为掩码，而数据库收到的仍是原值。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, Once};
+
+use hirust_mapper_runtime::sql_log::LOG_TARGET;
+use hirust_mapper_runtime::{
+    AfterSqlEvent, BeforeSqlEvent, EnvironmentConfig, HirustMapperConfig, MapperModel, RedactionPolicy,
+    SqlSessionFactory,
+};
+use serde_json::json;
+
+/// 捕获本二进制内 SQL 日志 target 的日志
+static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static INIT: Once = Once::new();
+/// 串行化本文件中的测试，避免并发污染 LOGS 的前后快照
+static TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+struct CapturingLogger;
+impl log::Log for CapturingLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == LOG_TARGET
+    }
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            LOGS.lock().unwrap().push(format!("{}", record.args()));
+        }
+    }
+    fn flush(&self) {}
+}
+
+fn install_logger() {
+    INIT.call_once(|| {
+        log::set_boxed_logger(Box::new(CapturingLogger)).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+    });
+}
+
+const XML: &str = r#"<mapper namespace="app.AccountDao">
+    <insert id="insert">
+        INSERT INTO accounts (name, pin, id_card, password, access_token)
+        VALUES (#{name}, #{pin, redact=true}, #{id_card}, #{password}, #{access_token})
+    </insert>
+    <select id="findPin">SELECT pin AS total FROM accounts WHERE name = #{name}</select>
+</mapper>"#;
+
+/// 手写模型：`password` 字段声明为敏感
+struct Account;
+impl MapperModel for Account {
+    const TYPE_NAME: &'static str = "Account";
+    fn column_mappings() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+    fn type_handlers() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+    fn redacted_fields() -> &'static [&'static str] {
+        &["password"]
+    }
+}
+
+async fn setup(suffix: &str, policy: RedactionPolicy, with_model: bool) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_redaction_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("AccountDao.xml"), XML).unwrap();
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+        .with_sql_log(true)
+        .with_redaction(policy);
+    let mut builder = SqlSessionFactory::builder(config, &temp);
+    if with_model {
+        builder = builder.model::<Account>();
+    }
+    let factory = builder.build().await.unwrap();
+    sqlx::query(
+        "CREATE TABLE accounts (name TEXT, pin TEXT, id_card TEXT, password TEXT, access_token TEXT)",
+    )
+    .execute(factory.environment().pool())
+    .await
+    .unwrap();
+    (factory, temp)
+}
+
+type Captured = Arc<Mutex<Vec<(&'static str, Vec<serde_json::Value>)>>>;
+
+fn collect(factory: &SqlSessionFactory) -> Captured {
+    let events: Captured = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    factory.event_bus().on(move |e: &BeforeSqlEvent| sink.lock().unwrap().push(("before", e.params.clone()))).detach();
+    let sink = Arc::clone(&events);
+    factory.event_bus().on(move |e: &AfterSqlEvent| sink.lock().unwrap().push(("after", e.params.clone()))).detach();
+    events
+}
+
+fn account() -> serde_json::Value {
+    json!({
+        "name": "张三",
+        "pin": "1234",
+        "id_card": "110101199001011234",
+        "password": "s3cret",
+        "access_token": "tok-abc",
+    })
+}
+
+#[tokio::test]
+async fn test_redacts_logs_and_events() {
+    let _guard = TEST_LOCK.lock().await;
+    install_logger();
+    let policy = RedactionPolicy::new().with_patterns(["id_card", "*TOKEN*"]).with_mask("<hidden>");
+    let (factory, temp) = setup("all_sources", policy, true).await;
+    let events = collect(&factory);
+    let mut session = factory.open_session();
+
+    let before = LOGS.lock().unwrap().len();
+    session.insert("app.AccountDao", "insert", &account()).await.unwrap();
+
+    let expected = vec![json!("张三"), json!("<hidden>"), json!("<hidden>"), json!("<hidden>"), json!("<hidden>")];
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(events, vec![("before", expected.clone()), ("after", expected)]);
+
+    let logs = LOGS.lock().unwrap()[before..].join("\n");
+    assert!(logs.contains("VALUES ('张三', '<hidden>', '<hidden>', '<hidden>', '<hidden>')"), "{logs}");
+    for secret in ["1234", "110101199001011234", "s3cret", "tok-abc"] {
+        assert!(!logs.contains(secret), "日志不应包含 {secret}\n{logs}");
+    }
+
+    // 打码不影响实际绑定
+    let params = HashMap::from([("name".to_string(), json!("张三"))]);
+    let pin: Option<String> = session.select_scalar("app.AccountDao", "findPin", &params).await.unwrap();
+    assert_eq!(pin.as_deref(), Some("1234"));
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_default_redacts_only_xml_flag() {
+    let _guard = TEST_LOCK.lock().await;
+    let (factory, temp) = setup("default", RedactionPolicy::default(), false).await;
+    let events = collect(&factory);
+    let mut session = factory.open_session();
+
+    session.insert("app.AccountDao", "insert", &account()).await.unwrap();
+
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(
+        events[0].1,
+        vec![json!("张三"), json!("******"), json!("110101199001011234"), json!("s3cret"), json!("tok-abc")]
+    );
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
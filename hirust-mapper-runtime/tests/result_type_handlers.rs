@@ -0,0 +1,161 @@
+//! ResultMap 列类型处理器集成测试
+//!
+//! 验证声明了 `rustType` / `jdbcType` / `typeHandler` 的 ResultMap 列经工厂共享的
+//! `TypeHandlerRegistry` 解码（含嵌套 association），以及未注册的 `typeHandler` 报错。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use hirust_mapper_runtime::{
+    Environment, EnvironmentConfig, HirustMapperConfig, MapperRegistry, MapperRuntimeError,
+    SqlSessionFactory, TypeAliasRegistry, TypeHandler, TypeHandlerRegistry,
+};
+use serde::Deserialize;
+use serde_json::Value;
+use sqlx::any::{AnyArguments, AnyRow};
+use sqlx::Row;
+
+/// 读取时转大写的自定义处理器
+struct UpperHandler;
+
+impl TypeHandler for UpperHandler {
+    fn type_name(&self) -> &'static str {
+        "upper"
+    }
+
+    fn get_result(&self, row: &AnyRow, column: &str) -> hirust_mapper_runtime::Result<Value> {
+        let v: Option<String> = row
+            .try_get(column)
+            .map_err(|e| MapperRuntimeError::TypeConversion(e.to_string()))?;
+        Ok(v.map(|s| Value::String(s.to_uppercase())).unwrap_or(Value::Null))
+    }
+
+    fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> hirust_mapper_runtime::Result<()> {
+        hirust_mapper_runtime::StringHandler.set_parameter(value, arguments)
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Owner {
+    id: i64,
+    verified: bool,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Product {
+    id: i64,
+    code: String,
+    name: String,
+    active: bool,
+    owner: Option<Owner>,
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="p">
+    <resultMap id="productMap" type="Product">
+        <id property="id" column="id"/>
+        <result property="code" column="code" typeHandler="upper"/>
+        <result property="name" column="name" jdbcType="VARCHAR"/>
+        <result property="active" column="active" rustType="flag"/>
+        <association property="owner" javaType="Owner">
+            <id property="id" column="owner_id"/>
+            <result property="verified" column="owner_verified" rustType="bool"/>
+        </association>
+    </resultMap>
+    <resultMap id="brokenMap" type="Product">
+        <id property="id" column="id"/>
+        <result property="code" column="code" typeHandler="Missing"/>
+    </resultMap>
+    <select id="findAll" resultMap="productMap">
+        SELECT p.id, p.code, p.name, p.active, o.id AS owner_id, o.verified AS owner_verified
+        FROM products p LEFT JOIN owners o ON p.owner_id = o.id ORDER BY p.id
+    </select>
+    <select id="findById" resultMap="productMap">
+        SELECT p.id, p.code, p.name, p.active, NULL AS owner_id, NULL AS owner_verified
+        FROM products p WHERE p.id = #{id}
+    </select>
+    <select id="findBroken" resultMap="brokenMap">SELECT id, code FROM products</select>
+</mapper>"#;
+
+async fn setup(suffix: &str) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_result_th_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    let mappers_dir = temp.join("mappers");
+    std::fs::create_dir_all(&mappers_dir).unwrap();
+    std::fs::write(mappers_dir.join("P.xml"), MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+        .with_type_alias("flag", "bool");
+
+    let environment = Environment::from_config(&config.environment).await.unwrap();
+    let mappers = MapperRegistry::new();
+    mappers.load_from_config(&config, &temp).unwrap();
+    let mut handlers = TypeHandlerRegistry::with_defaults();
+    handlers.register(Arc::new(UpperHandler));
+    let aliases = TypeAliasRegistry::from_map(config.type_aliases.clone());
+    let factory = SqlSessionFactory::from_parts(environment, mappers, aliases, handlers, config, temp.clone());
+
+    let pool = factory.environment().pool();
+    for sql in [
+        "CREATE TABLE owners (id INTEGER, verified INTEGER)",
+        "CREATE TABLE products (id INTEGER, code TEXT, name TEXT, active INTEGER, owner_id INTEGER)",
+        "INSERT INTO owners VALUES (7, 1)",
+        "INSERT INTO products VALUES (1, 'ab-1', '键盘', 1, 7)",
+        "INSERT INTO products VALUES (2, 'cd-2', '鼠标', 0, NULL)",
+    ] {
+        sqlx::query(sql).execute(pool).await.unwrap();
+    }
+    (factory, temp)
+}
+
+#[tokio::test]
+async fn test_result_map_columns_use_type_handlers() {
+    let (factory, temp) = setup("list").await;
+    let mut session = factory.open_session();
+
+    let products: Vec<Product> = session.select_list("p", "findAll", &HashMap::new()).await.unwrap();
+    assert_eq!(
+        products,
+        vec![
+            Product {
+                id: 1,
+                code: "AB-1".into(),
+                name: "键盘".into(),
+                active: true,
+                owner: Some(Owner { id: 7, verified: true }),
+            },
+            Product { id: 2, code: "CD-2".into(), name: "鼠标".into(), active: false, owner: None },
+        ]
+    );
+
+    let one: Option<Product> = session
+        .select_one("p", "findById", &HashMap::from([("id".to_string(), Value::from(2))]))
+        .await
+        .unwrap();
+    assert_eq!(one.map(|p| (p.code, p.active)), Some(("CD-2".to_string(), false)));
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_unknown_type_handler_errors() {
+    let (factory, temp) = setup("unknown").await;
+    let mut session = factory.open_session();
+
+    let err = session
+        .select_list::<Value>("p", "findBroken", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MapperRuntimeError::TypeConversion(_)));
+    assert!(err.to_string().contains("'Missing' 未注册"), "{}", err);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
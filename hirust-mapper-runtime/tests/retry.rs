@@ -0,0 +1,217 @@
+//! 瞬时错误重试集成测试
+//!
+//! 用独立连接持有 sqlite 排他锁（或共享锁，使提交失败）制造 `SQLITE_BUSY`，验证事务外查询与
+//! `transaction_with_retry` 在锁释放后重试成功，并为每次重试派发 `RetryEvent`。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hirust_mapper_runtime::{
+    EnvironmentConfig, HirustMapperConfig, RetryErrorClass, RetryEvent, RetryPolicy, RetryScope,
+    SqlSessionFactory,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::Connection;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Item {
+    id: i64,
+    name: String,
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="com.test.ItemDao">
+    <select id="findAll">SELECT id, name FROM items ORDER BY id</select>
+    <insert id="insert">INSERT INTO items (name) VALUES (#{name})</insert>
+</mapper>"#;
+
+const NS: &str = "com.test.ItemDao";
+
+async fn setup(suffix: &str, policy: RetryPolicy) -> (SqlSessionFactory, String, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_retry_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    let mappers_dir = temp.join("mappers");
+    std::fs::create_dir_all(&mappers_dir).unwrap();
+    std::fs::write(mappers_dir.join("ItemDao.xml"), MAPPER_XML).unwrap();
+
+    let url = format!("sqlite://{}?mode=rwc", temp.join("retry.db").display());
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: url.clone(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+        .with_retry_policy(policy);
+
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    let pool = factory.environment().pool();
+    sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(pool)
+        .await
+        .unwrap();
+    // 单连接池：关闭 sqlite 自带的忙等待，使锁冲突立即以 SQLITE_BUSY 返回
+    sqlx::query("PRAGMA busy_timeout = 0").execute(pool).await.unwrap();
+
+    (factory, url, temp)
+}
+
+/// 在独立连接上持有排他锁，`hold` 后提交释放
+async fn lock_database(url: &str, hold: Duration) -> tokio::task::JoinHandle<()> {
+    let mut conn = sqlx::AnyConnection::connect(url).await.unwrap();
+    sqlx::query("BEGIN EXCLUSIVE").execute(&mut conn).await.unwrap();
+    sqlx::query("INSERT INTO items (name) VALUES ('locker')")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+    tokio::spawn(async move {
+        tokio::time::sleep(hold).await;
+        sqlx::query("COMMIT").execute(&mut conn).await.unwrap();
+        conn.close().await.ok();
+    })
+}
+
+/// 在独立连接上开启读事务并持有共享锁（不阻塞写入，但阻塞其他连接提交），`hold` 后结束
+async fn hold_read_lock(url: &str, hold: Duration) -> tokio::task::JoinHandle<()> {
+    let mut conn = sqlx::AnyConnection::connect(url).await.unwrap();
+    sqlx::query("BEGIN").execute(&mut conn).await.unwrap();
+    sqlx::query("SELECT COUNT(*) FROM items").fetch_one(&mut conn).await.unwrap();
+    tokio::spawn(async move {
+        tokio::time::sleep(hold).await;
+        sqlx::query("COMMIT").execute(&mut conn).await.unwrap();
+        conn.close().await.ok();
+    })
+}
+
+fn record_retries(factory: &SqlSessionFactory) -> Arc<Mutex<Vec<RetryEvent>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    factory.event_bus().on(move |e: &RetryEvent| sink.lock().unwrap().push(e.clone())).detach();
+    events
+}
+
+fn policy() -> RetryPolicy {
+    RetryPolicy::new(30).with_backoff_ms(10, 40).with_jitter(false)
+}
+
+/// 事务外查询在锁释放后重试成功
+#[tokio::test]
+async fn test_select_retries_on_busy() {
+    let (factory, url, temp) = setup("select", policy()).await;
+    let events = record_retries(&factory);
+
+    let unlock = lock_database(&url, Duration::from_millis(150)).await;
+    let mut session = factory.open_session();
+    let items: Vec<Item> = session.select_list(NS, "findAll", &HashMap::new()).await.unwrap();
+    assert_eq!(items.len(), 1, "应读到释放锁后提交的数据");
+    unlock.await.unwrap();
+
+    let events = events.lock().unwrap().clone();
+    assert!(!events.is_empty());
+    let first = &events[0];
+    assert_eq!(
+        first.scope,
+        RetryScope::Statement { namespace: NS.into(), id: "findAll".into() }
+    );
+    assert_eq!(first.attempt, 1);
+    assert_eq!(first.max_attempts, 30);
+    assert_eq!(first.class, RetryErrorClass::Busy);
+    assert_eq!(first.delay, Duration::from_millis(10));
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 默认策略不重试：忙错误直接返回，且被识别为 Busy
+#[tokio::test]
+async fn test_default_policy_does_not_retry() {
+    let (factory, url, temp) = setup("disabled", RetryPolicy::default()).await;
+    let events = record_retries(&factory);
+
+    let unlock = lock_database(&url, Duration::from_millis(100)).await;
+    let mut session = factory.open_session();
+    let err = session
+        .select_list::<Item>(NS, "findAll", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert_eq!(RetryErrorClass::classify(&err), Some(RetryErrorClass::Busy));
+    assert!(events.lock().unwrap().is_empty());
+    unlock.await.unwrap();
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 整个事务单元回滚后重新执行闭包
+#[tokio::test]
+async fn test_transaction_with_retry_reruns_closure() {
+    let (factory, url, temp) = setup("transaction", policy()).await;
+    let events = record_retries(&factory);
+    let attempts = Arc::new(AtomicUsize::new(0));
+
+    let unlock = lock_database(&url, Duration::from_millis(150)).await;
+    let id = factory
+        .transaction_with_retry(|tx| {
+            let attempts = Arc::clone(&attempts);
+            async move {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                tx.insert(NS, "insert", &HashMap::from([("name", "甲")])).await
+            }
+        })
+        .await
+        .unwrap();
+    unlock.await.unwrap();
+
+    assert_eq!(id, Some(2), "locker 的行先提交，重试后的插入排在其后");
+    let tries = attempts.load(Ordering::SeqCst);
+    assert!(tries > 1, "闭包应被重试执行，实际 {} 次", tries);
+    let events = events.lock().unwrap().clone();
+    assert_eq!(events.len(), tries - 1);
+    assert!(events.iter().all(|e| e.scope == RetryScope::Transaction));
+
+    let mut session = factory.open_session();
+    let items: Vec<Item> = session.select_list(NS, "findAll", &HashMap::new()).await.unwrap();
+    let names: Vec<_> = items.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["locker", "甲"]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 提交时的锁冲突同样触发整个事务单元重试
+#[tokio::test]
+async fn test_transaction_with_retry_on_commit_busy() {
+    let (factory, url, temp) = setup("commit", policy()).await;
+    let events = record_retries(&factory);
+    let inserts = Arc::new(AtomicUsize::new(0));
+
+    let unlock = hold_read_lock(&url, Duration::from_millis(150)).await;
+    factory
+        .transaction_with_retry(|tx| {
+            let inserts = Arc::clone(&inserts);
+            async move {
+                let id = tx.insert(NS, "insert", &HashMap::from([("name", "甲")])).await?;
+                inserts.fetch_add(1, Ordering::SeqCst);
+                Ok(id)
+            }
+        })
+        .await
+        .unwrap();
+    unlock.await.unwrap();
+
+    let inserts = inserts.load(Ordering::SeqCst);
+    assert!(inserts > 1, "插入成功、提交失败后应重试整个事务，实际 {} 次", inserts);
+    let events = events.lock().unwrap().clone();
+    assert_eq!(events.len(), inserts - 1);
+    assert!(events.iter().all(|e| e.scope == RetryScope::Transaction && e.class == RetryErrorClass::Busy));
+
+    let mut session = factory.open_session();
+    let items: Vec<Item> = session.select_list(NS, "findAll", &HashMap::new()).await.unwrap();
+    let names: Vec<_> = items.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(names, vec!["甲"], "失败的提交不应留下数据");
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
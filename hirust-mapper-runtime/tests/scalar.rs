@@ -0,0 +1,68 @@
+//! 标量 / 元组结果集成测试
+//!
+//! 验证 `select_scalar` / `select_values` 按列位置映射（单列 → 标量，多列 → 元组），
+//! 以及 `resultType="long"` 等标量类型在 `select_one` / `select_list` 中取首列并经处理器解码。
+
+use std::collections::HashMap;
+
+use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, MapperRuntimeError, SqlSessionFactory};
+use serde_json::{json, Value};
+
+const MAPPER_XML: &str = r#"<mapper namespace="s">
+    <select id="count">SELECT COUNT(*) FROM users</select>
+    <select id="countLong" resultType="long">SELECT COUNT(*) AS total FROM users</select>
+    <select id="names" resultType="java.lang.String">SELECT name, id FROM users ORDER BY id</select>
+    <select id="flags" resultType="_boolean">SELECT active FROM users ORDER BY id</select>
+    <select id="pairs">SELECT id, name FROM users ORDER BY id</select>
+    <select id="nameOf">SELECT name FROM users WHERE id = #{id}</select>
+</mapper>"#;
+
+#[tokio::test]
+async fn test_scalar_and_tuple_results() {
+    let temp = std::env::temp_dir().join("hirust_scalar");
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("S.xml"), MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    let pool = factory.environment().pool();
+    sqlx::query("CREATE TABLE users (id INTEGER, name TEXT, active INTEGER)").execute(pool).await.unwrap();
+    sqlx::query("INSERT INTO users VALUES (1, '张三', 1), (2, '李四', 0)").execute(pool).await.unwrap();
+
+    let mut session = factory.open_session();
+    let none = HashMap::new();
+
+    // 按位置映射：单列 → 标量，多列 → 元组
+    assert_eq!(session.select_scalar::<i64>("s", "count", &none).await.unwrap(), Some(2));
+    let pairs: Vec<(i64, String)> = session.select_values("s", "pairs", &none).await.unwrap();
+    assert_eq!(pairs, vec![(1, "张三".to_string()), (2, "李四".to_string())]);
+    let by_id = |id: i64| HashMap::from([("id".to_string(), json!(id))]);
+    let name: Option<String> = session.select_scalar("s", "nameOf", &by_id(2)).await.unwrap();
+    assert_eq!(name.as_deref(), Some("李四"));
+    assert_eq!(session.select_scalar::<String>("s", "nameOf", &by_id(9)).await.unwrap(), None);
+    let err = session.select_scalar::<(i64, String)>("s", "pairs", &none).await.unwrap_err();
+    assert!(matches!(err, MapperRuntimeError::TooManyRows { actual: 2 }), "{}", err);
+
+    // 未声明标量 resultType 时 select_one 仍按对象映射
+    assert!(session.select_one::<i64>("s", "count", &none).await.is_err());
+
+    // 标量 resultType：select_one / select_list 取首列
+    assert_eq!(session.select_one::<i64>("s", "countLong", &none).await.unwrap(), Some(2));
+    let names: Vec<String> = session.select_list("s", "names", &none).await.unwrap();
+    assert_eq!(names, ["张三", "李四"]);
+    let flags: Vec<bool> = session.select_list("s", "flags", &none).await.unwrap();
+    assert_eq!(flags, [true, false]);
+    let raw: Vec<Value> = session.select_values("s", "flags", &none).await.unwrap();
+    assert_eq!(raw, [json!(true), json!(false)]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -0,0 +1,115 @@
+//! 慢查询执行计划集成测试
+//!
+//! 验证设置慢查询阈值后派发 [`SlowQueryEvent`]；开启 `[settings] slow_query_explain` 时在独立的池连接上
+//! 捕获执行计划并按最小间隔限流；`SqlSession::explain` 按需取执行计划。
+//! 使用文件 sqlite 库（内存库的每个连接是独立的数据库，捕获连接看不到测试表）。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, SlowQueryEvent, SqlSessionFactory};
+use serde_json::json;
+
+const XML: &str = r#"<mapper namespace="app.UserDao">
+    <select id="slowCount">
+        WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n &lt; #{limit})
+        SELECT COUNT(*) AS total FROM seq, users WHERE users.id = #{id}
+    </select>
+    <select id="findById">SELECT id, name FROM users WHERE id = #{id}</select>
+</mapper>"#;
+
+async fn setup(suffix: &str, explain: bool) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_slow_query_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("UserDao.xml"), XML).unwrap();
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: format!("sqlite://{}?mode=rwc", temp.join("db.sqlite").display()),
+            pool_max_connections: 2,
+            pool_min_connections: 2,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+        .with_sql_log_slow_threshold_ms(1)
+        .with_slow_query_explain(explain)
+        .with_slow_query_explain_interval_ms(60_000);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    let pool = factory.environment().pool();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO users (name) VALUES ('张三')").execute(pool).await.unwrap();
+    (factory, temp)
+}
+
+fn collect(factory: &SqlSessionFactory) -> Arc<Mutex<Vec<SlowQueryEvent>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    factory.event_bus().on(move |e: &SlowQueryEvent| sink.lock().unwrap().push(e.clone())).detach();
+    events
+}
+
+#[tokio::test]
+async fn test_slow_query_captures_plan_rate_limited() {
+    let (factory, temp) = setup("explain", true).await;
+    let events = collect(&factory);
+    let mut session = factory.open_session();
+    let params = HashMap::from([("limit".to_string(), json!(300_000)), ("id".to_string(), json!(1))]);
+    for _ in 0..2 {
+        let total: Option<i64> = session.select_scalar("app.UserDao", "slowCount", &params).await.unwrap();
+        assert_eq!(total, Some(300_000));
+    }
+
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(events.len(), 2, "两次都达到慢查询阈值");
+    let first = &events[0];
+    assert_eq!(first.statement.qualified_id(), "app.UserDao.slowCount");
+    assert_eq!(first.threshold.as_millis(), 1);
+    assert!(first.elapsed >= first.threshold);
+    assert_eq!(first.params, vec![json!(300_000), json!(1)]);
+    let plan = first.plan.as_ref().expect("首次慢查询附带执行计划");
+    assert!(plan.sql.starts_with("EXPLAIN QUERY PLAN "));
+    assert!(plan.lines().iter().any(|l| l.contains("users")), "{plan}");
+    assert!(events[1].plan.is_none(), "最小间隔内不再捕获");
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_slow_query_event_without_explain() {
+    let (factory, temp) = setup("no_explain", false).await;
+    let events = collect(&factory);
+    let mut session = factory.open_session();
+    let params = HashMap::from([("limit".to_string(), json!(300_000)), ("id".to_string(), json!(1))]);
+    let _: Option<i64> = session.select_scalar("app.UserDao", "slowCount", &params).await.unwrap();
+
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(events.len(), 1);
+    assert!(events[0].plan.is_none());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_explain_on_demand() {
+    let (factory, temp) = setup("on_demand", false).await;
+    let mut session = factory.open_session();
+    let plan = session.explain("app.UserDao", "findById", &json!({ "id": 1 })).await.unwrap();
+    assert_eq!(plan.sql, "EXPLAIN QUERY PLAN SELECT id, name FROM users WHERE id = ?");
+    assert!(plan.to_string().contains("users"), "{plan}");
+
+    // 事务内在事务连接上执行；Mapper 代理同样可用
+    session.begin().await.unwrap();
+    let plan = session.mapper("app.UserDao").unwrap().explain("findById", &json!({ "id": 1 })).await.unwrap();
+    assert!(!plan.rows.is_empty());
+    session.rollback().await.unwrap();
+
+    assert!(factory.open_session().explain("app.UserDao", "missing", &json!({})).await.is_err());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -0,0 +1,116 @@
+//! SQL 来源注释集成测试
+//!
+//! 验证开启 `[settings.sql_comment]` 后，Mapper 语句生成的 SQL 末尾带 sqlcommenter 注释（含构建器设置的追踪上下文），
+//! 分页改写后注释仍在末尾，参数顺序与执行结果不受影响；未开启时 SQL 保持原样。
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use hirust_mapper_runtime::{
+    BeforeSqlEvent, EnvironmentConfig, HirustMapperConfig, PageRequest, SqlCommentConfig, SqlSessionFactory,
+    TraceContext,
+};
+use serde::Deserialize;
+use serde_json::json;
+
+const XML: &str = r#"<mapper namespace="app.UserDao">
+    <select id="findByName">SELECT id, name FROM users WHERE name = #{name} AND id &gt;= #{min_id}</select>
+    <select id="findAll">SELECT id, name FROM users ORDER BY id</select>
+    <insert id="insert">INSERT INTO users (name) VALUES (#{name})</insert>
+</mapper>"#;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+}
+
+async fn setup(suffix: &str, comment: SqlCommentConfig, traced: bool) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_sql_comment_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("UserDao.xml"), XML).unwrap();
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+        .with_sql_comment(comment);
+    let mut builder = SqlSessionFactory::builder(config, &temp);
+    if traced {
+        builder = builder.trace_context(|| {
+            Some(TraceContext::new("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"))
+        });
+    }
+    let factory = builder.build().await.unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+    (factory, temp)
+}
+
+fn collect(factory: &SqlSessionFactory) -> Arc<Mutex<Vec<BeforeSqlEvent>>> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    factory.event_bus().on(move |e: &BeforeSqlEvent| sink.lock().unwrap().push(e.clone())).detach();
+    events
+}
+
+#[tokio::test]
+async fn test_statements_tagged_with_origin() {
+    let (factory, temp) = setup("on", SqlCommentConfig::enabled().with_app("order-service"), true).await;
+    let events = collect(&factory);
+    let mut session = factory.open_session();
+
+    session.insert("app.UserDao", "insert", &json!({ "name": "张三" })).await.unwrap();
+    let params = HashMap::from([("name".to_string(), json!("张三")), ("min_id".to_string(), json!(1))]);
+    let users: Vec<User> = session.select_list("app.UserDao", "findByName", &params).await.unwrap();
+    assert_eq!(users, vec![User { id: 1, name: "张三".into() }]);
+    let page: hirust_mapper_runtime::Page<User> =
+        session.select_page("app.UserDao", "findAll", &HashMap::new(), PageRequest::new(1, 10)).await.unwrap();
+    assert_eq!(page.total, 1);
+
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    let trace = "traceparent='00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01'";
+    assert_eq!(
+        events[0].raw_sql,
+        format!("INSERT INTO users (name) VALUES (?) /*app='order-service',id='insert',ns='app.UserDao',{trace}*/")
+    );
+    assert_eq!(
+        events[1].raw_sql,
+        format!(
+            "SELECT id, name FROM users WHERE name = ? AND id >= ? \
+             /*app='order-service',id='findByName',ns='app.UserDao',{trace}*/"
+        )
+    );
+    assert_eq!(events[1].params, vec![json!("张三"), json!(1)], "参数顺序不变");
+    // 分页：计数与数据查询的注释都在改写后的 SQL 末尾
+    assert!(events[2].raw_sql.starts_with("SELECT COUNT(*) FROM ("), "{}", events[2].raw_sql);
+    assert!(events[2].raw_sql.ends_with(&format!("id='findAll',ns='app.UserDao',{trace}*/")));
+    assert!(events[3].raw_sql.contains("LIMIT ? OFFSET ? /*app='order-service',id='findAll'"), "{}", events[3].raw_sql);
+
+    // build_bound_sql 同样带注释
+    let bound = session.build_bound_sql("app.UserDao", "findAll", &HashMap::new()).unwrap();
+    assert!(bound.sql.ends_with("*/"));
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_disabled_leaves_sql_untouched() {
+    let (factory, temp) = setup("off", SqlCommentConfig::default(), true).await;
+    let events = collect(&factory);
+    let mut session = factory.open_session();
+    let _: Vec<User> = session.select_list("app.UserDao", "findAll", &HashMap::new()).await.unwrap();
+
+    let events = std::mem::take(&mut *events.lock().unwrap());
+    assert_eq!(events[0].raw_sql, "SELECT id, name FROM users ORDER BY id");
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -1,15 +1,16 @@
 //! SQL 执行日志集成测试
 //!
 //! 用自定义 `log::Log` 捕获器验证：开启 `sql_log` 后执行点经 `log` facade 真正发射日志，
-//! 日志含「耗时 + 参数内联的可读 SQL」；关闭时不发射。
+//! 日志含「耗时 + 参数内联的可读 SQL」；关闭时不发射；JSON 格式、采样与命名空间级别覆盖生效。
 
 use std::collections::HashMap;
 use std::sync::{Mutex, Once};
 
 use hirust_mapper_runtime::{
     executor::execute_rows_affected, BoundSql, EnvironmentConfig, EventBus, HirustMapperConfig,
-    SqlLogConfig, SqlSessionFactory,
+    SqlLogConfig, SqlLogFormat, SqlLogLevel, SqlSessionFactory,
 };
+use hirust_mapper_runtime::sql_log::LOG_TARGET;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 
@@ -20,17 +21,17 @@ struct User {
     age: i64,
 }
 
-/// 捕获本二进制内所有 hirust_mapper target 的日志。
+/// 捕获本二进制内 SQL 日志 target 的日志（开启 `tracing` feature 时 span 经 log 兼容层输出的记录不计入）。
 static LOGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
 /// 日志后端只能安装一次（跨测试共享）。
 static INIT: Once = Once::new();
-/// 串行化本文件中的测试，避免并发污染 LOGS 的前后快照。
-static TEST_LOCK: Mutex<()> = Mutex::new(());
+/// 串行化本文件中的测试，避免并发污染 LOGS 的前后快照（跨 await 持有，用异步锁）。
+static TEST_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
 
 struct CapturingLogger;
 impl log::Log for CapturingLogger {
     fn enabled(&self, metadata: &log::Metadata) -> bool {
-        metadata.target().starts_with("hirust_mapper")
+        metadata.target() == LOG_TARGET
     }
     fn log(&self, record: &log::Record) {
         if self.enabled(record.metadata()) {
@@ -52,12 +53,24 @@ const USER_MAPPER_XML: &str = r#"<mapper namespace="app.UserDao">
     <insert id="insert">INSERT INTO users (name, age) VALUES (#{name}, #{age})</insert>
 </mapper>"#;
 
+const AUDIT_MAPPER_XML: &str = r#"<mapper namespace="app.AuditDao">
+    <select id="count">SELECT COUNT(*) AS total FROM users</select>
+</mapper>"#;
+
 async fn setup(suffix: &str, sql_log: bool) -> (SqlSessionFactory, std::path::PathBuf) {
+    setup_with(suffix, |config| config.with_sql_log(sql_log)).await
+}
+
+async fn setup_with(
+    suffix: &str,
+    configure: impl FnOnce(HirustMapperConfig) -> HirustMapperConfig,
+) -> (SqlSessionFactory, std::path::PathBuf) {
     let temp = std::env::temp_dir().join(format!("hirust_sqllog_{}", suffix));
     std::fs::remove_dir_all(&temp).ok();
     let mappers_dir = temp.join("mappers");
     std::fs::create_dir_all(&mappers_dir).unwrap();
     std::fs::write(mappers_dir.join("UserDao.xml"), USER_MAPPER_XML).unwrap();
+    std::fs::write(mappers_dir.join("AuditDao.xml"), AUDIT_MAPPER_XML).unwrap();
 
     let config = HirustMapperConfig::new()
         .with_environment(EnvironmentConfig {
@@ -66,8 +79,8 @@ async fn setup(suffix: &str, sql_log: bool) -> (SqlSessionFactory, std::path::Pa
             pool_max_connections: 1,
             pool_min_connections: 1,
         })
-        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
-        .with_sql_log(sql_log);
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let config = configure(config);
 
     let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
     sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT, age INTEGER)")
@@ -79,7 +92,7 @@ async fn setup(suffix: &str, sql_log: bool) -> (SqlSessionFactory, std::path::Pa
 
 #[tokio::test]
 async fn test_sql_log_emits_on_query_and_insert() {
-    let _guard = TEST_LOCK.lock().unwrap();
+    let _guard = TEST_LOCK.lock().await;
     install_logger();
     let (factory, temp) = setup("emits", true).await;
     let mut session = factory.open_session();
@@ -97,13 +110,14 @@ async fn test_sql_log_emits_on_query_and_insert() {
 
     assert!(after > before, "开启 sql_log 后应发射日志");
 
-    let logs = LOGS.lock().unwrap();
-    let recent = logs[before..after].join("\n");
+    let recent = LOGS.lock().unwrap()[before..after].join("\n");
     assert!(recent.contains("Consume Time"), "应包含耗时字段\n{recent}");
     assert!(recent.contains("Execute SQL"), "应包含 Execute SQL\n{recent}");
     assert!(recent.contains("INSERT INTO users"), "应记录 insert SQL\n{recent}");
     assert!(recent.contains("SELECT id, name, age"), "应记录 select SQL\n{recent}");
     assert!(recent.contains("'张三'"), "参数应内联进 SQL\n{recent}");
+    assert!(recent.contains("Statement: app.UserDao.insert [INSERT]"), "应记录语句上下文\n{recent}");
+    assert!(recent.contains("Statement: app.UserDao.findAll [SELECT]"), "应记录语句上下文\n{recent}");
 
     factory.close().await;
     std::fs::remove_dir_all(temp).ok();
@@ -111,7 +125,7 @@ async fn test_sql_log_emits_on_query_and_insert() {
 
 #[tokio::test]
 async fn test_sql_log_disabled_emits_nothing() {
-    let _guard = TEST_LOCK.lock().unwrap();
+    let _guard = TEST_LOCK.lock().await;
     install_logger();
     let (factory, temp) = setup("disabled", false).await;
     let mut session = factory.open_session();
@@ -135,7 +149,7 @@ async fn test_sql_log_disabled_emits_nothing() {
 
 #[tokio::test]
 async fn test_execute_rows_affected_respects_config() {
-    let _guard = TEST_LOCK.lock().unwrap();
+    let _guard = TEST_LOCK.lock().await;
     install_logger();
     let (factory, temp) = setup("rows_affected", false).await; // 全局关闭
     let session = factory.open_session();
@@ -145,15 +159,18 @@ async fn test_execute_rows_affected_respects_config() {
     let bound = BoundSql {
         sql: "INSERT INTO users (name, age) VALUES (?, ?)".to_string(),
         parameters: vec![json!("甲"), json!(1)],
+        ..Default::default()
     };
-    let cfg_on = SqlLogConfig { enabled: true, slow_threshold_ms: 0 };
+    let cfg_on = SqlLogConfig { enabled: true, ..Default::default() };
     let bus = EventBus::new();
     let before = LOGS.lock().unwrap().len();
     let n = execute_rows_affected(&bound, session.pool(), &cfg_on, &bus).await.unwrap();
     let after = LOGS.lock().unwrap().len();
     assert_eq!(n, 1);
     assert!(after > before, "传入开启配置时应发射日志");
-    assert!(LOGS.lock().unwrap()[before..after].join("\n").contains("INSERT INTO users"));
+    let emitted = LOGS.lock().unwrap()[before..after].join("\n");
+    assert!(emitted.contains("INSERT INTO users"));
+    assert!(emitted.contains("Statement: [OTHER]"), "无语句上下文时标记为 OTHER\n{emitted}");
 
     // 传入关闭的配置 → 不发射
     let cfg_off = SqlLogConfig::default();
@@ -164,3 +181,53 @@ async fn test_execute_rows_affected_respects_config() {
     factory.close().await;
     std::fs::remove_dir_all(temp).ok();
 }
+
+#[tokio::test]
+async fn test_sql_log_json_format_with_levels_and_sampling() {
+    let _guard = TEST_LOCK.lock().await;
+    install_logger();
+    let (factory, temp) = setup_with("json", |config| {
+        config
+            .with_sql_log(true)
+            .with_sql_log_format(SqlLogFormat::Json)
+            .with_sql_log_level("app.AuditDao", SqlLogLevel::Off)
+    })
+    .await;
+    let mut session = factory.open_session();
+
+    let before = LOGS.lock().unwrap().len();
+    session
+        .insert("app.UserDao", "insert", &User { id: 0, name: "张三".into(), age: 30 })
+        .await
+        .unwrap();
+    let _: Option<i64> = session.select_scalar("app.AuditDao", "count", &HashMap::new()).await.unwrap();
+    let logs = LOGS.lock().unwrap()[before..].to_vec();
+
+    assert_eq!(logs.len(), 1, "app.AuditDao 级别为 off，不记录\n{logs:?}");
+    let record: serde_json::Value = serde_json::from_str(&logs[0]).expect("每条日志为一个 JSON 对象");
+    assert_eq!(record["statement"], "app.UserDao.insert");
+    assert_eq!(record["kind"], "INSERT");
+    assert_eq!(record["sql"], "INSERT INTO users (name, age) VALUES (?, ?)");
+    assert_eq!(record["params"], json!(["张三", 30]));
+    assert_eq!(record["rows"], 1);
+    assert_eq!(record["outcome"], "ok");
+    assert!(record["elapsed_ms"].is_u64());
+    assert!(record["timestamp"].is_string());
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+
+    // 采样率 0：成功的语句不记录，失败的语句仍记录
+    let (factory, temp) =
+        setup_with("sampled", |config| config.with_sql_log(true).with_sql_log_sample_rate(0.0)).await;
+    let mut session = factory.open_session();
+    let before = LOGS.lock().unwrap().len();
+    let _: Vec<User> = session.select_list("app.UserDao", "findAll", &HashMap::new()).await.unwrap();
+    assert_eq!(LOGS.lock().unwrap().len(), before, "未被采样的成功语句不记录");
+    sqlx::query("DROP TABLE users").execute(factory.environment().pool()).await.unwrap();
+    assert!(session.select_list::<User>("app.UserDao", "findAll", &HashMap::new()).await.is_err());
+    let logs = LOGS.lock().unwrap()[before..].join("\n");
+    assert!(logs.contains("Statement: app.UserDao.findAll [SELECT]"), "失败语句总是记录\n{logs}");
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
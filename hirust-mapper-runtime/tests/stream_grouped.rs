@@ -0,0 +1,174 @@
+//! 流式 ResultMap 分组集成测试
+//!
+//! 验证 `select_stream_grouped` 对按父 id 有序的 join 结果逐个产出完整父对象（含 collection），
+//! 乱序时按 `OutOfOrderPolicy` 报错或退化为缓冲分组。
+
+use std::collections::HashMap;
+
+use hirust_mapper_runtime::{
+    EnvironmentConfig, HirustMapperConfig, MapperRuntimeError, OutOfOrderPolicy, SqlSessionFactory,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Order {
+    id: i64,
+    amount: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct UserWithOrders {
+    id: i64,
+    name: String,
+    #[serde(default)]
+    orders: Vec<Order>,
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="u">
+    <resultMap id="userOrdersMap" type="UserWithOrders">
+        <id property="id" column="id"/>
+        <result property="name" column="name"/>
+        <collection property="orders" ofType="Order">
+            <id property="id" column="order_id"/>
+            <result property="amount" column="amount"/>
+        </collection>
+    </resultMap>
+    <select id="findOrdered" resultMap="userOrdersMap">
+        SELECT u.id AS id, u.name AS name, o.id AS order_id, o.amount AS amount
+        FROM users u LEFT JOIN orders o ON o.user_id = u.id ORDER BY u.id, o.id
+    </select>
+    <select id="findByOrder" resultMap="userOrdersMap">
+        SELECT u.id AS id, u.name AS name, o.id AS order_id, o.amount AS amount
+        FROM users u JOIN orders o ON o.user_id = u.id ORDER BY o.id
+    </select>
+    <select id="findPlain">SELECT id, name FROM users ORDER BY id</select>
+</mapper>"#;
+
+async fn setup(suffix: &str) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_stream_grouped_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("U.xml"), MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    let pool = factory.environment().pool();
+    for sql in [
+        "CREATE TABLE users (id INTEGER, name TEXT)",
+        "CREATE TABLE orders (id INTEGER, user_id INTEGER, amount INTEGER)",
+        "INSERT INTO users VALUES (1, '张三'), (2, '李四'), (3, '王五')",
+        // 订单 id 交错归属：按订单 id 排序时用户 1 会再次出现
+        "INSERT INTO orders VALUES (10, 1, 100), (11, 2, 200), (12, 1, 300)",
+    ] {
+        sqlx::query(sql).execute(pool).await.unwrap();
+    }
+    (factory, temp)
+}
+
+fn order(id: i64, amount: i64) -> Order {
+    Order { id, amount }
+}
+
+#[tokio::test]
+async fn test_ordered_join_streams_parents() {
+    let (factory, temp) = setup("ordered").await;
+    let mut session = factory.open_session();
+
+    let mut users: Vec<UserWithOrders> = Vec::new();
+    session
+        .select_stream_grouped("u", "findOrdered", &HashMap::new(), OutOfOrderPolicy::Error, |u: &UserWithOrders| {
+            users.push(UserWithOrders {
+                id: u.id,
+                name: u.name.clone(),
+                orders: u.orders.iter().map(|o| order(o.id, o.amount)).collect(),
+            });
+            Ok(())
+        })
+        .await
+        .unwrap();
+    assert_eq!(
+        users,
+        vec![
+            UserWithOrders { id: 1, name: "张三".into(), orders: vec![order(10, 100), order(12, 300)] },
+            UserWithOrders { id: 2, name: "李四".into(), orders: vec![order(11, 200)] },
+            UserWithOrders { id: 3, name: "王五".into(), orders: vec![] },
+        ]
+    );
+
+    // 与缓冲式 select_list 结果一致
+    let listed: Vec<UserWithOrders> = session.select_list("u", "findOrdered", &HashMap::new()).await.unwrap();
+    assert_eq!(listed, users);
+
+    // 回调返回 Err 时提前终止
+    let mut seen = 0;
+    let err = session
+        .select_stream_grouped("u", "findOrdered", &HashMap::new(), OutOfOrderPolicy::Error, |_: &UserWithOrders| {
+            seen += 1;
+            Err(MapperRuntimeError::TypeConversion("stop".into()))
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(seen, 1);
+    assert!(err.to_string().contains("stop"));
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_unordered_ids_error_or_buffer() {
+    let (factory, temp) = setup("unordered").await;
+    let mut session = factory.open_session();
+
+    // 默认策略：用户 1 已随用户 2 的出现而产出，其再次出现时报错
+    let mut emitted = Vec::new();
+    let err = session
+        .select_stream_grouped("u", "findByOrder", &HashMap::new(), OutOfOrderPolicy::Error, |u: &UserWithOrders| {
+            emitted.push(u.id);
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, MapperRuntimeError::UnorderedGroup { ref result_map, .. } if result_map == "userOrdersMap"), "{}", err);
+    assert_eq!(emitted, vec![1]);
+
+    // Buffer：整体分组，与 select_list 一致
+    let mut users = Vec::new();
+    session
+        .select_stream_grouped("u", "findByOrder", &HashMap::new(), OutOfOrderPolicy::Buffer, |u: &UserWithOrders| {
+            users.push((u.id, u.orders.len()));
+            Ok(())
+        })
+        .await
+        .unwrap();
+    assert_eq!(users, vec![(1, 2), (2, 1)]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_without_result_map_maps_rows() {
+    let (factory, temp) = setup("plain").await;
+    let mut session = factory.open_session();
+
+    let mut names = Vec::new();
+    session
+        .select_stream_grouped("u", "findPlain", &HashMap::new(), OutOfOrderPolicy::default(), |v: &serde_json::Value| {
+            names.push(v["name"].as_str().unwrap().to_string());
+            Ok(())
+        })
+        .await
+        .unwrap();
+    assert_eq!(names, ["张三", "李四", "王五"]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -1,13 +1,15 @@
 //! 流式查询（streaming fetch）集成测试
 //!
-//! 验证 `select_for_each`（session 级回调式）与 `SimpleExecutor::query_stream`
-//!（executor 级 Stream）按行拉取、不一次性物化整表，且映射正确。
+//! 验证 `select_for_each` / `select_for_each_async`（session 级回调式）、`select_stream`
+//!（session 级 Stream）与 `SimpleExecutor::query_stream`（executor 级 Stream）按行拉取、
+//! 不一次性物化整表，且映射正确、结束时派发事件。
 
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use futures_util::StreamExt;
+use futures_util::{StreamExt, TryStreamExt};
 use hirust_mapper_runtime::{
-    EnvironmentConfig, HirustMapperConfig, SqlSessionFactory,
+    AfterSqlEvent, EnvironmentConfig, HirustMapperConfig, SqlKind, SqlOutcome, SqlSessionFactory,
 };
 use serde::{Deserialize, Serialize};
 
@@ -167,3 +169,79 @@ async fn test_select_for_each_empty() {
     factory.close().await;
     std::fs::remove_dir_all(temp).ok();
 }
+
+/// session 级 Stream：持有 BoundSql、事务内可见未提交数据，结束时派发 AfterSqlEvent
+#[tokio::test]
+async fn test_select_stream_in_transaction_emits_events() {
+    let (factory, temp) = setup("select_stream").await;
+    let outcomes = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&outcomes);
+    factory.event_bus().on(move |e: &AfterSqlEvent| {
+        if e.kind == SqlKind::Select {
+            sink.lock().unwrap().push(e.outcome.clone());
+        }
+    }).detach();
+
+    let mut session = factory.open_session();
+    session.begin().await.unwrap();
+    insert_users(&mut session, &["甲", "乙", "丙"]).await;
+
+    let names: Vec<String> = session
+        .select_stream::<User>("com.test.UserDao", "findAll", &HashMap::new())
+        .map(|u| u.map(|u| u.name))
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(names, ["甲", "乙", "丙"]);
+    assert!(matches!(outcomes.lock().unwrap().as_slice(), [SqlOutcome::Fetched(3)]));
+
+    // 提前丢弃：按已拉取行数记录
+    {
+        let mut stream = session.select_stream::<User>("com.test.UserDao", "findAll", &HashMap::new());
+        stream.next().await.unwrap().unwrap();
+    }
+    assert!(matches!(outcomes.lock().unwrap().last(), Some(SqlOutcome::Fetched(1))));
+
+    // 未知 statement：流产出错误后结束
+    let mut stream = session.select_stream::<User>("com.test.UserDao", "missing", &HashMap::new());
+    assert!(stream.next().await.unwrap().is_err());
+    assert!(stream.next().await.is_none());
+    drop(stream);
+
+    session.rollback().await.unwrap();
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 异步回调：逐行等待回调完成
+#[tokio::test]
+async fn test_select_for_each_async() {
+    let (factory, temp) = setup("foreach_async").await;
+    let mut session = factory.open_session();
+    insert_users(&mut session, &["甲", "乙"]).await;
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<User>(1);
+    let consumer = tokio::spawn(async move {
+        let mut names = Vec::new();
+        while let Some(u) = rx.recv().await {
+            names.push(u.name);
+        }
+        names
+    });
+    session
+        .select_for_each_async("com.test.UserDao", "findAll", &HashMap::new(), |u: User| {
+            let tx = tx.clone();
+            async move {
+                tx.send(u)
+                    .await
+                    .map_err(|e| hirust_mapper_runtime::MapperRuntimeError::Transaction(e.to_string()))
+            }
+        })
+        .await
+        .unwrap();
+    drop(tx);
+    assert_eq!(consumer.await.unwrap(), ["甲", "乙"]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -0,0 +1,125 @@
+//! 日期时间 / UUID 集成测试（需 `--features chrono,uuid`）
+//!
+//! 验证 sqlite 以文本存储的日期时间列经 ResultMap `rustType` / `jdbcType` 与已注册
+//! `MapperModel` 的 `type_handler` 注解解码为 RFC3339 / 规范 UUID 字符串，直接反序列化为
+//! chrono / uuid 类型；参数按处理器绑定为与 `CURRENT_TIMESTAMP` 一致的文本。
+#![cfg(all(feature = "chrono", feature = "uuid"))]
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, MapperModel, SqlSessionFactory};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Event {
+    id: Uuid,
+    happened_at: DateTime<Utc>,
+    day: NaiveDate,
+}
+
+/// 手写实现（等价于 `#[derive(MapperModel)]` 的展开）
+impl MapperModel for Event {
+    const TYPE_NAME: &'static str = "Event";
+
+    fn column_mappings() -> &'static [(&'static str, &'static str)] {
+        &[("id", "event_id"), ("happened_at", "happened_at"), ("day", "day")]
+    }
+
+    fn type_handlers() -> &'static [(&'static str, &'static str)] {
+        &[("id", "uuid::Uuid"), ("happened_at", "chrono::DateTime<chrono::Utc>"), ("day", "NaiveDate")]
+    }
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="e">
+    <resultMap id="eventMap" type="Event">
+        <id property="id" column="event_id" rustType="Uuid"/>
+        <result property="happened_at" column="happened_at" jdbcType="TIMESTAMP"/>
+        <result property="day" column="day" rustType="chrono::NaiveDate"/>
+    </resultMap>
+    <insert id="insert">
+        INSERT INTO events (event_id, happened_at, day)
+        VALUES (#{id, rustType=uuid::Uuid}, #{happened_at, jdbcType=TIMESTAMP}, #{day, rustType=NaiveDate})
+    </insert>
+    <select id="findMapped" resultMap="eventMap">SELECT event_id, happened_at, day FROM events</select>
+    <select id="findModel" resultType="myapp::Event">SELECT event_id, happened_at, day FROM events</select>
+    <select id="findRaw">SELECT event_id, happened_at, day FROM events</select>
+    <select id="findBefore" resultType="Event">
+        SELECT event_id, happened_at, day FROM events WHERE happened_at &lt; #{until, rustType=DateTime}
+    </select>
+</mapper>"#;
+
+#[tokio::test]
+async fn test_temporal_and_uuid_columns() {
+    let temp = std::env::temp_dir().join("hirust_temporal");
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("E.xml"), MAPPER_XML).unwrap();
+
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::builder(config, &temp)
+        .model::<Event>()
+        .build()
+        .await
+        .unwrap();
+    let pool = factory.environment().pool();
+    sqlx::query("CREATE TABLE events (event_id TEXT, happened_at TEXT, day TEXT)")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let event = Event {
+        id: Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap(),
+        happened_at: Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap(),
+        day: NaiveDate::from_ymd_opt(2024, 1, 2).unwrap(),
+    };
+    let mut session = factory.open_session();
+    session.insert("e", "insert", &event).await.unwrap();
+
+    // 以 sqlite 原生日期文本写入，可直接参与 datetime() 比较
+    let stored: (String, i64) = sqlx::query_as(
+        "SELECT happened_at, happened_at = datetime('2024-01-02 03:04:05') FROM events",
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    assert_eq!(stored, ("2024-01-02 03:04:05".to_string(), 1));
+
+    let no_params = HashMap::new();
+    let mapped: Vec<Event> = session.select_list("e", "findMapped", &no_params).await.unwrap();
+    assert_eq!(mapped, [event]);
+    let event = mapped.into_iter().next().unwrap();
+
+    // resultType 为已注册模型：按注解改名（event_id → id）并解码
+    let model: Option<Event> = session.select_one("e", "findModel", &no_params).await.unwrap();
+    assert_eq!(model.as_ref(), Some(&event));
+
+    // 未声明类型时保留驱动原值（sqlite 文本）
+    let raw: Option<Value> = session.select_one("e", "findRaw", &no_params).await.unwrap();
+    assert_eq!(raw.unwrap()["happened_at"], json!("2024-01-02 03:04:05"));
+
+    // RFC3339 参数换算为 UTC 文本后比较
+    let until = |t: &str| HashMap::from([("until".to_string(), json!(t))]);
+    let before: Vec<Event> = session
+        .select_list("e", "findBefore", &until("2024-01-02T11:04:06+08:00"))
+        .await
+        .unwrap();
+    assert_eq!(before, vec![event]);
+    let before: Vec<Event> = session
+        .select_list("e", "findBefore", &until("2024-01-02T11:04:05+08:00"))
+        .await
+        .unwrap();
+    assert!(before.is_empty());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -0,0 +1,183 @@
+//! 语句超时集成测试
+//!
+//! 验证 XML `timeout` 属性与 `default_statement_timeout_ms` 默认值：超时返回
+//! `MapperRuntimeError::Timeout` 并携带语句坐标，超时连接被丢弃后连接池仍可用；
+//! 事务内超时后事务被中止（事务连接被关闭而不归还连接池），仅可回滚。
+
+use std::collections::HashMap;
+
+use hirust_mapper_runtime::{
+    EnvironmentConfig, HirustMapperConfig, MapperRuntimeError, SqlSessionFactory,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct Total {
+    total: i64,
+}
+
+const SLOW_SQL: &str = "WITH RECURSIVE c(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM c WHERE 4000000 > x) \
+                        SELECT count(*) AS total FROM c";
+
+fn mapper_xml() -> String {
+    format!(
+        r#"<mapper namespace="com.test.SlowDao">
+    <select id="slow">{SLOW_SQL}</select>
+    <select id="slowWithAttr" timeout="1">{SLOW_SQL}</select>
+    <select id="fastUnlimited" timeout="0">SELECT 1 AS total</select>
+    <select id="fast">SELECT 1 AS total</select>
+    <insert id="insert">INSERT INTO items (name) VALUES (#{{name}})</insert>
+    <update id="tagConnection">PRAGMA cache_size = {TAG_CACHE_SIZE}</update>
+</mapper>"#
+    )
+}
+
+const NS: &str = "com.test.SlowDao";
+
+/// 标记事务连接用的 `cache_size`（连接级设置，不随事务回滚）
+const TAG_CACHE_SIZE: i64 = -12345;
+
+/// 连接池中（最多 `max` 个）连接是否有被标记的
+async fn pool_has_tagged_connection(factory: &SqlSessionFactory, max: usize) -> bool {
+    let mut conns = Vec::new();
+    for _ in 0..max {
+        conns.push(factory.environment().pool().acquire().await.unwrap());
+    }
+    for conn in &mut conns {
+        let (size,): (i64,) = sqlx::query_as("PRAGMA cache_size").fetch_one(&mut **conn).await.unwrap();
+        if size == TAG_CACHE_SIZE {
+            return true;
+        }
+    }
+    false
+}
+
+async fn setup(suffix: &str, default_timeout_ms: u64) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_timeout_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    let mappers_dir = temp.join("mappers");
+    std::fs::create_dir_all(&mappers_dir).unwrap();
+    std::fs::write(mappers_dir.join("SlowDao.xml"), mapper_xml()).unwrap();
+
+    let mut config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: format!("sqlite://{}?mode=rwc", temp.join("timeout.db").display()),
+            pool_max_connections: 4,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    config.settings.default_statement_timeout_ms = default_timeout_ms;
+
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    sqlx::query("CREATE TABLE items (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    (factory, temp)
+}
+
+fn assert_timeout(err: MapperRuntimeError, id: &str) {
+    assert!(err.to_string().contains(&format!("{NS}.{id}")), "错误信息应含语句坐标: {err}");
+    match err {
+        MapperRuntimeError::Timeout { namespace, id: actual } => {
+            assert_eq!(namespace, NS);
+            assert_eq!(actual, id);
+        }
+        other => panic!("应为超时错误，实际: {other:?}"),
+    }
+}
+
+async fn count_items(factory: &SqlSessionFactory) -> i64 {
+    let (n,): (i64,) = sqlx::query_as("SELECT count(*) FROM items")
+        .fetch_one(factory.environment().pool())
+        .await
+        .unwrap();
+    n
+}
+
+/// 默认超时生效；超时后连接池仍可正常使用
+#[tokio::test]
+async fn test_default_timeout_and_pool_recovers() {
+    let (factory, temp) = setup("default", 100).await;
+    let mut session = factory.open_session();
+
+    let err = session
+        .select_one::<Total>(NS, "slow", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert_timeout(err, "slow");
+
+    // 超时连接被丢弃，后续查询照常（多次执行以覆盖池中所有连接）
+    for _ in 0..3 {
+        let total: Option<Total> = session.select_one(NS, "fast", &HashMap::new()).await.unwrap();
+        assert_eq!(total, Some(Total { total: 1 }));
+    }
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// XML `timeout` 属性（秒）优先于默认值；`timeout="0"` 表示不限时
+#[tokio::test]
+async fn test_statement_timeout_attribute() {
+    let (factory, temp) = setup("attribute", 0).await;
+    let mut session = factory.open_session();
+
+    let err = session
+        .select_list::<Total>(NS, "slowWithAttr", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert_timeout(err, "slowWithAttr");
+
+    let rows: Vec<Total> = session
+        .select_list(NS, "fastUnlimited", &HashMap::<String, Value>::new())
+        .await
+        .unwrap();
+    assert_eq!(rows, vec![Total { total: 1 }]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 事务内超时：事务被中止，后续语句、再次 begin 与提交均报错
+#[tokio::test]
+async fn test_timeout_aborts_transaction() {
+    let (factory, temp) = setup("transaction", 100).await;
+    let mut session = factory.open_session();
+
+    session.begin().await.unwrap();
+    session
+        .insert(NS, "insert", &HashMap::from([("name", "甲")]))
+        .await
+        .unwrap();
+    session.update(NS, "tagConnection", &HashMap::<String, Value>::new()).await.unwrap();
+    let err = session
+        .select_one::<Total>(NS, "slow", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert_timeout(err, "slow");
+
+    let err = session
+        .select_one::<Total>(NS, "fast", &HashMap::new())
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("超时中止"));
+    assert!(session.begin().await.is_err(), "中止的事务未回滚前不可再次开启");
+    assert!(session.commit().await.is_err(), "已中止的事务不可提交");
+    assert_eq!(count_items(&factory).await, 0, "超时前的写入应已回滚");
+    // 超时的事务连接被关闭，而不是回滚后归还连接池（取满连接池上限逐一检查）
+    assert!(!pool_has_tagged_connection(&factory, 4).await, "超时的事务连接不应回到连接池");
+
+    // 回滚视为正常结束；新 session 不受影响
+    let mut session = factory.open_session();
+    session.begin().await.unwrap();
+    let total: Option<Total> = session.select_one(NS, "fast", &HashMap::new()).await.unwrap();
+    assert_eq!(total, Some(Total { total: 1 }));
+    session.rollback().await.unwrap();
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -0,0 +1,174 @@
+//! tracing span 集成测试（需 `--features tracing`）
+//!
+//! 用手写的最小 `tracing::Subscriber` 记录 span 名、字段与父子关系，验证每次执行产生带语句
+//! 上下文与结果字段的 `hirust_mapper.sql` span，事务内的 SQL span 挂在 `hirust_mapper.transaction` 下。
+#![cfg(feature = "tracing")]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use hirust_mapper_runtime::telemetry::{SQL_SPAN, TRANSACTION_SPAN};
+use hirust_mapper_runtime::{EnvironmentConfig, HirustMapperConfig, SqlSessionFactory};
+use serde::{Deserialize, Serialize};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: i64,
+    name: String,
+}
+
+const XML: &str = r#"<mapper namespace="app.UserDao">
+    <select id="findAll">SELECT id, name FROM users ORDER BY id</select>
+    <select id="broken">SELECT id FROM missing_table</select>
+    <insert id="insert">INSERT INTO users (name) VALUES (#{name})</insert>
+</mapper>"#;
+
+#[derive(Debug, Clone)]
+struct SpanRecord {
+    name: &'static str,
+    parent: Option<u64>,
+    fields: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct State {
+    spans: Vec<SpanRecord>,
+    stack: Vec<u64>,
+}
+
+/// 记录全部 span 的订阅器（span id 为记录下标 + 1）
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<State>>);
+
+struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let mut state = self.0.lock().unwrap();
+        let parent = match attrs.parent() {
+            Some(id) => Some(id.into_u64()),
+            None if attrs.is_contextual() => state.stack.last().copied(),
+            None => None,
+        };
+        let mut fields = HashMap::new();
+        attrs.record(&mut FieldVisitor(&mut fields));
+        state.spans.push(SpanRecord { name: attrs.metadata().name(), parent, fields });
+        Id::from_u64(state.spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut state = self.0.lock().unwrap();
+        let record = &mut state.spans[span.into_u64() as usize - 1];
+        values.record(&mut FieldVisitor(&mut record.fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        self.0.lock().unwrap().stack.push(span.into_u64());
+    }
+
+    fn exit(&self, _span: &Id) {
+        self.0.lock().unwrap().stack.pop();
+    }
+}
+
+impl Capture {
+    fn spans(&self, name: &str) -> Vec<(u64, SpanRecord)> {
+        let state = self.0.lock().unwrap();
+        state
+            .spans
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.name == name)
+            .map(|(i, s)| (i as u64 + 1, s.clone()))
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn test_sql_and_transaction_spans() {
+    let capture = Capture::default();
+    let _guard = tracing::subscriber::set_default(capture.clone());
+
+    let temp = std::env::temp_dir().join("hirust_tracing_spans");
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("UserDao.xml"), XML).unwrap();
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    let mut session = factory.open_session();
+    let session_id = session.id().to_string();
+    session.insert("app.UserDao", "insert", &User { id: 0, name: "张三".into() }).await.unwrap();
+    assert!(session.select_list::<User>("app.UserDao", "broken", &HashMap::new()).await.is_err());
+    session.begin().await.unwrap();
+    let users: Vec<User> = session.select_list("app.UserDao", "findAll", &HashMap::new()).await.unwrap();
+    assert_eq!(users.len(), 1);
+    session.commit().await.unwrap();
+
+    let sql = capture.spans(SQL_SPAN);
+    let by_id = |id: &str| {
+        sql.iter()
+            .find(|(_, s)| s.fields.get("statement_id").map(String::as_str) == Some(id))
+            .unwrap_or_else(|| panic!("缺少 {id} 的 span: {sql:?}"))
+            .clone()
+    };
+
+    let (_, insert) = by_id("insert");
+    assert_eq!(insert.fields["namespace"], "app.UserDao");
+    assert_eq!(insert.fields["kind"], "INSERT");
+    assert_eq!(insert.fields["session_id"], session_id);
+    assert_eq!(insert.fields["in_transaction"], "false");
+    assert_eq!(insert.fields["rows"], "1");
+    assert!(insert.fields.contains_key("elapsed_ms"));
+
+    let (_, broken) = by_id("broken");
+    assert!(broken.fields["error"].contains("missing_table"), "{broken:?}");
+    assert!(!broken.fields.contains_key("rows"));
+
+    let transactions = capture.spans(TRANSACTION_SPAN);
+    assert_eq!(transactions.len(), 1);
+    let (tx_id, tx) = &transactions[0];
+    assert_eq!(tx.fields["outcome"], "commit");
+    assert_eq!(tx.fields["session_id"], session_id);
+
+    let (_, find_all) = by_id("findAll");
+    assert_eq!(find_all.parent, Some(*tx_id), "事务内的 SQL span 应挂在事务 span 下");
+    assert_eq!(find_all.fields["in_transaction"], "true");
+    assert_eq!(find_all.fields["rows"], "1");
+    assert_eq!(insert.parent, None);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
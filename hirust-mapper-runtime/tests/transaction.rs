@@ -0,0 +1,297 @@
+//! 闭包式事务集成测试
+//!
+//! 验证 `factory.transaction`：Ok 提交、Err / panic 回滚、嵌套调用 SAVEPOINT、
+//! 传播方式（required / requires_new / nested）与 `current_session` 加入环境事务；
+//! 以及 `begin_with` 的隔离级别与只读事务。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use hirust_mapper_runtime::{
+    BeforeSqlEvent, EnvironmentConfig, HirustMapperConfig, IsolationLevel, MapperRuntimeError,
+    Propagation, SqlSessionFactory, TransactionOptions,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct User {
+    id: i64,
+    name: String,
+}
+
+const USER_MAPPER_XML: &str = r#"<mapper namespace="com.test.UserDao">
+    <select id="findAll">SELECT id, name FROM users ORDER BY id</select>
+    <insert id="insert">INSERT INTO users (name) VALUES (#{name})</insert>
+</mapper>"#;
+
+const NS: &str = "com.test.UserDao";
+
+/// 默认使用单连接内存库；RequiresNew 需要第二个连接，`file_db` 时改用文件库
+async fn setup(suffix: &str, file_db: bool) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_tx_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    let mappers_dir = temp.join("mappers");
+    std::fs::create_dir_all(&mappers_dir).unwrap();
+    std::fs::write(mappers_dir.join("UserDao.xml"), USER_MAPPER_XML).unwrap();
+
+    let (url, pool) = if file_db {
+        (format!("sqlite://{}?mode=rwc", temp.join("tx.db").display()), 2)
+    } else {
+        ("sqlite::memory:".to_string(), 1)
+    };
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url,
+            pool_max_connections: pool,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    (factory, temp)
+}
+
+fn user(name: &str) -> User {
+    User { id: 0, name: name.into() }
+}
+
+async fn names(factory: &SqlSessionFactory) -> Vec<String> {
+    let mut session = factory.open_session();
+    let users: Vec<User> = session.select_list(NS, "findAll", &HashMap::new()).await.unwrap();
+    users.into_iter().map(|u| u.name).collect()
+}
+
+fn fail() -> MapperRuntimeError {
+    MapperRuntimeError::Transaction("业务失败".into())
+}
+
+#[tokio::test]
+async fn test_transaction_commits_on_ok() {
+    let (factory, temp) = setup("commit", false).await;
+
+    let id = factory
+        .transaction(|tx| async move {
+            tx.insert(NS, "insert", &user("甲")).await?;
+            tx.insert(NS, "insert", &user("乙")).await
+        })
+        .await
+        .unwrap();
+    assert_eq!(id, Some(2));
+    assert_eq!(names(&factory).await, vec!["甲", "乙"]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_transaction_rolls_back_on_err() {
+    let (factory, temp) = setup("rollback", false).await;
+
+    let res: hirust_mapper_runtime::Result<()> = factory
+        .transaction(|tx| async move {
+            tx.insert(NS, "insert", &user("甲")).await?;
+            Err(fail())
+        })
+        .await;
+    assert!(res.unwrap_err().to_string().contains("业务失败"), "应返回闭包的原始错误");
+    assert!(names(&factory).await.is_empty());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_transaction_rolls_back_on_panic() {
+    let (factory, temp) = setup("panic", false).await;
+
+    let res = futures_util::FutureExt::catch_unwind(std::panic::AssertUnwindSafe(
+        factory.transaction(|tx| async move {
+            tx.insert(NS, "insert", &user("甲")).await?;
+            let explode = true;
+            if explode {
+                panic!("闭包内 panic");
+            }
+            Ok(())
+        }),
+    ))
+    .await;
+    assert!(res.is_err(), "panic 应在回滚后继续传播");
+    assert!(names(&factory).await.is_empty());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 默认（Nested）：内层失败只回滚到保存点，外层继续并提交
+#[tokio::test]
+async fn test_nested_transaction_uses_savepoint() {
+    let (factory, temp) = setup("nested", false).await;
+    let f = &factory;
+
+    f.transaction(|tx| async move {
+        tx.insert(NS, "insert", &user("外层")).await?;
+        let inner: hirust_mapper_runtime::Result<()> = f
+            .transaction(|tx| async move {
+                tx.insert(NS, "insert", &user("内层")).await?;
+                Err(fail())
+            })
+            .await;
+        assert!(inner.is_err());
+        f.transaction(|tx| async move { tx.insert(NS, "insert", &user("内层2")).await })
+            .await?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(names(&factory).await, vec!["外层", "内层2"]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// Required：加入外层事务；内层失败将整个事务标记为仅回滚
+#[tokio::test]
+async fn test_required_marks_rollback_only() {
+    let (factory, temp) = setup("required", false).await;
+    let f = &factory;
+
+    let res = f
+        .transaction(|tx| async move {
+            tx.insert(NS, "insert", &user("外层")).await?;
+            let _ = f
+                .transaction_with(Propagation::Required, |tx| async move {
+                    tx.insert(NS, "insert", &user("内层")).await?;
+                    Err::<(), _>(fail())
+                })
+                .await;
+            assert!(tx.is_rollback_only());
+            Ok(())
+        })
+        .await;
+
+    assert!(res.unwrap_err().to_string().contains("仅回滚"));
+    assert!(names(&factory).await.is_empty());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// RequiresNew：内层在独立连接上提交，不受外层回滚影响
+#[tokio::test]
+async fn test_requires_new_commits_independently() {
+    let (factory, temp) = setup("requires_new", true).await;
+    let f = &factory;
+
+    let res: hirust_mapper_runtime::Result<()> = f
+        .transaction(|_tx| async move {
+            f.transaction_with(Propagation::RequiresNew, |tx| async move {
+                tx.insert(NS, "insert", &user("独立")).await
+            })
+            .await?;
+            Err(fail())
+        })
+        .await;
+    assert!(res.is_err());
+    assert_eq!(names(&factory).await, vec!["独立"]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// current_session：事务闭包内加入环境事务，闭包外打开独立 session
+#[tokio::test]
+async fn test_current_session_joins_ambient_transaction() {
+    let (factory, temp) = setup("current_session", false).await;
+    let f = &factory;
+
+    assert!(!f.current_session().await.is_joined());
+
+    let res: hirust_mapper_runtime::Result<()> = f
+        .transaction(|_tx| async move {
+            let mut s = f.current_session().await;
+            assert!(s.is_joined());
+            assert!(s.in_transaction());
+            s.insert(NS, "insert", &user("共享")).await?;
+            Err(fail())
+        })
+        .await;
+    assert!(res.is_err());
+    assert!(names(&factory).await.is_empty(), "经 current_session 的写入应随事务回滚");
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// 只读事务：写操作在访问数据库前即被拒绝，读操作正常
+#[tokio::test]
+async fn test_read_only_transaction_rejects_writes() {
+    let (factory, temp) = setup("read_only", false).await;
+    let executed = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&executed);
+    factory.event_bus().on(move |_: &BeforeSqlEvent| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    }).detach();
+
+    let mut session = factory.open_session();
+    session
+        .begin_with(TransactionOptions::new().with_read_only(true))
+        .await
+        .unwrap();
+    assert!(session.is_read_only());
+
+    let err = session.insert(NS, "insert", &user("甲")).await.unwrap_err();
+    assert!(matches!(err, MapperRuntimeError::ReadOnlyViolation { .. }));
+    assert!(err.to_string().contains("com.test.UserDao.insert"));
+    assert_eq!(executed.load(Ordering::SeqCst), 0, "写操作不应到达数据库");
+
+    let users: Vec<User> = session.select_list(NS, "findAll", &HashMap::new()).await.unwrap();
+    assert!(users.is_empty());
+    assert_eq!(executed.load(Ordering::SeqCst), 1);
+    session.commit().await.unwrap();
+
+    // 只读标记随事务结束清除
+    let mut session = factory.open_session();
+    session.begin().await.unwrap();
+    assert!(!session.is_read_only());
+    session.insert(NS, "insert", &user("乙")).await.unwrap();
+    session.commit().await.unwrap();
+    assert_eq!(names(&factory).await, vec!["乙"]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+/// sqlite：指定隔离级别时以 BEGIN IMMEDIATE / EXCLUSIVE 开启，事务可正常提交
+#[tokio::test]
+async fn test_begin_with_isolation_level() {
+    let (factory, temp) = setup("isolation", false).await;
+
+    for level in [IsolationLevel::ReadCommitted, IsolationLevel::Serializable] {
+        let mut session = factory.open_session();
+        session
+            .begin_with(TransactionOptions::new().with_isolation(level))
+            .await
+            .unwrap();
+        session.insert(NS, "insert", &user(level.as_sql())).await.unwrap();
+        session.commit().await.unwrap();
+    }
+    assert_eq!(names(&factory).await, vec!["READ COMMITTED", "SERIALIZABLE"]);
+
+    // 已开启事务时再次 begin_with 报错
+    let mut session = factory.open_session();
+    session.begin().await.unwrap();
+    assert!(session.begin_with(TransactionOptions::default()).await.is_err());
+    session.rollback().await.unwrap();
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
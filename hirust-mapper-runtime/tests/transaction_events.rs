@@ -0,0 +1,191 @@
+//! 事务生命周期事件与提交 / 回滚回调集成测试
+//!
+//! 验证 `begin` / `commit` / `rollback` / `close` / drop 派发 `TransactionBeginEvent` /
+//! `TransactionCommitEvent` / `TransactionRollbackEvent`，`after_commit` 只在提交成功后执行、
+//! `after_rollback` 只在回滚后执行，闭包式事务中回滚到保存点会丢弃其后登记的 `after_commit` 回调。
+
+use std::sync::{Arc, Mutex};
+
+use hirust_mapper_runtime::{
+    EnvironmentConfig, HirustMapperConfig, MapperRuntimeError, RollbackReason, SqlSessionFactory,
+    TransactionBeginEvent, TransactionCommitEvent, TransactionOptions, TransactionRollbackEvent,
+};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct User {
+    name: String,
+}
+
+const XML: &str = r#"<mapper namespace="app.UserDao">
+    <insert id="insert">INSERT INTO users (name) VALUES (#{name})</insert>
+</mapper>"#;
+
+const NS: &str = "app.UserDao";
+
+async fn setup(suffix: &str) -> (SqlSessionFactory, std::path::PathBuf) {
+    let temp = std::env::temp_dir().join(format!("hirust_tx_events_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    std::fs::create_dir_all(temp.join("mappers")).unwrap();
+    std::fs::write(temp.join("mappers").join("UserDao.xml"), XML).unwrap();
+    let config = HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()]);
+    let factory = SqlSessionFactory::build(config, &temp).await.unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY AUTOINCREMENT, name TEXT)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+    (factory, temp)
+}
+
+fn user(name: &str) -> User {
+    User { name: name.into() }
+}
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+/// 把事务事件与回调按发生顺序记入同一日志
+fn collect(factory: &SqlSessionFactory) -> Log {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let bus = factory.event_bus();
+    let sink = Arc::clone(&log);
+    bus.on(move |e: &TransactionBeginEvent| {
+        sink.lock().unwrap().push(format!("begin#{} read_only={}", e.session_id, e.options.read_only))
+    })
+    .detach();
+    let sink = Arc::clone(&log);
+    bus.on(move |e: &TransactionCommitEvent| sink.lock().unwrap().push(format!("commit#{}", e.session_id)))
+        .detach();
+    let sink = Arc::clone(&log);
+    bus.on(move |e: &TransactionRollbackEvent| {
+        sink.lock().unwrap().push(format!("rollback#{} {:?}", e.session_id, e.reason))
+    })
+    .detach();
+    log
+}
+
+fn hook(log: &Log, entry: &str) -> impl FnOnce() + Send + 'static {
+    let log = Arc::clone(log);
+    let entry = entry.to_string();
+    move || log.lock().unwrap().push(entry)
+}
+
+fn take(log: &Log) -> Vec<String> {
+    std::mem::take(&mut *log.lock().unwrap())
+}
+
+#[tokio::test]
+async fn test_session_transaction_events_and_hooks() {
+    let (factory, temp) = setup("session").await;
+    let log = collect(&factory);
+
+    // 提交：只执行 after_commit，且在提交事件之后
+    let mut session = factory.open_session();
+    let id = session.id();
+    session.begin_with(TransactionOptions::new().with_read_only(false)).await.unwrap();
+    session.insert(NS, "insert", &user("张三")).await.unwrap();
+    session.after_commit(hook(&log, "after_commit"));
+    session.after_rollback(hook(&log, "after_rollback"));
+    assert_eq!(take(&log), vec![format!("begin#{id} read_only=false")], "提交前不执行回调");
+    session.commit().await.unwrap();
+    assert_eq!(take(&log), vec![format!("commit#{id}"), "after_commit".to_string()]);
+
+    // 显式回滚：只执行 after_rollback
+    let mut session = factory.open_session();
+    let id = session.id();
+    session.begin().await.unwrap();
+    session.after_commit(hook(&log, "after_commit"));
+    session.after_rollback(hook(&log, "after_rollback"));
+    session.rollback().await.unwrap();
+    assert_eq!(
+        take(&log),
+        vec![format!("begin#{id} read_only=false"), format!("rollback#{id} Requested"), "after_rollback".to_string()]
+    );
+
+    // close 回滚未提交事务
+    let mut session = factory.open_session();
+    let id = session.id();
+    session.begin().await.unwrap();
+    session.close().await.unwrap();
+    assert_eq!(take(&log)[1], format!("rollback#{id} Closed"));
+
+    // 未提交即 drop
+    let mut session = factory.open_session();
+    let id = session.id();
+    session.begin().await.unwrap();
+    session.after_rollback(hook(&log, "after_rollback"));
+    drop(session);
+    assert_eq!(take(&log)[1..], [format!("rollback#{id} Dropped"), "after_rollback".to_string()]);
+
+    // 无事务：after_commit 立即执行，after_rollback 丢弃
+    let mut session = factory.open_session();
+    session.after_rollback(hook(&log, "after_rollback"));
+    session.after_commit(hook(&log, "after_commit"));
+    assert_eq!(take(&log), vec!["after_commit"]);
+    drop(session);
+    assert!(take(&log).is_empty());
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_closure_transaction_hooks_follow_savepoints() {
+    let (factory, temp) = setup("closure").await;
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let f = &factory;
+    let l = &log;
+
+    f.transaction(|tx| async move {
+        tx.insert(NS, "insert", &user("外层")).await?;
+        tx.after_commit(hook(l, "outer")).await;
+        // 嵌套（保存点）失败：其中登记的 after_commit 被丢弃
+        let inner: hirust_mapper_runtime::Result<()> = f
+            .transaction(|tx| async move {
+                tx.after_commit(hook(l, "inner")).await;
+                Err(MapperRuntimeError::Transaction("业务失败".into()))
+            })
+            .await;
+        assert!(inner.is_err());
+        f.transaction(|tx| async move {
+            tx.after_commit(hook(l, "inner2")).await;
+            Ok(())
+        })
+        .await?;
+        assert!(take(l).is_empty(), "外层提交前不执行");
+        Ok(())
+    })
+    .await
+    .unwrap();
+    assert_eq!(take(&log), vec!["outer", "inner2"]);
+
+    // 外层失败：after_commit 丢弃，after_rollback 执行
+    let result: hirust_mapper_runtime::Result<()> = f
+        .transaction(|tx| async move {
+            tx.after_commit(hook(l, "after_commit")).await;
+            tx.after_rollback(hook(l, "after_rollback")).await;
+            Err(MapperRuntimeError::Transaction("业务失败".into()))
+        })
+        .await;
+    assert!(result.is_err());
+    assert_eq!(take(&log), vec!["after_rollback"]);
+
+    // 事件中的回滚原因
+    let reasons = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&reasons);
+    let _listener = factory
+        .event_bus()
+        .on(move |e: &TransactionRollbackEvent| sink.lock().unwrap().push(e.reason.clone()));
+    let _: hirust_mapper_runtime::Result<()> =
+        f.transaction(|_| async { Err(MapperRuntimeError::Transaction("业务失败".into())) }).await;
+    assert_eq!(*reasons.lock().unwrap(), vec![RollbackReason::Requested]);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -0,0 +1,186 @@
+//! 自定义类型处理器集成测试
+//!
+//! 验证 `SqlSessionFactory::builder(..).type_handler(..)` 按名称注册的处理器、
+//! `[[type_handlers]]` 的类型绑定同时作用于参数绑定（`#{x, typeHandler=..}` / `rustType`）
+//! 与 ResultMap 结果读取，以及引用未注册处理器时构建失败。
+
+use std::collections::HashMap;
+
+use hirust_mapper_runtime::{
+    EnvironmentConfig, HirustMapperConfig, MapperRuntimeError, SqlSessionFactory, TypeHandler,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::any::{AnyArguments, AnyRow};
+use sqlx::{Arguments, Row};
+
+/// 金额处理器：库中以「分」存整数，对外为两位小数的字符串（"12.34"）
+struct MoneyHandler;
+
+impl TypeHandler for MoneyHandler {
+    fn type_name(&self) -> &'static str {
+        "money"
+    }
+
+    fn get_result(&self, row: &AnyRow, column: &str) -> hirust_mapper_runtime::Result<Value> {
+        let cents: Option<i64> = row
+            .try_get(column)
+            .map_err(|e| MapperRuntimeError::TypeConversion(e.to_string()))?;
+        Ok(cents
+            .map(|c| Value::String(format!("{}.{:02}", c / 100, c % 100)))
+            .unwrap_or(Value::Null))
+    }
+
+    fn set_parameter(&self, value: &Value, arguments: &mut AnyArguments) -> hirust_mapper_runtime::Result<()> {
+        let cents = match value {
+            Value::Null => None,
+            Value::String(s) => {
+                let (yuan, fen) = s.split_once('.').unwrap_or((s, "0"));
+                let parse = |t: &str| {
+                    t.parse::<i64>()
+                        .map_err(|e| MapperRuntimeError::TypeConversion(format!("金额 '{}': {}", s, e)))
+                };
+                Some(parse(yuan)? * 100 + parse(&format!("{:0<2}", fen))?)
+            }
+            other => {
+                return Err(MapperRuntimeError::TypeConversion(format!("金额须为字符串: {}", other)));
+            }
+        };
+        arguments
+            .add(cents)
+            .map_err(|e| MapperRuntimeError::TypeConversion(e.to_string()))
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Order {
+    id: i64,
+    price: String,
+    discount: String,
+}
+
+const MAPPER_XML: &str = r#"<mapper namespace="o">
+    <resultMap id="orderMap" type="Order">
+        <id property="id" column="id"/>
+        <result property="price" column="price" typeHandler="MoneyHandler"/>
+        <result property="discount" column="discount" rustType="Money"/>
+    </resultMap>
+    <insert id="insert">
+        INSERT INTO orders (id, price, discount)
+        VALUES (#{id}, #{price, typeHandler=MoneyHandler}, #{discount, rustType=myapp::Money})
+    </insert>
+    <select id="findById" resultMap="orderMap">
+        SELECT id, price, discount FROM orders WHERE id = #{id}
+    </select>
+    <select id="findByPrice" resultMap="orderMap">
+        SELECT id, price, discount FROM orders WHERE price = #{price, typeHandler=MoneyHandler}
+    </select>
+</mapper>"#;
+
+fn config(temp: &std::path::Path) -> HirustMapperConfig {
+    let mappers_dir = temp.join("mappers");
+    std::fs::create_dir_all(&mappers_dir).unwrap();
+    std::fs::write(mappers_dir.join("O.xml"), MAPPER_XML).unwrap();
+    HirustMapperConfig::new()
+        .with_environment(EnvironmentConfig {
+            driver: "sqlite".into(),
+            url: "sqlite::memory:".into(),
+            pool_max_connections: 1,
+            pool_min_connections: 1,
+        })
+        .with_mapper_paths(vec!["mappers/**/*.xml".to_string()])
+}
+
+fn temp_dir(suffix: &str) -> std::path::PathBuf {
+    let temp = std::env::temp_dir().join(format!("hirust_type_handlers_{}", suffix));
+    std::fs::remove_dir_all(&temp).ok();
+    temp
+}
+
+#[tokio::test]
+async fn test_named_handler_applies_to_params_and_results() {
+    let temp = temp_dir("roundtrip");
+    let config = config(&temp).with_type_handler("myapp::Money", "myapp::MoneyHandler");
+    let factory = SqlSessionFactory::builder(config, &temp)
+        .type_handler("MoneyHandler", MoneyHandler)
+        .build()
+        .await
+        .unwrap();
+    let pool = factory.environment().pool();
+    sqlx::query("CREATE TABLE orders (id INTEGER, price INTEGER, discount INTEGER)")
+        .execute(pool)
+        .await
+        .unwrap();
+
+    let mut session = factory.open_session();
+    session
+        .insert("o", "insert", &json!({"id": 1, "price": "12.34", "discount": "0.5"}))
+        .await
+        .unwrap();
+
+    // 参数经处理器以「分」写入
+    let raw: (i64, i64) = sqlx::query_as("SELECT price, discount FROM orders WHERE id = 1")
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    assert_eq!(raw, (1234, 50));
+
+    // 结果经处理器还原为字符串（typeHandler 名称 / rustType 短名两种引用）
+    let order: Option<Order> = session
+        .select_one("o", "findById", &HashMap::from([("id".to_string(), Value::from(1))]))
+        .await
+        .unwrap();
+    assert_eq!(
+        order,
+        Some(Order { id: 1, price: "12.34".into(), discount: "0.50".into() })
+    );
+
+    // 查询条件同样经处理器绑定
+    let by_price: Vec<Order> = session
+        .select_list("o", "findByPrice", &HashMap::from([("price".to_string(), json!("12.34"))]))
+        .await
+        .unwrap();
+    assert_eq!(by_price.len(), 1);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_unregistered_handler_fails_build() {
+    let temp = temp_dir("unknown");
+    let config = config(&temp).with_type_handler("myapp::Money", "myapp::MoneyHandler");
+
+    let err = SqlSessionFactory::build(config.clone(), &temp).await.unwrap_err();
+    assert!(matches!(err, MapperRuntimeError::Config(_)));
+    assert!(err.to_string().contains("myapp::MoneyHandler"), "{}", err);
+
+    let err = SqlSessionFactory::builder(config, &temp)
+        .type_handler("OtherHandler", MoneyHandler)
+        .build()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("未注册的类型处理器"), "{}", err);
+
+    std::fs::remove_dir_all(temp).ok();
+}
+
+#[tokio::test]
+async fn test_unregistered_parameter_handler_errors() {
+    let temp = temp_dir("param");
+    let factory = SqlSessionFactory::build(config(&temp), &temp).await.unwrap();
+    sqlx::query("CREATE TABLE orders (id INTEGER, price INTEGER, discount INTEGER)")
+        .execute(factory.environment().pool())
+        .await
+        .unwrap();
+
+    let mut session = factory.open_session();
+    let err = session
+        .select_list::<Value>("o", "findByPrice", &HashMap::from([("price".to_string(), json!("1.00"))]))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("'MoneyHandler' 未注册"), "{}", err);
+
+    factory.close().await;
+    std::fs::remove_dir_all(temp).ok();
+}
@@ -43,28 +43,28 @@ impl Subscriber for AuditSubscriber {
     fn subscribe(&self, bus: &EventBus) {
         // 执行前：观察即将运行的 SQL
         bus.on(|e: &BeforeSqlEvent| {
-            println!("[BEFORE] kind={:?} params={}", e.kind, e.params.len());
-        });
+            println!("[BEFORE] {} params={}", e.statement, e.params.len());
+        }).detach();
         // 执行后：记录耗时与结果摘要
         let counter = Arc::clone(&self.sql_count);
         bus.on(move |e: &AfterSqlEvent| {
             counter.fetch_add(1, Ordering::Relaxed);
             match &e.outcome {
                 SqlOutcome::Fetched(n) => println!(
-                    "[AFTER ] kind={:?} | {:>3} ms | fetched {}",
-                    e.kind,
+                    "[AFTER ] {} | {:>3} ms | fetched {}",
+                    e.statement,
                     e.elapsed.as_millis(),
                     n
                 ),
                 SqlOutcome::Affected(n) => println!(
-                    "[AFTER ] kind={:?} | {:>3} ms | affected {}",
-                    e.kind,
+                    "[AFTER ] {} | {:>3} ms | affected {}",
+                    e.statement,
                     e.elapsed.as_millis(),
                     n
                 ),
-                SqlOutcome::Failed(err) => println!("[AFTER ] kind={:?} | FAILED: {}", e.kind, err),
+                SqlOutcome::Failed(err) => println!("[AFTER ] {} | FAILED: {}", e.statement, err),
             }
-        });
+        }).detach();
     }
 }
 
@@ -105,7 +105,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // 3b) 同一总线也可派发自定义业务事件
     factory
         .event_bus()
-        .on(|e: &LoginEvent| println!("[LOGIN] {} 登录了", e.user));
+        .on(|e: &LoginEvent| println!("[LOGIN] {} 登录了", e.user)).detach();
     factory.event_bus().dispatch(&LoginEvent { user: "张三".into() });
 
     println!("--- ORM 操作开始 ---");